@@ -0,0 +1,310 @@
+use crate::scalar::Scalar;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// An absolute and a relative tolerance for comparing computed values.
+/// Two values a and b are close when
+/// |a - b| <= max(abs, rel * max(|a|, |b|)).
+pub struct Tolerance {
+    pub abs: f64,
+    pub rel: f64,
+}
+
+impl Default for Tolerance {
+    /// An absolute tolerance of 1e-12 and a relative tolerance of 1e-9
+    fn default() -> Tolerance {
+        Tolerance::new(1e-12, 1e-9)
+    }
+}
+
+impl Tolerance {
+    /// Creates a tolerance
+    ///
+    /// # Arguments
+    /// `abs` - The absolute tolerance, used near zero.
+    /// `rel` - The relative tolerance, scaled by the size of the values.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::approx::Tolerance;
+    /// let t = Tolerance::new(1e-12, 1e-6);
+    /// assert!(t.close(1e6, 1e6 + 0.5));
+    /// assert!(!t.close(1., 1.5));
+    /// ```
+    pub fn new(abs: f64, rel: f64) -> Tolerance {
+        Tolerance { abs, rel }
+    }
+
+    /// Creates a purely absolute tolerance
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::approx::Tolerance;
+    /// assert!(Tolerance::absolute(0.1).close(1., 1.05));
+    /// ```
+    pub fn absolute(abs: f64) -> Tolerance {
+        Tolerance::new(abs, 0.)
+    }
+
+    /// Creates a purely relative tolerance
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::approx::Tolerance;
+    /// assert!(Tolerance::relative(0.01).close(100., 100.5));
+    /// assert!(!Tolerance::relative(0.01).close(0., 1e-300));
+    /// ```
+    pub fn relative(rel: f64) -> Tolerance {
+        Tolerance::new(0., rel)
+    }
+
+    /// A zero tolerance, for exact types such as `Rational`
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::approx::Tolerance;
+    /// assert!(!Tolerance::exact().close(0.1 + 0.2, 0.3));
+    /// ```
+    pub fn exact() -> Tolerance {
+        Tolerance::new(0., 0.)
+    }
+
+    /// Returns whether 2 values are within the tolerance of each other
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::approx::Tolerance;
+    /// assert!(Tolerance::default().close(0.1 + 0.2, 0.3));
+    /// ```
+    pub fn close<T: Scalar>(&self, a: T, b: T) -> bool {
+        let scale = a.magnitude().max(b.magnitude());
+        (a - b).magnitude() <= self.abs.max(self.rel * scale)
+    }
+
+    /// Returns whether a value is zero up to the tolerance, where the
+    /// relative part is taken with respect to `scale`, usually the
+    /// size of the largest element around it
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::approx::Tolerance;
+    /// let t = Tolerance::relative(1e-9);
+    /// assert!(t.negligible(1e-4, 1e6));
+    /// assert!(!t.negligible(1e-4, 1.));
+    /// ```
+    pub fn negligible<T: Scalar>(&self, x: T, scale: f64) -> bool {
+        x.magnitude() <= self.abs.max(self.rel * scale)
+    }
+}
+
+/// Approximate equality for values computed in floating point.
+///
+/// `approx_eq` compares with a `Tolerance`, the absolute and relative
+/// comparisons are shorthands for it, and `ulps_eq` counts the number of
+/// representable f64 values between the two. Compound types such as
+/// `Point`, `Line`, `Circle` and `Matrix` compare element by element.
+///
+/// # Examples
+/// ```
+/// use ralgeb::approx::{ApproxEq, Tolerance};
+/// assert!((0.1 + 0.2).approx_eq(&0.3, Tolerance::default()));
+/// assert!(1f64.abs_diff_eq(&1.05, 0.1));
+/// assert!(100f64.relative_eq(&100.5, 0.01));
+/// assert!((0.1 + 0.2).ulps_eq(&0.3, 1));
+/// ```
+pub trait ApproxEq {
+    /// Returns whether the values are equal within the tolerance
+    fn approx_eq(&self, other: &Self, tolerance: Tolerance) -> bool;
+
+    /// Returns whether the values are at most `ulps` representable
+    /// f64 values apart
+    fn ulps_eq(&self, other: &Self, ulps: u64) -> bool;
+
+    /// Returns whether the values differ by at most `epsilon`
+    fn abs_diff_eq(&self, other: &Self, epsilon: f64) -> bool {
+        self.approx_eq(other, Tolerance::absolute(epsilon))
+    }
+
+    /// Returns whether the values differ by at most `max_relative`
+    /// times the larger of the two
+    fn relative_eq(&self, other: &Self, max_relative: f64) -> bool {
+        self.approx_eq(other, Tolerance::relative(max_relative))
+    }
+}
+
+impl ApproxEq for f64 {
+    fn approx_eq(&self, other: &f64, tolerance: Tolerance) -> bool {
+        self == other || tolerance.close(*self, *other)
+    }
+
+    fn ulps_eq(&self, other: &f64, ulps: u64) -> bool {
+        if self == other {
+            return true;
+        }
+        if self.is_nan() || other.is_nan() || self.is_sign_positive() != other.is_sign_positive() {
+            return false;
+        }
+        let (a, b) = (self.to_bits(), other.to_bits());
+        a.max(b) - a.min(b) <= ulps
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for [T] {
+    fn approx_eq(&self, other: &[T], tolerance: Tolerance) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .zip(other)
+                .all(|(a, b)| a.approx_eq(b, tolerance))
+    }
+
+    fn ulps_eq(&self, other: &[T], ulps: u64) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a.ulps_eq(b, ulps))
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for Vec<T> {
+    fn approx_eq(&self, other: &Vec<T>, tolerance: Tolerance) -> bool {
+        self[..].approx_eq(&other[..], tolerance)
+    }
+
+    fn ulps_eq(&self, other: &Vec<T>, ulps: u64) -> bool {
+        self[..].ulps_eq(&other[..], ulps)
+    }
+}
+
+/// Asserts that 2 values are approximately equal using `ApproxEq`.
+/// Takes an optional `Tolerance`, `Tolerance::default()` otherwise.
+///
+/// # Examples
+/// ```
+/// use ralgeb::assert_approx_eq;
+/// use ralgeb::approx::Tolerance;
+/// assert_approx_eq!(0.1 + 0.2, 0.3);
+/// assert_approx_eq!(vec![1., 2.], vec![1.05, 2.], Tolerance::absolute(0.1));
+/// ```
+#[macro_export]
+macro_rules! assert_approx_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_approx_eq!($left, $right, $crate::approx::Tolerance::default())
+    };
+    ($left:expr, $right:expr, $tolerance:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if !$crate::approx::ApproxEq::approx_eq(left, right, $tolerance) {
+                    panic!(
+                        "assertion failed: `left ≈ right` with {:?}\n  left: `{:?}`\n right: `{:?}`",
+                        $tolerance, left, right
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// Asserts that 2 values differ by at most an absolute epsilon
+///
+/// # Examples
+/// ```
+/// use ralgeb::assert_abs_diff_eq;
+/// assert_abs_diff_eq!(1., 1.05, 0.1);
+/// ```
+#[macro_export]
+macro_rules! assert_abs_diff_eq {
+    ($left:expr, $right:expr, $epsilon:expr $(,)?) => {
+        $crate::assert_approx_eq!($left, $right, $crate::approx::Tolerance::absolute($epsilon))
+    };
+}
+
+/// Asserts that 2 values differ by at most a relative tolerance
+///
+/// # Examples
+/// ```
+/// use ralgeb::assert_relative_eq;
+/// assert_relative_eq!(1e10, 1e10 + 1., 1e-9);
+/// ```
+#[macro_export]
+macro_rules! assert_relative_eq {
+    ($left:expr, $right:expr, $max_relative:expr $(,)?) => {
+        $crate::assert_approx_eq!(
+            $left,
+            $right,
+            $crate::approx::Tolerance::relative($max_relative)
+        )
+    };
+}
+
+/// Asserts that 2 values are at most a number of ULPs apart
+///
+/// # Examples
+/// ```
+/// use ralgeb::assert_ulps_eq;
+/// assert_ulps_eq!(0.1 + 0.2, 0.3, 1);
+/// ```
+#[macro_export]
+macro_rules! assert_ulps_eq {
+    ($left:expr, $right:expr, $ulps:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if !$crate::approx::ApproxEq::ulps_eq(left, right, $ulps) {
+                    panic!(
+                        "assertion failed: `left ≈ right` within {} ulps\n  left: `{:?}`\n right: `{:?}`",
+                        $ulps, left, right
+                    );
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::approx::{ApproxEq, Tolerance};
+    use crate::rational::Rational;
+
+    #[test]
+    fn tolerance() {
+        let t = Tolerance::default();
+        assert!(t.close(1e-13, 0.));
+        assert!(!t.close(1e-11, 0.));
+        assert!(t.close(1e9, 1e9 + 0.5));
+        assert!(!t.close(1e9, 1e9 + 2.));
+        assert!(!t.close(f64::NAN, f64::NAN));
+
+        let e = Tolerance::exact();
+        assert!(e.close(Rational::new(1, 3), Rational::new(2, 6)));
+        assert!(!e.close(Rational::new(1, 3), Rational::new(1, 4)));
+        assert!(e.negligible(Rational::from_integer(0), 10.));
+    }
+
+    #[test]
+    fn approx_eq_f64() {
+        assert!(1f64.abs_diff_eq(&(1. + 1e-9), 1e-8));
+        assert!(!1f64.abs_diff_eq(&1.1, 1e-8));
+        assert!(f64::INFINITY.approx_eq(&f64::INFINITY, Tolerance::exact()));
+        assert!(!f64::NAN.approx_eq(&f64::NAN, Tolerance::default()));
+
+        assert!(1f64.ulps_eq(&(1. + f64::EPSILON), 1));
+        assert!(!1f64.ulps_eq(&(1. + 2. * f64::EPSILON), 1));
+        assert!(0f64.ulps_eq(&-0f64, 0));
+        assert!(!f64::MIN_POSITIVE.ulps_eq(&-f64::MIN_POSITIVE, u64::MAX));
+        assert!(!f64::NAN.ulps_eq(&f64::NAN, u64::MAX));
+    }
+
+    #[test]
+    fn approx_eq_vectors() {
+        let a = vec![1., 2., 3.];
+        let b = vec![1. + 1e-14, 2., 3. - 1e-14];
+        assert!(a.approx_eq(&b, Tolerance::default()));
+        assert!(!a.approx_eq(&b, Tolerance::exact()));
+        assert!(!a.approx_eq(&vec![1., 2.], Tolerance::default()));
+        assert!(a[..2].ulps_eq(&b[..2], 100));
+        crate::assert_approx_eq!(a, b);
+        crate::assert_relative_eq!(a, b, 1e-12);
+    }
+
+    #[test]
+    #[should_panic(expected = "left ≈ right")]
+    fn assert_macro_panics() {
+        crate::assert_abs_diff_eq!(1., 1.1, 0.01);
+    }
+}
@@ -0,0 +1,319 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// BigUint represents an arbitrarily large non-negative integer.
+/// It is stored as base 2⁶⁴ digits, least significant first,
+/// without leading zero digits.
+///
+/// # Examples
+/// 2⁶⁴ + 5 is stored as [5, 1]
+pub struct BigUint {
+    digits: Vec<u64>,
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Peel off base 10¹⁹ chunks, the largest power of 10 in a u64
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+        let mut chunks = Vec::new();
+        let mut rest = self.clone();
+        while !rest.is_zero() {
+            let (q, r) = rest.div_rem_u64(CHUNK);
+            chunks.push(r);
+            rest = q;
+        }
+        match chunks.pop() {
+            None => write!(f, "0"),
+            Some(top) => {
+                write!(f, "{}", top)?;
+                for c in chunks.iter().rev() {
+                    write!(f, "{:019}", c)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl BigUint {
+    /// Returns 0
+    ///
+    /// ```
+    /// use ralgeb::biguint::BigUint;
+    /// assert!(BigUint::zero().is_zero());
+    /// ```
+    pub fn zero() -> BigUint {
+        BigUint { digits: Vec::new() }
+    }
+
+    /// Returns 1
+    ///
+    /// ```
+    /// use ralgeb::biguint::BigUint;
+    /// assert_eq!(BigUint::one(), BigUint::from(1));
+    /// ```
+    pub fn one() -> BigUint {
+        BigUint::from(1)
+    }
+
+    /// Returns true if the value is 0
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    /// Returns the value as a u64 or None if it does not fit
+    ///
+    /// ```
+    /// use ralgeb::biguint::BigUint;
+    /// assert_eq!(BigUint::from(7).to_u64(), Some(7));
+    /// assert_eq!((BigUint::from(u64::MAX) + BigUint::one()).to_u64(), None);
+    /// ```
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.digits.len() {
+            0 => Some(0),
+            1 => Some(self.digits[0]),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of bits needed to represent the value
+    ///
+    /// ```
+    /// use ralgeb::biguint::BigUint;
+    /// assert_eq!(BigUint::from(255).bits(), 8);
+    /// assert_eq!(BigUint::zero().bits(), 0);
+    /// ```
+    pub fn bits(&self) -> u64 {
+        match self.digits.last() {
+            None => 0,
+            Some(top) => 64 * (self.digits.len() as u64 - 1) + (64 - top.leading_zeros() as u64),
+        }
+    }
+
+    /// Returns self raised to the power exp
+    ///
+    /// ```
+    /// use ralgeb::biguint::BigUint;
+    /// assert_eq!(BigUint::from(2).pow(100).to_string(), "1267650600228229401496703205376");
+    /// ```
+    pub fn pow(&self, mut exp: u32) -> BigUint {
+        let mut base = self.clone();
+        let mut result = BigUint::one();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = &result * &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    /// Multiplies the value in place by a u64
+    ///
+    /// ```
+    /// use ralgeb::biguint::BigUint;
+    /// let mut n = BigUint::from(u64::MAX);
+    /// n.mul_u64(2);
+    /// assert_eq!(n.to_string(), "36893488147419103230");
+    /// ```
+    pub fn mul_u64(&mut self, m: u64) {
+        if m == 0 {
+            self.digits.clear();
+            return;
+        }
+        let mut carry = 0u128;
+        for d in self.digits.iter_mut() {
+            let v = *d as u128 * m as u128 + carry;
+            *d = v as u64;
+            carry = v >> 64;
+        }
+        if carry > 0 {
+            self.digits.push(carry as u64);
+        }
+    }
+
+    /// Returns the quotient and the remainder of the division by a
+    /// non-zero u64
+    ///
+    /// ```
+    /// use ralgeb::biguint::BigUint;
+    /// let (q, r) = BigUint::from(100).div_rem_u64(7);
+    /// assert_eq!((q.to_u64(), r), (Some(14), 2));
+    /// ```
+    pub fn div_rem_u64(&self, d: u64) -> (BigUint, u64) {
+        if d == 0 {
+            panic!("BigUint division by zero");
+        }
+        let mut quotient = vec![0; self.digits.len()];
+        let mut rem = 0u128;
+        for (q, digit) in quotient.iter_mut().zip(self.digits.iter()).rev() {
+            let v = (rem << 64) | *digit as u128;
+            *q = (v / d as u128) as u64;
+            rem = v % d as u128;
+        }
+        let mut q = BigUint { digits: quotient };
+        q.trim();
+        (q, rem as u64)
+    }
+
+    /// Removes leading zero digits
+    fn trim(&mut self) {
+        while self.digits.last() == Some(&0) {
+            self.digits.pop();
+        }
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(n: u64) -> BigUint {
+        let mut b = BigUint { digits: vec![n] };
+        b.trim();
+        b
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &BigUint) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &BigUint) -> Ordering {
+        self.digits
+            .len()
+            .cmp(&other.digits.len())
+            .then_with(|| self.digits.iter().rev().cmp(other.digits.iter().rev()))
+    }
+}
+
+impl<'a> Add<&'a BigUint> for &'a BigUint {
+    type Output = BigUint;
+    fn add(self, rhs: &BigUint) -> BigUint {
+        let (long, short) = if self.digits.len() >= rhs.digits.len() {
+            (self, rhs)
+        } else {
+            (rhs, self)
+        };
+        let mut digits = Vec::with_capacity(long.digits.len() + 1);
+        let mut carry = false;
+        for (i, a) in long.digits.iter().enumerate() {
+            let b = short.digits.get(i).cloned().unwrap_or(0);
+            let (s, c1) = a.overflowing_add(b);
+            let (s, c2) = s.overflowing_add(carry as u64);
+            digits.push(s);
+            carry = c1 || c2;
+        }
+        if carry {
+            digits.push(1);
+        }
+        BigUint { digits }
+    }
+}
+
+impl Add for BigUint {
+    type Output = BigUint;
+    fn add(self, rhs: BigUint) -> BigUint {
+        &self + &rhs
+    }
+}
+
+impl<'a> Mul<&'a BigUint> for &'a BigUint {
+    type Output = BigUint;
+    fn mul(self, rhs: &BigUint) -> BigUint {
+        if self.is_zero() || rhs.is_zero() {
+            return BigUint::zero();
+        }
+        let mut digits = vec![0u64; self.digits.len() + rhs.digits.len()];
+        for (i, a) in self.digits.iter().enumerate() {
+            let mut carry = 0u128;
+            for (j, b) in rhs.digits.iter().enumerate() {
+                let v = *a as u128 * *b as u128 + digits[i + j] as u128 + carry;
+                digits[i + j] = v as u64;
+                carry = v >> 64;
+            }
+            digits[i + rhs.digits.len()] = carry as u64;
+        }
+        let mut product = BigUint { digits };
+        product.trim();
+        product
+    }
+}
+
+impl Mul for BigUint {
+    type Output = BigUint;
+    fn mul(self, rhs: BigUint) -> BigUint {
+        &self * &rhs
+    }
+}
+
+impl Mul<u64> for BigUint {
+    type Output = BigUint;
+    fn mul(mut self, rhs: u64) -> BigUint {
+        self.mul_u64(rhs);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::biguint::BigUint;
+
+    #[test]
+    fn display() {
+        assert_eq!(BigUint::zero().to_string(), "0");
+        assert_eq!(BigUint::from(0).to_string(), "0");
+        assert_eq!(BigUint::from(u64::MAX).to_string(), "18446744073709551615");
+        // A chunk with leading zeros must be padded
+        let n = BigUint::from(10_000_000_000_000_000_000) * 10 + BigUint::from(7);
+        assert_eq!(n.to_string(), "100000000000000000007");
+    }
+
+    #[test]
+    fn arithmetic() {
+        let max = BigUint::from(u64::MAX);
+        let sum = &max + &BigUint::one();
+        assert_eq!(sum.bits(), 65);
+        assert_eq!(sum.to_string(), "18446744073709551616");
+        assert_eq!(&max + &BigUint::zero(), max);
+        let square = &max * &max;
+        assert_eq!(
+            square.to_string(),
+            "340282366920938463426481119284349108225"
+        );
+        assert_eq!(&square * &BigUint::zero(), BigUint::zero());
+        let mut erased = max.clone();
+        erased.mul_u64(0);
+        assert!(erased.is_zero());
+        assert_eq!(BigUint::from(3).pow(0), BigUint::one());
+        assert_eq!(
+            BigUint::from(3).pow(81).to_string(),
+            "443426488243037769948249630619149892803"
+        );
+        let (q, r) = square.div_rem_u64(u64::MAX);
+        assert_eq!((q, r), (max.clone(), 0));
+        let (q, r) = BigUint::zero().div_rem_u64(3);
+        assert_eq!((q, r), (BigUint::zero(), 0));
+    }
+
+    #[test]
+    fn ordering() {
+        let small = BigUint::from(5);
+        let big = BigUint::from(2).pow(70);
+        assert!(small < big);
+        assert!(big > BigUint::from(2).pow(69));
+        assert!(BigUint::from(2).pow(69) * 3 > big);
+        assert_eq!(big.cmp(&big.clone()), std::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    #[should_panic]
+    fn division_by_zero() {
+        BigUint::one().div_rem_u64(0);
+    }
+}
@@ -1,12 +1,16 @@
+use crate::approx::{ApproxEq, Tolerance};
 use crate::point::Point;
+use crate::scalar::Real;
+use std::f64::consts::PI;
 
+#[derive(Debug)]
 /// Represents a circle
-pub struct Circle {
-    pub radius: f64,
-    pub centre: Point,
+pub struct Circle<T = f64> {
+    pub radius: T,
+    pub centre: Point<T>,
 }
 
-impl Circle {
+impl<T: Real> Circle<T> {
     /// Creates a new instance of circle
     /// with given radius and centre point
     /// # Arguments
@@ -19,7 +23,7 @@ impl Circle {
     /// use ralgeb::circle::Circle;
     /// let c = Circle::new(2., Point{x: 3.,y: -4.});
     /// ```
-    pub fn new(r: f64, centre: Point) -> Circle {
+    pub fn new(r: T, centre: Point<T>) -> Circle<T> {
         Circle { radius: r, centre }
     }
 
@@ -32,8 +36,8 @@ impl Circle {
     /// let c = Circle::new(2., Point{x: 3.,y: -4.});
     /// assert_eq!(c.circumference(), 2. * 2. * std::f64::consts::PI);
     /// ```
-    pub fn circumference(&self) -> f64 {
-        2 as f64 * self.radius * std::f64::consts::PI
+    pub fn circumference(&self) -> T {
+        T::from_f64(2.) * self.radius * T::from_f64(PI)
     }
     /// Returns the area of the circle
     ///
@@ -44,8 +48,20 @@ impl Circle {
     /// let c = Circle::new(2., Point{x: 3.,y: -4.});
     /// assert_eq!(c.area(), std::f64::consts::PI * (2 as f64).powi(2));
     /// ```
-    pub fn area(&self) -> f64 {
-        std::f64::consts::PI * self.radius.powi(2)
+    pub fn area(&self) -> T {
+        T::from_f64(PI) * self.radius.powi(2)
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for Circle<T> {
+    /// Compares the radius and the centre
+    fn approx_eq(&self, other: &Circle<T>, tolerance: Tolerance) -> bool {
+        self.radius.approx_eq(&other.radius, tolerance)
+            && self.centre.approx_eq(&other.centre, tolerance)
+    }
+
+    fn ulps_eq(&self, other: &Circle<T>, ulps: u64) -> bool {
+        self.radius.ulps_eq(&other.radius, ulps) && self.centre.ulps_eq(&other.centre, ulps)
     }
 }
 
@@ -63,15 +79,21 @@ mod tests {
     }
     #[test]
     fn circumference() {
-        let c = circle::Circle::new(1 as f64, new_point());
-        assert_eq!(
-            c.circumference(),
-            2 as f64 * c.radius * std::f64::consts::PI
-        )
+        let c = circle::Circle::new(1., new_point());
+        crate::assert_ulps_eq!(c.circumference(), 2. * c.radius * std::f64::consts::PI, 1)
     }
     #[test]
     fn area() {
-        let c = circle::Circle::new(1 as f64, new_point());
-        assert_eq!(std::f64::consts::PI * c.radius.powi(2), c.area())
+        let c = circle::Circle::new(1., new_point());
+        crate::assert_ulps_eq!(std::f64::consts::PI * c.radius.powi(2), c.area(), 1)
+    }
+    #[test]
+    fn approx_eq() {
+        use crate::approx::{ApproxEq, Tolerance};
+        let c1 = circle::Circle::new((2f64).sqrt().powi(2), new_point());
+        let c2 = circle::Circle::new(2., new_point());
+        assert!(c1.approx_eq(&c2, Tolerance::default()));
+        assert!(!c1.approx_eq(&circle::Circle::new(2.1, new_point()), Tolerance::default()));
+        crate::assert_ulps_eq!(c1, c2, 1);
     }
 }
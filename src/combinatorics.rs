@@ -1,4 +1,8 @@
-/// Returns the factorial of a given number n
+use crate::biguint::BigUint;
+
+/// Returns the factorial of a given number n.
+/// Panics if the result overflows usize; use `checked_fact` or
+/// `big_fact` for large n.
 /// # Arguments
 /// `n` - A non-negative number
 ///
@@ -6,15 +10,31 @@
 ///```
 /// use ralgeb::combinatorics;
 /// let result = combinatorics::fact(3);
+/// assert_eq!(result, 6);
+/// assert_eq!(combinatorics::fact(0), 1);
 ///```
 pub fn fact(n: usize) -> usize {
-  if n == 1 {
-    return 1;
-  }
-  return n * fact(n - 1);
+  checked_fact(n).expect("Factorial overflows usize")
+}
+
+/// Returns the factorial of a given number n or None on overflow
+/// # Arguments
+/// `n` - A non-negative number
+///
+/// # Examples
+///```
+/// use ralgeb::combinatorics;
+/// assert_eq!(combinatorics::checked_fact(5), Some(120));
+/// assert_eq!(combinatorics::checked_fact(100), None);
+///```
+pub fn checked_fact(n: usize) -> Option<usize> {
+  checked_permutation(n, n)
 }
 
-/// Returns the permutation
+/// Returns the permutation n!/(n-r)!, the number of ordered
+/// arrangements of r out of n items. It is 0 if r > n.
+/// Panics if the result overflows usize; use `checked_permutation` or
+/// `big_permutation` for large values.
 ///
 /// # Arguments
 /// `n` - The number of items which are to be arranged.
@@ -26,22 +46,44 @@ pub fn fact(n: usize) -> usize {
 /// use ralgeb::combinatorics;
 ///
 /// // Number of ways 3 people can sit in a 2 seated vehicle
-/// // 3!/(3-2)! => 3! => 6 ways in which 4 people can be arranged
+/// // 3!/(3-2)! => 6 ways
 /// // let a,b,c be 3 people then the number of ways are
-/// // ab,bc,ca,ba,cd,ac
+/// // ab,ba,bc,cb,ca,ac
 ///
-/// let ways = combinatorics::permutation(4,3);
+/// let ways = combinatorics::permutation(3, 2);
+/// assert_eq!(ways, 6);
 /// ```
 ///
 pub fn permutation(n: usize, r: usize) -> usize {
-  if n > r {
-    fact(n) / fact(n - r)
-  } else {
-    1
+  checked_permutation(n, r).expect("Permutation overflows usize")
+}
+
+/// Returns the permutation n!/(n-r)! or None on overflow.
+/// It is computed as the product n(n-1)...(n-r+1), without factorials.
+///
+/// # Arguments
+/// `n` - The number of items which are to be arranged.
+///
+/// `r` - The slots available.
+///
+/// # Examples
+/// ```
+/// use ralgeb::combinatorics;
+/// assert_eq!(combinatorics::checked_permutation(30, 3), Some(24360));
+/// assert_eq!(combinatorics::checked_permutation(3, 4), Some(0));
+/// assert_eq!(combinatorics::checked_permutation(100, 50), None);
+/// ```
+pub fn checked_permutation(n: usize, r: usize) -> Option<usize> {
+  if r > n {
+    return Some(0);
   }
+  (n - r + 1..=n).try_fold(1usize, |acc, k| acc.checked_mul(k))
 }
 
-/// Returns the combinations
+/// Returns the combinations n!/((n-r)!r!), the number of ways to
+/// choose r out of n items. It is 0 if r > n.
+/// Panics if the result overflows usize; use `checked_combinations` or
+/// `big_combinations` for large values.
 /// # Arguments
 /// `n` - The number of items
 ///
@@ -56,33 +98,261 @@ pub fn permutation(n: usize, r: usize) -> usize {
 /// // let a,b,c,d be 4 people then result is
 /// // abc, bcd, cda, dab
 /// let combinations = combinatorics::combinations(4,3);
-///
+/// assert_eq!(combinations, 4);
 /// ```
 ///
 pub fn combinations(n: usize, r: usize) -> usize {
-  if n > r {
-    permutation(n, r) / fact(r)
-  } else {
-    1
+  checked_combinations(n, r).expect("Combinations overflow usize")
+}
+
+/// Returns the combinations n!/((n-r)!r!) or None on overflow.
+/// It is computed with the multiplicative formula, so it succeeds
+/// whenever the result itself fits in usize.
+/// # Arguments
+/// `n` - The number of items
+///
+/// `r` - The slots available
+///
+/// # Examples
+/// ```
+/// use ralgeb::combinatorics;
+/// assert_eq!(combinatorics::checked_combinations(30, 15), Some(155117520));
+/// assert_eq!(combinatorics::checked_combinations(4, 5), Some(0));
+/// assert_eq!(combinatorics::checked_combinations(1000, 500), None);
+/// ```
+pub fn checked_combinations(n: usize, r: usize) -> Option<usize> {
+  if r > n {
+    return Some(0);
+  }
+  let r = r.min(n - r);
+  let mut result: u128 = 1;
+  for i in 1..=r {
+    // result is C(n - r + i - 1, i - 1), which is at most the final
+    // value and so fits in usize; the product then fits in u128 and
+    // the division is exact
+    result = result * (n - r + i) as u128 / i as u128;
+    if result > usize::MAX as u128 {
+      return None;
+    }
+  }
+  Some(result as usize)
+}
+
+/// Returns the factorial n! as a big integer, so it cannot overflow
+/// # Arguments
+/// `n` - A non-negative number
+///
+/// # Examples
+///```
+/// use ralgeb::combinatorics;
+/// let result = combinatorics::big_fact(25);
+/// assert_eq!(result.to_string(), "15511210043330985984000000");
+///```
+pub fn big_fact(n: usize) -> BigUint {
+  big_permutation(n, n)
+}
+
+/// Returns the permutation n!/(n-r)! as a big integer.
+/// It is computed as the product n(n-1)...(n-r+1), without factorials,
+/// and is 0 if r > n.
+///
+/// # Arguments
+/// `n` - The number of items which are to be arranged.
+///
+/// `r` - The slots available.
+///
+/// # Examples
+/// ```
+/// use ralgeb::combinatorics;
+/// let ways = combinatorics::big_permutation(100, 10);
+/// assert_eq!(ways.to_string(), "62815650955529472000");
+/// ```
+pub fn big_permutation(n: usize, r: usize) -> BigUint {
+  if r > n {
+    return BigUint::zero();
+  }
+  let mut result = BigUint::one();
+  for k in n - r + 1..=n {
+    result.mul_u64(k as u64);
+  }
+  result
+}
+
+/// Returns the combinations n!/((n-r)!r!) as a big integer.
+/// It is computed with the multiplicative formula, where each partial
+/// result is itself a binomial coefficient, and is 0 if r > n.
+///
+/// # Arguments
+/// `n` - The number of items
+///
+/// `r` - The slots available
+///
+/// # Examples
+/// ```
+/// use ralgeb::combinatorics;
+/// let c = combinatorics::big_combinations(1000, 500);
+/// assert_eq!(c.to_string().len(), 300);
+/// ```
+pub fn big_combinations(n: usize, r: usize) -> BigUint {
+  if r > n {
+    return BigUint::zero();
   }
+  let r = r.min(n - r);
+  let mut result = BigUint::one();
+  for i in 1..=r {
+    // result is C(n - r + i - 1, i - 1), so the division is exact
+    result.mul_u64((n - r + i) as u64);
+    result = result.div_rem_u64(i as u64).0;
+  }
+  result
 }
 
 #[cfg(test)]
 mod tests {
   use crate::combinatorics;
+  /// Returns n! for n = 0, 1, ... while it fits in usize
+  fn factorial_table() -> Vec<usize> {
+    let mut table = vec![1usize];
+    while let Some(next) = table.last().unwrap().checked_mul(table.len()) {
+      table.push(next);
+    }
+    table
+  }
+
+  /// Returns Pascal's triangle with n rows, built by addition only.
+  /// Entries that overflow usize are None.
+  fn pascal_triangle(n: usize) -> Vec<Vec<Option<usize>>> {
+    let mut rows: Vec<Vec<Option<usize>>> = vec![vec![Some(1)]];
+    for i in 1..n {
+      let prev = &rows[i - 1];
+      let row = (0..=i)
+        .map(|r| {
+          let left = if r == 0 { Some(0) } else { prev[r - 1] };
+          let right = if r == i { Some(0) } else { prev[r] };
+          left?.checked_add(right?)
+        })
+        .collect();
+      rows.push(row);
+    }
+    rows
+  }
+
   #[test]
   fn fact() {
     let f = combinatorics::fact(3);
     assert_eq!(6, f);
+    assert_eq!(combinatorics::fact(0), 1);
+    assert_eq!(combinatorics::fact(1), 1);
+    let table = factorial_table();
+    for (n, expected) in table.iter().enumerate() {
+      assert_eq!(combinatorics::checked_fact(n), Some(*expected));
+      assert_eq!(combinatorics::fact(n), *expected);
+    }
+    assert_eq!(combinatorics::checked_fact(table.len()), None);
+    assert_eq!(combinatorics::checked_fact(usize::MAX), None);
+  }
+  #[test]
+  #[should_panic]
+  fn fact_overflow() {
+    combinatorics::fact(1000);
   }
   #[test]
   fn permutation() {
     let ways = combinatorics::permutation(3, 2);
     assert_eq!(ways, 6);
+    assert_eq!(combinatorics::permutation(4, 3), 24);
+    assert_eq!(combinatorics::permutation(5, 5), 120);
+    assert_eq!(combinatorics::permutation(5, 0), 1);
+    assert_eq!(combinatorics::permutation(0, 0), 1);
+    assert_eq!(combinatorics::permutation(2, 3), 0);
+    let table = factorial_table();
+    for n in 0..table.len() {
+      for r in 0..=n + 1 {
+        let expected = if r > n { 0 } else { table[n] / table[n - r] };
+        assert_eq!(combinatorics::checked_permutation(n, r), Some(expected));
+      }
+    }
+    // P(n, 1) = n never overflows, P(n, 2) does for large n
+    assert_eq!(combinatorics::checked_permutation(usize::MAX, 1), Some(usize::MAX));
+    assert_eq!(combinatorics::checked_permutation(usize::MAX, 2), None);
   }
   #[test]
   fn combinations() {
     let combi = combinatorics::combinations(4, 3);
     assert_eq!(combi, 4);
+    assert_eq!(combinatorics::combinations(4, 4), 1);
+    assert_eq!(combinatorics::combinations(4, 0), 1);
+    assert_eq!(combinatorics::combinations(4, 5), 0);
+    assert_eq!(combinatorics::combinations(0, 0), 1);
+    assert_eq!(combinatorics::combinations(30, 15), 155117520);
+    // Every entry of Pascal's triangle, up to and past overflow
+    let triangle = pascal_triangle(usize::BITS as usize + 8);
+    for (n, row) in triangle.iter().enumerate() {
+      for (r, expected) in row.iter().enumerate() {
+        assert_eq!(combinatorics::checked_combinations(n, r), *expected, "C({}, {})", n, r);
+      }
+      assert_eq!(combinatorics::checked_combinations(n, n + 1), Some(0));
+    }
+    assert_eq!(combinatorics::checked_combinations(usize::MAX, 1), Some(usize::MAX));
+    assert_eq!(combinatorics::checked_combinations(usize::MAX, usize::MAX - 1), Some(usize::MAX));
+    assert_eq!(combinatorics::checked_combinations(usize::MAX, 2), None);
+  }
+  fn fits_usize(b: crate::biguint::BigUint) -> Option<usize> {
+    b.to_u64().filter(|v| *v <= usize::MAX as u64).map(|v| v as usize)
+  }
+  #[test]
+  fn matches_big_versions() {
+    for n in 0..80 {
+      for r in 0..=n + 1 {
+        let c = fits_usize(combinatorics::big_combinations(n, r));
+        assert_eq!(combinatorics::checked_combinations(n, r), c);
+        let p = fits_usize(combinatorics::big_permutation(n, r));
+        assert_eq!(combinatorics::checked_permutation(n, r), p);
+      }
+      assert_eq!(combinatorics::checked_fact(n), fits_usize(combinatorics::big_fact(n)));
+    }
+  }
+  #[test]
+  fn big_fact() {
+    assert_eq!(combinatorics::big_fact(0).to_string(), "1");
+    assert_eq!(combinatorics::big_fact(1).to_string(), "1");
+    assert_eq!(combinatorics::big_fact(20).to_u64(), Some(2432902008176640000));
+    assert_eq!(
+      combinatorics::big_fact(30).to_string(),
+      "265252859812191058636308480000000"
+    );
+    assert_eq!(
+      combinatorics::big_fact(52).to_string(),
+      "80658175170943878571660636856403766975289505440883277824000000000000"
+    );
+  }
+  #[test]
+  fn big_permutation() {
+    assert_eq!(combinatorics::big_permutation(5, 0).to_string(), "1");
+    assert_eq!(combinatorics::big_permutation(5, 5).to_string(), "120");
+    assert_eq!(combinatorics::big_permutation(5, 6).to_string(), "0");
+    assert_eq!(combinatorics::big_permutation(0, 0).to_string(), "1");
+    assert_eq!(combinatorics::big_permutation(30, 30), combinatorics::big_fact(30));
+  }
+  #[test]
+  fn big_combinations() {
+    assert_eq!(combinatorics::big_combinations(30, 15).to_u64(), Some(155117520));
+    assert_eq!(combinatorics::big_combinations(4, 4).to_string(), "1");
+    assert_eq!(combinatorics::big_combinations(4, 0).to_string(), "1");
+    assert_eq!(combinatorics::big_combinations(4, 5).to_string(), "0");
+    assert_eq!(combinatorics::big_combinations(0, 0).to_string(), "1");
+    assert_eq!(
+      combinatorics::big_combinations(1000, 500).to_string(),
+      "270288240945436569515614693625975275496152008446548287007392875106625428705522193898612483924502370165362606085021546104802209750050679917549894219699518475423665484263751733356162464079737887344364574161119497604571044985756287880514600994219426752366915856603136862602484428109296905863799821216320"
+    );
+    // Pascal's rule
+    for n in 1..60 {
+      for r in 1..n {
+        assert_eq!(
+          combinatorics::big_combinations(n, r),
+          combinatorics::big_combinations(n - 1, r - 1) + combinatorics::big_combinations(n - 1, r)
+        );
+      }
+    }
   }
 }
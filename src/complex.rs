@@ -0,0 +1,402 @@
+use crate::approx::{ApproxEq, Tolerance};
+use crate::scalar::Scalar;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Complex represents a number re + im·i
+///
+/// # Examples
+/// (re: 1., im: -2.) is 1 - 2i
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl fmt::Display for Complex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.im < 0. {
+            write!(f, "{}-{}i", self.re, -self.im)
+        } else {
+            write!(f, "{}+{}i", self.re, self.im)
+        }
+    }
+}
+
+impl Complex {
+    /// Returns a new complex number
+    ///
+    /// # Arguments
+    /// * `re` - The real part
+    /// * `im` - The imaginary part
+    ///
+    /// ```
+    /// use ralgeb::complex::Complex;
+    /// let z = Complex::new(1., -2.);
+    /// assert_eq!(z.to_string(), "1-2i");
+    /// ```
+    pub fn new(re: f64, im: f64) -> Complex {
+        Complex { re, im }
+    }
+
+    /// Returns the imaginary unit i
+    ///
+    /// ```
+    /// use ralgeb::complex::Complex;
+    /// assert_eq!(Complex::i() * Complex::i(), Complex::new(-1., 0.));
+    /// ```
+    pub fn i() -> Complex {
+        Complex::new(0., 1.)
+    }
+
+    /// Returns the complex number with the given polar coordinates
+    ///
+    /// # Arguments
+    /// * `r` - The modulus
+    /// * `theta` - The argument in radians
+    ///
+    /// ```
+    /// use ralgeb::complex::Complex;
+    /// let z = Complex::from_polar(2., std::f64::consts::FRAC_PI_2);
+    /// assert!((z.im - 2.).abs() < 1e-15);
+    /// ```
+    pub fn from_polar(r: f64, theta: f64) -> Complex {
+        Complex::new(r * theta.cos(), r * theta.sin())
+    }
+
+    /// Returns the complex conjugate re - im·i
+    ///
+    /// ```
+    /// use ralgeb::complex::Complex;
+    /// assert_eq!(Complex::new(1., 2.).conj(), Complex::new(1., -2.));
+    /// ```
+    pub fn conj(&self) -> Complex {
+        Complex::new(self.re, -self.im)
+    }
+
+    /// Returns the modulus (absolute value) |z|
+    ///
+    /// ```
+    /// use ralgeb::complex::Complex;
+    /// assert_eq!(Complex::new(3., 4.).modulus(), 5.);
+    /// ```
+    pub fn modulus(&self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    /// Returns the argument in radians, in the range (-π, π]
+    ///
+    /// ```
+    /// use ralgeb::complex::Complex;
+    /// assert_eq!(Complex::new(0., 1.).arg(), std::f64::consts::FRAC_PI_2);
+    /// ```
+    pub fn arg(&self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    /// Returns the polar form as (modulus, argument)
+    ///
+    /// ```
+    /// use ralgeb::complex::Complex;
+    /// let (r, theta) = Complex::new(-1., 0.).to_polar();
+    /// assert_eq!((r, theta), (1., std::f64::consts::PI));
+    /// ```
+    pub fn to_polar(&self) -> (f64, f64) {
+        (self.modulus(), self.arg())
+    }
+
+    /// Returns e raised to the complex number
+    ///
+    /// ```
+    /// use ralgeb::complex::Complex;
+    /// let z = Complex::new(0., std::f64::consts::PI).exp();
+    /// assert!((z.re + 1.).abs() < 1e-15);
+    /// ```
+    pub fn exp(&self) -> Complex {
+        Complex::from_polar(self.re.exp(), self.im)
+    }
+
+    /// Returns the principal natural logarithm
+    ///
+    /// ```
+    /// use ralgeb::complex::Complex;
+    /// let z = Complex::new(-1., 0.).ln();
+    /// assert_eq!(z, Complex::new(0., std::f64::consts::PI));
+    /// ```
+    pub fn ln(&self) -> Complex {
+        Complex::new(self.modulus().ln(), self.arg())
+    }
+
+    /// Returns the principal value of the complex number raised to a complex power
+    ///
+    /// # Arguments
+    /// * `w` - The exponent
+    ///
+    /// ```
+    /// use ralgeb::complex::Complex;
+    /// // i^i = e^(-π/2)
+    /// let z = Complex::i().powc(Complex::i());
+    /// assert!((z.re - (-std::f64::consts::FRAC_PI_2).exp()).abs() < 1e-15);
+    /// ```
+    pub fn powc(&self, w: Complex) -> Complex {
+        if self.re == 0. && self.im == 0. {
+            return if w.re == 0. && w.im == 0. {
+                Complex::new(1., 0.)
+            } else {
+                Complex::new(0., 0.)
+            };
+        }
+        (w * self.ln()).exp()
+    }
+
+    /// Returns the principal value of the complex number raised to a real power
+    ///
+    /// # Arguments
+    /// * `x` - The exponent
+    ///
+    /// ```
+    /// use ralgeb::complex::Complex;
+    /// let z = Complex::new(-4., 0.).powf(0.5);
+    /// assert!((z.im - 2.).abs() < 1e-15);
+    /// ```
+    pub fn powf(&self, x: f64) -> Complex {
+        if self.re == 0. && self.im == 0. {
+            return self.powc(Complex::new(x, 0.));
+        }
+        let (r, theta) = self.to_polar();
+        Complex::from_polar(r.powf(x), theta * x)
+    }
+
+    /// Returns the complex number raised to an integer power
+    /// by repeated multiplication
+    ///
+    /// # Arguments
+    /// * `n` - The exponent
+    ///
+    /// ```
+    /// use ralgeb::complex::Complex;
+    /// assert_eq!(Complex::new(1., 1.).powi(2), Complex::new(0., 2.));
+    /// ```
+    pub fn powi(&self, n: i32) -> Complex {
+        let mut base = if n < 0 {
+            Complex::new(1., 0.) / *self
+        } else {
+            *self
+        };
+        let mut e = n.unsigned_abs();
+        let mut result = Complex::new(1., 0.);
+        while e > 0 {
+            if e & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            e >>= 1;
+        }
+        result
+    }
+
+    /// Returns the principal square root
+    ///
+    /// ```
+    /// use ralgeb::complex::Complex;
+    /// assert_eq!(Complex::new(-9., 0.).sqrt(), Complex::new(0., 3.));
+    /// ```
+    pub fn sqrt(&self) -> Complex {
+        let r = self.modulus();
+        let re = ((r + self.re) / 2.).sqrt();
+        let im = ((r - self.re) / 2.).sqrt();
+        Complex::new(re, if self.im < 0. { -im } else { im })
+    }
+}
+
+impl From<f64> for Complex {
+    fn from(re: f64) -> Complex {
+        Complex::new(re, 0.)
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+    fn add(self, rhs: Complex) -> Complex {
+        Complex::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+    fn sub(self, rhs: Complex) -> Complex {
+        Complex::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+    fn mul(self, rhs: Complex) -> Complex {
+        Complex::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl Div for Complex {
+    type Output = Complex;
+    fn div(self, rhs: Complex) -> Complex {
+        // Smith's algorithm avoids overflow in |rhs|²
+        if rhs.re.abs() >= rhs.im.abs() {
+            let ratio = rhs.im / rhs.re;
+            let denom = rhs.re + rhs.im * ratio;
+            Complex::new(
+                (self.re + self.im * ratio) / denom,
+                (self.im - self.re * ratio) / denom,
+            )
+        } else {
+            let ratio = rhs.re / rhs.im;
+            let denom = rhs.re * ratio + rhs.im;
+            Complex::new(
+                (self.re * ratio + self.im) / denom,
+                (self.im * ratio - self.re) / denom,
+            )
+        }
+    }
+}
+
+impl Neg for Complex {
+    type Output = Complex;
+    fn neg(self) -> Complex {
+        Complex::new(-self.re, -self.im)
+    }
+}
+
+impl Add<f64> for Complex {
+    type Output = Complex;
+    fn add(self, rhs: f64) -> Complex {
+        Complex::new(self.re + rhs, self.im)
+    }
+}
+
+impl Sub<f64> for Complex {
+    type Output = Complex;
+    fn sub(self, rhs: f64) -> Complex {
+        Complex::new(self.re - rhs, self.im)
+    }
+}
+
+impl Mul<f64> for Complex {
+    type Output = Complex;
+    fn mul(self, rhs: f64) -> Complex {
+        Complex::new(self.re * rhs, self.im * rhs)
+    }
+}
+
+impl Div<f64> for Complex {
+    type Output = Complex;
+    fn div(self, rhs: f64) -> Complex {
+        Complex::new(self.re / rhs, self.im / rhs)
+    }
+}
+
+impl Scalar for Complex {
+    fn zero() -> Complex {
+        Complex::new(0., 0.)
+    }
+    fn one() -> Complex {
+        Complex::new(1., 0.)
+    }
+    fn magnitude(&self) -> f64 {
+        self.modulus()
+    }
+}
+
+impl ApproxEq for Complex {
+    /// Compares the modulus of the difference against the tolerance
+    fn approx_eq(&self, other: &Complex, tolerance: Tolerance) -> bool {
+        self == other || tolerance.close(*self, *other)
+    }
+
+    fn ulps_eq(&self, other: &Complex, ulps: u64) -> bool {
+        self.re.ulps_eq(&other.re, ulps) && self.im.ulps_eq(&other.im, ulps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::complex::Complex;
+    use std::f64::consts::PI;
+
+    fn assert_close(a: Complex, b: Complex) {
+        assert!((a - b).modulus() < 1e-12, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn new_complex() {
+        let z = Complex::new(1., 2.);
+        assert_eq!(z.re, 1.);
+        assert_eq!(z.im, 2.);
+        assert_eq!(Complex::from(3.), Complex::new(3., 0.));
+        assert_eq!(format!("{}", Complex::new(-1.5, -2.)), "-1.5-2i");
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = Complex::new(1., 2.);
+        let b = Complex::new(3., -1.);
+        assert_eq!(a + b, Complex::new(4., 1.));
+        assert_eq!(a - b, Complex::new(-2., 3.));
+        assert_eq!(a * b, Complex::new(5., 5.));
+        assert_close(a / b, Complex::new(0.1, 0.7));
+        assert_close((a / b) * b, a);
+        let c = Complex::new(1e-3, 2.);
+        assert_close((Complex::new(1., 1.) / c) * c, Complex::new(1., 1.));
+        assert_eq!(-a, Complex::new(-1., -2.));
+        assert_eq!(a * 2., Complex::new(2., 4.));
+        assert_eq!(a + 1., Complex::new(2., 2.));
+        assert_eq!(a - 1., Complex::new(0., 2.));
+        assert_eq!(a / 2., Complex::new(0.5, 1.));
+    }
+
+    #[test]
+    fn polar() {
+        let z = Complex::new(1., -1.);
+        assert_eq!(z.conj(), Complex::new(1., 1.));
+        assert_eq!(z.modulus(), 2_f64.sqrt());
+        assert_eq!(z.arg(), -PI / 4.);
+        let (r, theta) = z.to_polar();
+        assert_close(Complex::from_polar(r, theta), z);
+    }
+
+    #[test]
+    fn exp_and_ln() {
+        assert_close(Complex::new(0., PI).exp(), Complex::new(-1., 0.));
+        let z = Complex::new(0.3, -2.);
+        assert_close(z.ln().exp(), z);
+        assert_close(Complex::new(1., 0.).ln(), Complex::new(0., 0.));
+    }
+
+    #[test]
+    fn powers() {
+        let z = Complex::new(1., 2.);
+        assert_close(z.powi(3), z * z * z);
+        assert_close(z.powi(-2), Complex::new(1., 0.) / (z * z));
+        assert_eq!(z.powi(0), Complex::new(1., 0.));
+        assert_close(z.powf(2.), z * z);
+        assert_close(z.powc(Complex::new(2., 0.)), z * z);
+        assert_close(z.sqrt() * z.sqrt(), z);
+        assert_eq!(
+            Complex::new(0., 0.).powc(Complex::new(2., 1.)),
+            Complex::new(0., 0.)
+        );
+        assert_eq!(Complex::new(0., 0.).powf(0.), Complex::new(1., 0.));
+        assert_eq!(Complex::new(4., 0.).sqrt(), Complex::new(2., 0.));
+        assert_eq!(Complex::new(0., -2.).sqrt(), Complex::new(1., -1.));
+    }
+
+    #[test]
+    fn approx_eq() {
+        use crate::approx::{ApproxEq, Tolerance};
+        let z = Complex::from_polar(2., PI / 3.);
+        assert!(z.approx_eq(&Complex::new(1., 3f64.sqrt()), Tolerance::default()));
+        assert!(!z.approx_eq(&Complex::new(1., 1.7), Tolerance::default()));
+        assert!(!z.ulps_eq(&z.conj(), 10));
+    }
+}
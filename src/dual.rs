@@ -0,0 +1,497 @@
+use crate::approx::{ApproxEq, Tolerance};
+use crate::scalar::{Real, Scalar};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Dual represents a number re + eps·ε where ε² = 0.
+/// Evaluating a function on re + 1·ε gives f(re) + f'(re)·ε,
+/// so derivatives are carried along with the values (forward mode
+/// automatic differentiation).
+///
+/// # Examples
+/// (re: 3., eps: 1.) is the variable x at x = 3
+pub struct Dual {
+    pub re: f64,
+    pub eps: f64,
+}
+
+impl fmt::Display for Dual {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.eps < 0. {
+            write!(f, "{}-{}ε", self.re, -self.eps)
+        } else {
+            write!(f, "{}+{}ε", self.re, self.eps)
+        }
+    }
+}
+
+impl Dual {
+    /// Returns a new dual number
+    ///
+    /// # Arguments
+    /// * `re` - The value
+    /// * `eps` - The derivative
+    ///
+    /// ```
+    /// use ralgeb::dual::Dual;
+    /// let d = Dual::new(1., -2.);
+    /// assert_eq!(d.to_string(), "1-2ε");
+    /// ```
+    pub fn new(re: f64, eps: f64) -> Dual {
+        Dual { re, eps }
+    }
+
+    /// Returns a constant, whose derivative is 0
+    ///
+    /// ```
+    /// use ralgeb::dual::Dual;
+    /// assert_eq!(Dual::constant(2.), Dual::new(2., 0.));
+    /// ```
+    pub fn constant(re: f64) -> Dual {
+        Dual::new(re, 0.)
+    }
+
+    /// Returns the independent variable at re, whose derivative is 1
+    ///
+    /// ```
+    /// use ralgeb::dual::Dual;
+    /// let x = Dual::variable(3.);
+    /// assert_eq!(x * x, Dual::new(9., 6.));
+    /// ```
+    pub fn variable(re: f64) -> Dual {
+        Dual::new(re, 1.)
+    }
+
+    /// Applies a function with known value and derivative at re
+    /// using the chain rule. A constant stays constant even where
+    /// the derivative is infinite, as for sqrt at 0.
+    fn chain(&self, value: f64, derivative: f64) -> Dual {
+        if self.eps == 0. {
+            return Dual::constant(value);
+        }
+        Dual::new(value, derivative * self.eps)
+    }
+
+    /// Returns the square root
+    ///
+    /// ```
+    /// use ralgeb::dual::Dual;
+    /// assert_eq!(Dual::variable(4.).sqrt(), Dual::new(2., 0.25));
+    /// ```
+    pub fn sqrt(&self) -> Dual {
+        let root = self.re.sqrt();
+        self.chain(root, 0.5 / root)
+    }
+
+    /// Returns e^self
+    ///
+    /// ```
+    /// use ralgeb::dual::Dual;
+    /// assert_eq!(Dual::variable(0.).exp(), Dual::new(1., 1.));
+    /// ```
+    pub fn exp(&self) -> Dual {
+        let e = self.re.exp();
+        self.chain(e, e)
+    }
+
+    /// Returns the natural logarithm
+    ///
+    /// ```
+    /// use ralgeb::dual::Dual;
+    /// assert_eq!(Dual::variable(2.).ln(), Dual::new(2_f64.ln(), 0.5));
+    /// ```
+    pub fn ln(&self) -> Dual {
+        self.chain(self.re.ln(), 1. / self.re)
+    }
+
+    /// Returns the sine of self in radians
+    ///
+    /// ```
+    /// use ralgeb::dual::Dual;
+    /// assert_eq!(Dual::variable(0.).sin(), Dual::new(0., 1.));
+    /// ```
+    pub fn sin(&self) -> Dual {
+        self.chain(self.re.sin(), self.re.cos())
+    }
+
+    /// Returns the cosine of self in radians
+    ///
+    /// ```
+    /// use ralgeb::dual::Dual;
+    /// assert_eq!(Dual::variable(0.).cos(), Dual::new(1., 0.));
+    /// ```
+    pub fn cos(&self) -> Dual {
+        self.chain(self.re.cos(), -self.re.sin())
+    }
+
+    /// Returns the tangent of self in radians
+    ///
+    /// ```
+    /// use ralgeb::dual::Dual;
+    /// assert_eq!(Dual::variable(0.).tan(), Dual::new(0., 1.));
+    /// ```
+    pub fn tan(&self) -> Dual {
+        let t = self.re.tan();
+        self.chain(t, 1. + t * t)
+    }
+
+    /// Returns the four quadrant arctangent of self (y) and x in radians
+    ///
+    /// ```
+    /// use ralgeb::dual::Dual;
+    /// let theta = Dual::variable(1.).atan2(Dual::constant(1.));
+    /// assert_eq!(theta, Dual::new(std::f64::consts::FRAC_PI_4, 0.5));
+    /// ```
+    pub fn atan2(&self, x: Dual) -> Dual {
+        let r2 = self.re * self.re + x.re * x.re;
+        Dual::new(
+            self.re.atan2(x.re),
+            (x.re * self.eps - self.re * x.eps) / r2,
+        )
+    }
+
+    /// Returns the absolute value. The derivative at 0 is taken as 0.
+    ///
+    /// ```
+    /// use ralgeb::dual::Dual;
+    /// assert_eq!(Dual::variable(-2.).abs(), Dual::new(2., -1.));
+    /// ```
+    pub fn abs(&self) -> Dual {
+        if self.re < 0. {
+            -*self
+        } else if self.re > 0. {
+            *self
+        } else {
+            Dual::constant(0.)
+        }
+    }
+
+    /// Returns self raised to an integer power
+    ///
+    /// ```
+    /// use ralgeb::dual::Dual;
+    /// assert_eq!(Dual::variable(2.).powi(3), Dual::new(8., 12.));
+    /// ```
+    pub fn powi(&self, n: i32) -> Dual {
+        if n == 0 {
+            return Dual::constant(1.);
+        }
+        self.chain(self.re.powi(n), n as f64 * self.re.powi(n - 1))
+    }
+
+    /// Returns self raised to a real power
+    ///
+    /// ```
+    /// use ralgeb::dual::Dual;
+    /// assert_eq!(Dual::variable(4.).powf(0.5), Dual::new(2., 0.25));
+    /// ```
+    pub fn powf(&self, p: f64) -> Dual {
+        if p == 0. {
+            return Dual::constant(1.);
+        }
+        self.chain(self.re.powf(p), p * self.re.powf(p - 1.))
+    }
+}
+
+/// Returns the value and the derivative of f at x
+///
+/// # Arguments
+/// * `f` - A function of one variable written over `Dual`
+/// * `x` - The point at which to differentiate
+///
+/// # Examples
+/// ```
+/// use ralgeb::dual::{self, Dual};
+/// let (value, slope) = dual::derivative(|x: Dual| x.sin() * x, 0.);
+/// assert_eq!((value, slope), (0., 0.));
+/// ```
+pub fn derivative<F: Fn(Dual) -> Dual>(f: F, x: f64) -> (f64, f64) {
+    let y = f(Dual::variable(x));
+    (y.re, y.eps)
+}
+
+/// Returns the value and the gradient of f at x.
+/// f is evaluated once per coordinate, each time seeding that
+/// coordinate as the variable and the others as constants.
+///
+/// # Arguments
+/// * `f` - A function of several variables written over `Dual`
+/// * `x` - The point at which to differentiate
+///
+/// # Examples
+/// ```
+/// use ralgeb::dual::{self, Dual};
+/// let (value, grad) = dual::gradient(|v: &[Dual]| v[0] * v[1] + v[1], &[2., 3.]);
+/// assert_eq!(value, 9.);
+/// assert_eq!(grad, vec![3., 3.]);
+/// ```
+pub fn gradient<F: Fn(&[Dual]) -> Dual>(f: F, x: &[f64]) -> (f64, Vec<f64>) {
+    let mut args: Vec<Dual> = x.iter().map(|v| Dual::constant(*v)).collect();
+    let mut value = None;
+    let mut grad = Vec::with_capacity(x.len());
+    for i in 0..x.len() {
+        args[i].eps = 1.;
+        let y = f(&args);
+        args[i].eps = 0.;
+        value = Some(y.re);
+        grad.push(y.eps);
+    }
+    (value.unwrap_or_else(|| f(&args).re), grad)
+}
+
+impl From<f64> for Dual {
+    fn from(re: f64) -> Dual {
+        Dual::constant(re)
+    }
+}
+
+/// Dual numbers are ordered by their values only
+impl PartialOrd for Dual {
+    fn partial_cmp(&self, other: &Dual) -> Option<Ordering> {
+        self.re.partial_cmp(&other.re)
+    }
+}
+
+impl Add for Dual {
+    type Output = Dual;
+    fn add(self, rhs: Dual) -> Dual {
+        Dual::new(self.re + rhs.re, self.eps + rhs.eps)
+    }
+}
+
+impl Sub for Dual {
+    type Output = Dual;
+    fn sub(self, rhs: Dual) -> Dual {
+        Dual::new(self.re - rhs.re, self.eps - rhs.eps)
+    }
+}
+
+impl Mul for Dual {
+    type Output = Dual;
+    fn mul(self, rhs: Dual) -> Dual {
+        Dual::new(self.re * rhs.re, self.re * rhs.eps + self.eps * rhs.re)
+    }
+}
+
+impl Div for Dual {
+    type Output = Dual;
+    fn div(self, rhs: Dual) -> Dual {
+        Dual::new(
+            self.re / rhs.re,
+            (self.eps * rhs.re - self.re * rhs.eps) / (rhs.re * rhs.re),
+        )
+    }
+}
+
+impl Neg for Dual {
+    type Output = Dual;
+    fn neg(self) -> Dual {
+        Dual::new(-self.re, -self.eps)
+    }
+}
+
+impl Add<f64> for Dual {
+    type Output = Dual;
+    fn add(self, rhs: f64) -> Dual {
+        Dual::new(self.re + rhs, self.eps)
+    }
+}
+
+impl Sub<f64> for Dual {
+    type Output = Dual;
+    fn sub(self, rhs: f64) -> Dual {
+        Dual::new(self.re - rhs, self.eps)
+    }
+}
+
+impl Mul<f64> for Dual {
+    type Output = Dual;
+    fn mul(self, rhs: f64) -> Dual {
+        Dual::new(self.re * rhs, self.eps * rhs)
+    }
+}
+
+impl Div<f64> for Dual {
+    type Output = Dual;
+    fn div(self, rhs: f64) -> Dual {
+        Dual::new(self.re / rhs, self.eps / rhs)
+    }
+}
+
+impl Scalar for Dual {
+    fn zero() -> Dual {
+        Dual::constant(0.)
+    }
+    fn one() -> Dual {
+        Dual::constant(1.)
+    }
+    fn magnitude(&self) -> f64 {
+        self.re.abs()
+    }
+}
+
+impl Real for Dual {
+    fn from_f64(x: f64) -> Dual {
+        Dual::constant(x)
+    }
+    fn sqrt(self) -> Dual {
+        Dual::sqrt(&self)
+    }
+    fn powi(self, n: i32) -> Dual {
+        Dual::powi(&self, n)
+    }
+    fn atan2(self, x: Dual) -> Dual {
+        Dual::atan2(&self, x)
+    }
+}
+
+impl ApproxEq for Dual {
+    /// Compares the values and the derivatives separately
+    fn approx_eq(&self, other: &Dual, tolerance: Tolerance) -> bool {
+        self.re.approx_eq(&other.re, tolerance) && self.eps.approx_eq(&other.eps, tolerance)
+    }
+
+    fn ulps_eq(&self, other: &Dual, ulps: u64) -> bool {
+        self.re.ulps_eq(&other.re, ulps) && self.eps.ulps_eq(&other.eps, ulps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::assert_approx_eq;
+    use crate::dual::{self, Dual};
+
+    #[test]
+    fn arithmetic() {
+        let a = Dual::new(2., 1.);
+        let b = Dual::new(-1., 3.);
+        assert_eq!(a + b, Dual::new(1., 4.));
+        assert_eq!(a - b, Dual::new(3., -2.));
+        assert_eq!(a * b, Dual::new(-2., 5.));
+        assert_eq!(a / b, Dual::new(-2., -7.));
+        assert_eq!(-a, Dual::new(-2., -1.));
+        assert_eq!(a + 1., Dual::new(3., 1.));
+        assert_eq!(a - 1., Dual::new(1., 1.));
+        assert_eq!(a * 2., Dual::new(4., 2.));
+        assert_eq!(a / 2., Dual::new(1., 0.5));
+        assert_eq!(Dual::from(3.), Dual::constant(3.));
+        assert!(Dual::new(1., 5.) < Dual::new(2., 0.));
+        assert_eq!(format!("{}", Dual::new(-1.5, -2.)), "-1.5-2ε");
+    }
+
+    type Case = (fn(Dual) -> Dual, f64);
+
+    #[test]
+    fn elementary_functions() {
+        // Compare against central differences
+        let fs: Vec<Case> = vec![
+            (|x| x.sqrt(), 2.),
+            (|x| x.exp(), 0.7),
+            (|x| x.ln(), 1.3),
+            (|x| x.sin(), 0.4),
+            (|x| x.cos(), 0.4),
+            (|x| x.tan(), 0.4),
+            (|x| x.abs(), -0.4),
+            (|x| x.powi(-3), 1.5),
+            (|x| x.powf(2.5), 1.5),
+            (|x| x.atan2(Dual::constant(-2.)), 0.5),
+            (|x| Dual::constant(-2.).atan2(x), 0.5),
+            (|x| (x * x + 1.).sqrt() / x.exp(), 0.9),
+        ];
+        let h = 1e-6;
+        for (f, x) in fs {
+            let (value, slope) = dual::derivative(f, x);
+            let numeric = (f(Dual::constant(x + h)).re - f(Dual::constant(x - h)).re) / (2. * h);
+            assert_eq!(value, f(Dual::constant(x)).re);
+            assert!((slope - numeric).abs() < 1e-8, "{} != {}", slope, numeric);
+        }
+        assert_eq!(Dual::variable(0.).abs(), Dual::constant(0.));
+        assert_eq!(Dual::variable(3.).powi(0), Dual::constant(1.));
+        assert_eq!(Dual::variable(3.).powf(0.), Dual::constant(1.));
+        // Constants keep a zero derivative where f' is infinite
+        assert_eq!(Dual::constant(0.).sqrt(), Dual::constant(0.));
+        assert_eq!(Dual::constant(0.).ln(), Dual::constant(f64::NEG_INFINITY));
+    }
+
+    #[test]
+    fn gradient() {
+        let f = |v: &[Dual]| (v[0] * v[1]).sin() + v[2].powi(2);
+        let (value, grad) = dual::gradient(f, &[1., 2., 3.]);
+        assert_eq!(value, 2_f64.sin() + 9.);
+        assert_approx_eq!(
+            Dual::new(grad[0], grad[1]),
+            Dual::new(2. * 2_f64.cos(), 2_f64.cos()),
+        );
+        assert_eq!(grad[2], 6.);
+        let (value, grad) = dual::gradient(|_| Dual::constant(4.), &[]);
+        assert_eq!((value, grad), (4., vec![]));
+    }
+
+    #[test]
+    fn geometry() {
+        use crate::circle::Circle;
+        use crate::line::Line;
+        use crate::point::Point;
+        use crate::scalar::Scalar;
+        use std::f64::consts::PI;
+        // d length / d x2 = dx / length
+        let (length, grad) = dual::gradient(
+            |v: &[Dual]| Line::new(Point::new(v[0], v[1]), Point::new(v[2], v[3])).length(),
+            &[1., 2., 4., 6.],
+        );
+        assert_eq!(length, 5.);
+        assert_approx_eq!(grad, vec![-0.6, -0.8, 0.6, 0.8]);
+        let line = Line::new(
+            Point::new(Dual::constant(0.), Dual::constant(0.)),
+            Point::new(Dual::constant(1.), Dual::variable(1.)),
+        );
+        assert_approx_eq!(line.slope(), Dual::new(1., 1.));
+        assert_approx_eq!(line.theta(), Dual::new(PI / 4., 0.5));
+
+        let (area, slope) = dual::derivative(
+            |r| Circle::new(r, Point::new(Dual::constant(0.), Dual::constant(0.))).area(),
+            2.,
+        );
+        crate::assert_ulps_eq!(area, 4. * PI, 1);
+        crate::assert_ulps_eq!(slope, 4. * PI, 1);
+        let c = Circle::new(Dual::variable(2.), Point::new(Dual::zero(), Dual::zero()));
+        assert_approx_eq!(c.circumference(), Dual::new(4. * PI, 2. * PI));
+    }
+
+    #[test]
+    fn matrix() {
+        use crate::matrix::Matrix;
+        // Jacobi's formula: d det(A + tE) / dt = det(A) tr(A⁻¹ E)
+        let a = Matrix::new_mat(vec![vec![2., 1., 0.], vec![1., 3., 1.], vec![0., 1., 4.]]);
+        let e = Matrix::from_fn(3, 3, |r, c| (r + 2 * c) as f64 - 2.);
+        let m = Matrix::from_fn(3, 3, |r, c| Dual::new(a[(r, c)], e[(r, c)]));
+        let det = m.determinant().unwrap();
+        let expected = a.determinant().unwrap()
+            * Matrix::multiply(&a.inverse().unwrap(), &e)
+                .unwrap()
+                .trace()
+                .unwrap();
+        assert_approx_eq!(det, Dual::new(a.determinant().unwrap(), expected));
+
+        // d A⁻¹ = -A⁻¹ E A⁻¹
+        let inv = m.inverse().unwrap();
+        let ai = a.inverse().unwrap();
+        let d = Matrix::multiply(&Matrix::multiply(&ai, &e).unwrap(), &ai).unwrap();
+        for r in 0..3 {
+            for c in 0..3 {
+                assert_approx_eq!(inv[(r, c)], Dual::new(ai[(r, c)], -d[(r, c)]));
+            }
+        }
+    }
+
+    #[test]
+    fn approx_eq() {
+        use crate::approx::{ApproxEq, Tolerance};
+        let a = Dual::new(0.1 + 0.2, 1.);
+        assert!(a.approx_eq(&Dual::new(0.3, 1.), Tolerance::default()));
+        assert!(!a.approx_eq(&Dual::new(0.3, 1.1), Tolerance::default()));
+        assert!(a.ulps_eq(&Dual::new(0.3, 1.), 1));
+    }
+}
@@ -0,0 +1,366 @@
+use crate::matrix::{Matrix, MatrixError};
+use std::cell::OnceCell;
+use std::ops::{Add, Mul, Neg, Sub};
+
+#[derive(Debug, Clone)]
+/// A lazy matrix expression.
+///
+/// Combining expressions with `+`, `-`, `*` and unary `-` only builds a tree,
+/// no arithmetic is done until `eval` or `eval_into` is called. Element-wise
+/// operations and products of plain matrices are then fused into a single
+/// pass over the destination, so `A * B + C * k` needs no intermediate
+/// matrices. Only the operand of a product that itself contains a product
+/// is evaluated into a temporary.
+///
+/// # Examples
+/// ```
+/// use ralgeb::expr::lazy;
+/// use ralgeb::matrix::Matrix;
+/// let a = Matrix::new_mat(vec![vec![1., 2.], vec![3., 4.]]);
+/// let b = Matrix::identity(2, 2).unwrap();
+/// let c = Matrix::ones(2, 2);
+/// let m = (lazy(&a) * &b + lazy(&c) * 10.).eval().unwrap();
+/// assert_eq!(m.get_row(1).unwrap(), vec![13., 14.]);
+/// ```
+pub struct Expr<'a> {
+    node: Node<'a>,
+    // The tree ready for evaluation, built on the first evaluation
+    prepared: OnceCell<Node<'a>>,
+}
+
+#[derive(Debug, Clone)]
+enum Node<'a> {
+    Leaf(&'a Matrix),
+    Owned(Matrix),
+    Add(Box<Node<'a>>, Box<Node<'a>>),
+    Sub(Box<Node<'a>>, Box<Node<'a>>),
+    Scale(Box<Node<'a>>, f64),
+    Neg(Box<Node<'a>>),
+    Product(Box<Node<'a>>, Box<Node<'a>>),
+    // A product whose inner dimension has been checked, built by `prepare`
+    Dot(Box<Node<'a>>, Box<Node<'a>>, usize),
+}
+
+/// Starts a lazy expression from a matrix
+///
+/// # Examples
+/// ```
+/// use ralgeb::expr::lazy;
+/// use ralgeb::matrix::Matrix;
+/// let a = Matrix::ones(2, 2);
+/// let e = lazy(&a) + &a;
+/// assert_eq!(e.shape().unwrap(), (2, 2));
+/// ```
+pub fn lazy(m: &Matrix) -> Expr<'_> {
+    Expr::new(Node::Leaf(m))
+}
+
+impl<'a> From<&'a Matrix> for Expr<'a> {
+    fn from(m: &'a Matrix) -> Expr<'a> {
+        lazy(m)
+    }
+}
+
+impl<'a> Expr<'a> {
+    /// Returns the dimensions of the result, or an error if
+    /// the operands of some operation do not fit together
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::expr::lazy;
+    /// use ralgeb::matrix::Matrix;
+    /// let a = Matrix::new(2, 3);
+    /// assert_eq!((lazy(&a) * &Matrix::new(3, 4)).shape().unwrap(), (2, 4));
+    /// assert!((lazy(&a) + &Matrix::new(3, 2)).shape().is_err());
+    /// ```
+    pub fn shape(&self) -> Result<(usize, usize), MatrixError> {
+        self.node.shape()
+    }
+
+    /// Evaluates the expression into a new matrix
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::expr::lazy;
+    /// use ralgeb::matrix::Matrix;
+    /// let a = Matrix::new_mat(vec![vec![1., 2.]]);
+    /// let m = (-lazy(&a) * 2.).eval().unwrap();
+    /// assert_eq!(m.get_row(0).unwrap(), vec![-2., -4.]);
+    /// ```
+    pub fn eval(&self) -> Result<Matrix, MatrixError> {
+        let (rows, cols) = self.shape()?;
+        let mut out = Matrix::new(rows, cols);
+        self.eval_into(&mut out)?;
+        Ok(out)
+    }
+
+    /// Evaluates the expression into an existing matrix of the right
+    /// dimensions. Nothing is allocated unless a product has an
+    /// operand that contains another product. Such an operand is
+    /// evaluated into a temporary on the first evaluation only and
+    /// reused by every later call.
+    ///
+    /// # Arguments
+    /// `out` - The destination. It cannot be one of the operands.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::expr::lazy;
+    /// use ralgeb::matrix::Matrix;
+    /// let a = Matrix::new_mat(vec![vec![1., 2.], vec![3., 4.]]);
+    /// let mut out = Matrix::new(2, 2);
+    /// (lazy(&a) * &a - &a).eval_into(&mut out).unwrap();
+    /// assert_eq!(out.get_row(0).unwrap(), vec![6., 8.]);
+    /// ```
+    pub fn eval_into(&self, out: &mut Matrix) -> Result<(), MatrixError> {
+        let (rows, cols) = self.shape()?;
+        if (out.rows, out.cols) != (rows, cols) {
+            return Err(MatrixError::new(format!(
+                "Cannot evaluate a {}x{} expression into a {}x{} matrix",
+                rows, cols, out.rows, out.cols
+            )));
+        }
+        let node = match self.prepared.get() {
+            Some(node) => node,
+            None => {
+                let prepared = self.node.prepare()?;
+                self.prepared.get_or_init(|| prepared)
+            }
+        };
+        for r in 0..rows {
+            for c in 0..cols {
+                out[(r, c)] = node.at(r, c);
+            }
+        }
+        Ok(())
+    }
+
+    fn new(node: Node<'a>) -> Expr<'a> {
+        Expr {
+            node,
+            prepared: OnceCell::new(),
+        }
+    }
+
+    fn binary(self, rhs: Expr<'a>, op: fn(Box<Node<'a>>, Box<Node<'a>>) -> Node<'a>) -> Expr<'a> {
+        Expr::new(op(Box::new(self.node), Box::new(rhs.node)))
+    }
+}
+
+impl<'a> Node<'a> {
+    fn shape(&self) -> Result<(usize, usize), MatrixError> {
+        match self {
+            Node::Leaf(m) => Ok((m.rows, m.cols)),
+            Node::Owned(m) => Ok((m.rows, m.cols)),
+            Node::Add(a, b) | Node::Sub(a, b) => {
+                let (sa, sb) = (a.shape()?, b.shape()?);
+                if sa == sb {
+                    Ok(sa)
+                } else {
+                    Err(MatrixError::new(format!(
+                        "Cannot add or subtract a {}x{} matrix and a {}x{} matrix",
+                        sa.0, sa.1, sb.0, sb.1
+                    )))
+                }
+            }
+            Node::Scale(a, _) | Node::Neg(a) => a.shape(),
+            Node::Product(a, b) | Node::Dot(a, b, _) => {
+                let (sa, sb) = (a.shape()?, b.shape()?);
+                if sa.1 == sb.0 {
+                    Ok((sa.0, sb.1))
+                } else {
+                    Err(MatrixError::new(format!(
+                        "Cannot multiply a {}x{} matrix with a {}x{} matrix",
+                        sa.0, sa.1, sb.0, sb.1
+                    )))
+                }
+            }
+        }
+    }
+
+    fn has_product(&self) -> bool {
+        match self {
+            Node::Leaf(_) | Node::Owned(_) => false,
+            Node::Add(a, b) | Node::Sub(a, b) => a.has_product() || b.has_product(),
+            Node::Scale(a, _) | Node::Neg(a) => a.has_product(),
+            Node::Product(_, _) | Node::Dot(_, _, _) => true,
+        }
+    }
+
+    /// Evaluates the operands of products that contain products
+    /// themselves, so that every element costs at most one dot product
+    fn prepare(&self) -> Result<Node<'a>, MatrixError> {
+        let boxed = |n: &Node<'a>| n.prepare().map(Box::new);
+        Ok(match self {
+            Node::Leaf(m) => Node::Leaf(m),
+            Node::Owned(m) => Node::Owned(m.clone()),
+            Node::Add(a, b) => Node::Add(boxed(a)?, boxed(b)?),
+            Node::Sub(a, b) => Node::Sub(boxed(a)?, boxed(b)?),
+            Node::Scale(a, k) => Node::Scale(boxed(a)?, *k),
+            Node::Neg(a) => Node::Neg(boxed(a)?),
+            Node::Product(a, b) | Node::Dot(a, b, _) => Node::Dot(
+                Box::new(a.materialize_products()?),
+                Box::new(b.materialize_products()?),
+                b.shape()?.0,
+            ),
+        })
+    }
+
+    fn materialize_products(&self) -> Result<Node<'a>, MatrixError> {
+        if self.has_product() {
+            let expr = Expr::new(self.clone());
+            Ok(Node::Owned(expr.eval()?))
+        } else {
+            self.prepare()
+        }
+    }
+
+    fn at(&self, r: usize, c: usize) -> f64 {
+        match self {
+            Node::Leaf(m) => m[(r, c)],
+            Node::Owned(m) => m[(r, c)],
+            Node::Add(a, b) => a.at(r, c) + b.at(r, c),
+            Node::Sub(a, b) => a.at(r, c) - b.at(r, c),
+            Node::Scale(a, k) => k * a.at(r, c),
+            Node::Neg(a) => -a.at(r, c),
+            Node::Product(a, b) => {
+                let inner = b.shape().map_or(0, |s| s.0);
+                (0..inner).map(|k| a.at(r, k) * b.at(k, c)).sum()
+            }
+            Node::Dot(a, b, inner) => (0..*inner).map(|k| a.at(r, k) * b.at(k, c)).sum(),
+        }
+    }
+}
+
+impl<'a, R: Into<Expr<'a>>> Add<R> for Expr<'a> {
+    type Output = Expr<'a>;
+
+    fn add(self, rhs: R) -> Expr<'a> {
+        self.binary(rhs.into(), Node::Add)
+    }
+}
+
+impl<'a, R: Into<Expr<'a>>> Sub<R> for Expr<'a> {
+    type Output = Expr<'a>;
+
+    fn sub(self, rhs: R) -> Expr<'a> {
+        self.binary(rhs.into(), Node::Sub)
+    }
+}
+
+impl<'a, R: Into<Expr<'a>>> Mul<R> for Expr<'a> {
+    type Output = Expr<'a>;
+
+    /// Matrix product
+    fn mul(self, rhs: R) -> Expr<'a> {
+        self.binary(rhs.into(), Node::Product)
+    }
+}
+
+impl<'a> Mul<f64> for Expr<'a> {
+    type Output = Expr<'a>;
+
+    /// Multiplication by a scalar
+    fn mul(self, k: f64) -> Expr<'a> {
+        Expr::new(Node::Scale(Box::new(self.node), k))
+    }
+}
+
+impl<'a> Neg for Expr<'a> {
+    type Output = Expr<'a>;
+
+    fn neg(self) -> Expr<'a> {
+        Expr::new(Node::Neg(Box::new(self.node)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::approx::{ApproxEq, Tolerance};
+    use crate::expr::{lazy, Expr, Node};
+    use crate::matrix;
+
+    fn sample(seed: f64, rows: usize, cols: usize) -> matrix::Matrix {
+        matrix::Matrix::from_fn(rows, cols, |r, c| ((r * cols + c) as f64 + seed).sin())
+    }
+
+    #[test]
+    fn fused_matches_eager() {
+        let (a, b, c) = (sample(1., 3, 4), sample(2., 4, 2), sample(3., 3, 2));
+        let k = 2.5;
+        let lazy_result = (lazy(&a) * &b + lazy(&c) * k).eval().unwrap();
+        let eager = matrix::Matrix::add(
+            &matrix::Matrix::multiply(&a, &b).unwrap(),
+            &c.clone().scalar_mat_mul(k).unwrap(),
+        )
+        .unwrap();
+        assert!(lazy_result.approx_eq(&eager, Tolerance::default()));
+
+        let chained = (-(lazy(&c) - lazy(&c) * 1.) + lazy(&a) * &b - &c)
+            .eval()
+            .unwrap();
+        let eager =
+            matrix::Matrix::subtract(&matrix::Matrix::multiply(&a, &b).unwrap(), &c).unwrap();
+        assert!(chained.approx_eq(&eager, Tolerance::default()));
+    }
+
+    #[test]
+    fn nested_products() {
+        let (a, b, c) = (sample(1., 2, 3), sample(2., 3, 3), sample(3., 3, 2));
+        let abc = (lazy(&a) * (lazy(&b) * &c)).eval().unwrap();
+        let left = (lazy(&a) * &b * &c).eval().unwrap();
+        let eager =
+            matrix::Matrix::multiply(&matrix::Matrix::multiply(&a, &b).unwrap(), &c).unwrap();
+        assert!(abc.approx_eq(&eager, Tolerance::default()));
+        assert!(left.approx_eq(&eager, Tolerance::default()));
+
+        // (A + B)C is computed without forming A + B
+        let sum_product = ((lazy(&b) + &b) * &c).eval().unwrap();
+        let eager = matrix::Matrix::multiply(&b, &c)
+            .unwrap()
+            .scalar_mat_mul(2.)
+            .unwrap();
+        assert!(sum_product.approx_eq(&eager, Tolerance::default()));
+    }
+
+    #[test]
+    fn destinations_and_errors() {
+        let a = sample(1., 2, 2);
+        let mut out = matrix::Matrix::new(2, 2);
+        for _ in 0..3 {
+            (lazy(&a) * &a + &a).eval_into(&mut out).unwrap();
+        }
+        let eager = matrix::Matrix::add(&matrix::Matrix::multiply(&a, &a).unwrap(), &a).unwrap();
+        assert!(out.approx_eq(&eager, Tolerance::default()));
+
+        let mut wrong = matrix::Matrix::new(3, 2);
+        assert!((lazy(&a) + &a).eval_into(&mut wrong).is_err());
+        let b = sample(0., 3, 2);
+        assert!((lazy(&a) * &b).eval().is_err());
+        assert!((lazy(&a) + &b).eval().is_err());
+        assert!((lazy(&a) * (lazy(&b) * &a)).eval().is_err());
+    }
+
+    #[test]
+    fn repeated_evaluation_reuses_temporaries() {
+        let (a, b, c) = (sample(1., 2, 3), sample(2., 3, 3), sample(3., 3, 2));
+        let e = lazy(&a) * (lazy(&b) * &c);
+        // The temporary holding B C in the prepared tree
+        let temporary = |e: &Expr| match e.prepared.get() {
+            Some(Node::Dot(_, rhs, _)) => match rhs.as_ref() {
+                Node::Owned(m) => Some(m as *const matrix::Matrix),
+                _ => None,
+            },
+            _ => None,
+        };
+        assert!(temporary(&e).is_none());
+        let mut out = matrix::Matrix::new(2, 2);
+        e.eval_into(&mut out).unwrap();
+        let first = (temporary(&e).unwrap(), out.clone());
+        for _ in 0..3 {
+            e.eval_into(&mut out).unwrap();
+            assert_eq!(temporary(&e), Some(first.0));
+            assert!(out.approx_eq(&first.1, Tolerance::exact()));
+        }
+    }
+}
@@ -0,0 +1,958 @@
+use crate::matrix::MatrixError;
+use std::fmt;
+use std::ops::Index;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Represents a rows x cols matrix over the finite field GF(p),
+/// the integers modulo a prime p.
+/// Every element is kept reduced into 0..p.
+pub struct GfpMatrix {
+    pub rows: usize,
+    pub cols: usize,
+    p: u64,
+    mat: Vec<Vec<u64>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Represents a rows x cols matrix over GF(2).
+/// Each row is packed into 64 bit words so that row operations
+/// work on 64 elements at a time.
+pub struct Gf2Matrix {
+    pub rows: usize,
+    pub cols: usize,
+    mat: Vec<Vec<u64>>,
+}
+
+impl Index<(usize, usize)> for GfpMatrix {
+    type Output = u64;
+
+    /// Returns the element at (row, col)
+    fn index(&self, (row, col): (usize, usize)) -> &u64 {
+        &self.mat[row][col]
+    }
+}
+
+impl fmt::Display for GfpMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.mat.iter() {
+            let row: Vec<String> = row.iter().map(|v| v.to_string()).collect();
+            writeln!(f, "[{}]", row.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Gf2Matrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for r in 0..self.rows {
+            let row: String = (0..self.cols)
+                .map(|c| if self.get(r, c) { '1' } else { '0' })
+                .collect();
+            writeln!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns whether n is prime, using a Miller-Rabin test
+/// with bases that are deterministic for every u64
+///
+/// # Examples
+/// ```
+/// use ralgeb::finite_field::is_prime;
+/// assert!(is_prime(65537));
+/// assert!(!is_prime(561));
+/// ```
+pub fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    for &b in BASES.iter() {
+        if n % b == 0 {
+            return n == b;
+        }
+    }
+    let mut d = n - 1;
+    let mut s = 0;
+    while d % 2 == 0 {
+        d /= 2;
+        s += 1;
+    }
+    'bases: for &b in BASES.iter() {
+        let mut x = pow_mod(b, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                continue 'bases;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+// Sums of residues can exceed u64::MAX when p > 2^63, so they are taken in u128
+fn add_mod(a: u64, b: u64, p: u64) -> u64 {
+    ((a as u128 + b as u128) % p as u128) as u64
+}
+
+fn sub_mod(a: u64, b: u64, p: u64) -> u64 {
+    ((a as u128 + p as u128 - b as u128) % p as u128) as u64
+}
+
+fn mul_mod(a: u64, b: u64, p: u64) -> u64 {
+    (a as u128 * b as u128 % p as u128) as u64
+}
+
+fn pow_mod(mut base: u64, mut e: u64, p: u64) -> u64 {
+    let mut result = 1 % p;
+    base %= p;
+    while e > 0 {
+        if e & 1 == 1 {
+            result = mul_mod(result, base, p);
+        }
+        base = mul_mod(base, base, p);
+        e >>= 1;
+    }
+    result
+}
+
+fn dimension_error(op: &str, r1: usize, c1: usize, r2: usize, c2: usize) -> MatrixError {
+    MatrixError::new(format!(
+        "Cannot {} a {}x{} matrix with a {}x{} matrix",
+        op, r1, c1, r2, c2
+    ))
+}
+
+fn modulus_error(op: &str, p1: u64, p2: u64) -> MatrixError {
+    MatrixError::new(format!(
+        "Cannot {} a matrix over GF({}) with a matrix over GF({})",
+        op, p1, p2
+    ))
+}
+
+impl GfpMatrix {
+    /// Returns a matrix over GF(p) with all 0 values
+    ///
+    /// # Arguments
+    /// `rows` - The number of rows in a matrix.
+    /// `cols` - The number of columns in a matrix.
+    /// `p` - The modulus. It must be prime.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::GfpMatrix;
+    /// let m = GfpMatrix::new(2, 3, 7).unwrap();
+    /// assert!(GfpMatrix::new(2, 3, 6).is_err());
+    /// ```
+    pub fn new(rows: usize, cols: usize, p: u64) -> Result<GfpMatrix, MatrixError> {
+        if !is_prime(p) {
+            return Err(MatrixError::new(format!("The modulus {} is not prime", p)));
+        }
+        Ok(GfpMatrix {
+            rows,
+            cols,
+            p,
+            mat: vec![vec![0; cols]; rows],
+        })
+    }
+
+    /// Returns the n x n identity matrix over GF(p)
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::GfpMatrix;
+    /// let m = GfpMatrix::identity(3, 5).unwrap();
+    /// assert_eq!(m[(2, 2)], 1);
+    /// ```
+    pub fn identity(n: usize, p: u64) -> Result<GfpMatrix, MatrixError> {
+        let mut m = GfpMatrix::new(n, n, p)?;
+        for i in 0..n {
+            m.mat[i][i] = 1;
+        }
+        Ok(m)
+    }
+
+    /// Creates a matrix over GF(p) from a list of rows of integers.
+    /// The integers are reduced modulo p, negative values included.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::GfpMatrix;
+    /// let m = GfpMatrix::from_rows(vec![vec![-1, 8], vec![3, 4]], 7).unwrap();
+    /// assert_eq!(m.get_row(0).unwrap(), vec![6, 1]);
+    /// ```
+    pub fn from_rows(rows: Vec<Vec<i64>>, p: u64) -> Result<GfpMatrix, MatrixError> {
+        let cols = rows.first().map_or(0, |r| r.len());
+        if rows.iter().any(|r| r.len() != cols) {
+            return Err(MatrixError::new(
+                "All the rows should have the same length".to_string(),
+            ));
+        }
+        let mut m = GfpMatrix::new(rows.len(), cols, p)?;
+        for (r, row) in rows.iter().enumerate() {
+            for (c, v) in row.iter().enumerate() {
+                m.mat[r][c] = (*v as i128).rem_euclid(p as i128) as u64;
+            }
+        }
+        Ok(m)
+    }
+
+    /// Returns the modulus p
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::GfpMatrix;
+    /// assert_eq!(GfpMatrix::new(1, 1, 13).unwrap().modulus(), 13);
+    /// ```
+    pub fn modulus(&self) -> u64 {
+        self.p
+    }
+
+    /// Sets the element at (row, col), reducing the value modulo p
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::GfpMatrix;
+    /// let mut m = GfpMatrix::new(2, 2, 5).unwrap();
+    /// m.set(0, 1, 12);
+    /// assert_eq!(m[(0, 1)], 2);
+    /// ```
+    pub fn set(&mut self, row: usize, col: usize, value: u64) {
+        self.mat[row][col] = value % self.p;
+    }
+
+    /// Will return a row from the matrix
+    /// # Arguments
+    /// `row_num` - The row number to return. Indexing starts from 0.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::GfpMatrix;
+    /// let m = GfpMatrix::identity(2, 3).unwrap();
+    /// assert_eq!(m.get_row(1).unwrap(), vec![0, 1]);
+    /// ```
+    pub fn get_row(&self, row_num: usize) -> Result<Vec<u64>, MatrixError> {
+        if row_num >= self.rows {
+            Err(MatrixError::new(format!(
+                "The matrix does not contain row: {}",
+                row_num
+            )))
+        } else {
+            Ok(self.mat[row_num].to_owned())
+        }
+    }
+
+    /// Adds 2 matrices over the same field
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::GfpMatrix;
+    /// let m = GfpMatrix::from_rows(vec![vec![3, 4]], 5).unwrap();
+    /// let s = GfpMatrix::add(&m, &m).unwrap();
+    /// assert_eq!(s.get_row(0).unwrap(), vec![1, 3]);
+    /// ```
+    pub fn add(m1: &GfpMatrix, m2: &GfpMatrix) -> Result<GfpMatrix, MatrixError> {
+        m1.same_shape(m2, "add")?;
+        let mut res = m1.clone();
+        for (row, other) in res.mat.iter_mut().zip(m2.mat.iter()) {
+            for (v, o) in row.iter_mut().zip(other.iter()) {
+                *v = add_mod(*v, *o, m1.p);
+            }
+        }
+        Ok(res)
+    }
+
+    /// Subtracts m2 from m1 over the same field
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::GfpMatrix;
+    /// let m1 = GfpMatrix::from_rows(vec![vec![1, 4]], 5).unwrap();
+    /// let m2 = GfpMatrix::from_rows(vec![vec![3, 1]], 5).unwrap();
+    /// let d = GfpMatrix::subtract(&m1, &m2).unwrap();
+    /// assert_eq!(d.get_row(0).unwrap(), vec![3, 3]);
+    /// ```
+    pub fn subtract(m1: &GfpMatrix, m2: &GfpMatrix) -> Result<GfpMatrix, MatrixError> {
+        m1.same_shape(m2, "subtract")?;
+        let mut res = m1.clone();
+        for (row, other) in res.mat.iter_mut().zip(m2.mat.iter()) {
+            for (v, o) in row.iter_mut().zip(other.iter()) {
+                *v = sub_mod(*v, *o, m1.p);
+            }
+        }
+        Ok(res)
+    }
+
+    /// Multiplies 2 matrices over the same field
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::GfpMatrix;
+    /// let m1 = GfpMatrix::from_rows(vec![vec![1, 2], vec![3, 4]], 5).unwrap();
+    /// let m2 = GfpMatrix::identity(2, 5).unwrap();
+    /// assert_eq!(GfpMatrix::multiply(&m1, &m2).unwrap(), m1);
+    /// ```
+    pub fn multiply(m1: &GfpMatrix, m2: &GfpMatrix) -> Result<GfpMatrix, MatrixError> {
+        if m1.p != m2.p {
+            return Err(modulus_error("multiply", m1.p, m2.p));
+        }
+        if m1.cols != m2.rows {
+            return Err(dimension_error(
+                "multiply", m1.rows, m1.cols, m2.rows, m2.cols,
+            ));
+        }
+        let p = m1.p as u128;
+        let mut res = GfpMatrix::new(m1.rows, m2.cols, m1.p)?;
+        for i in 0..m1.rows {
+            for j in 0..m2.cols {
+                let mut acc: u128 = 0;
+                for k in 0..m1.cols {
+                    acc = (acc + m1.mat[i][k] as u128 * m2.mat[k][j] as u128) % p;
+                }
+                res.mat[i][j] = acc as u64;
+            }
+        }
+        Ok(res)
+    }
+
+    /// Returns the transposed matrix
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::GfpMatrix;
+    /// let m = GfpMatrix::from_rows(vec![vec![1, 2, 3]], 5).unwrap();
+    /// assert_eq!(m.transpose().rows, 3);
+    /// ```
+    pub fn transpose(&self) -> GfpMatrix {
+        let mut res = GfpMatrix {
+            rows: self.cols,
+            cols: self.rows,
+            p: self.p,
+            mat: vec![vec![0; self.rows]; self.cols],
+        };
+        for (r, row) in self.mat.iter().enumerate() {
+            for (c, v) in row.iter().enumerate() {
+                res.mat[c][r] = *v;
+            }
+        }
+        res
+    }
+
+    /// Returns the reduced row echelon form modulo p
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::GfpMatrix;
+    /// let m = GfpMatrix::from_rows(vec![vec![2, 4], vec![1, 3]], 5).unwrap();
+    /// assert_eq!(m.rref(), GfpMatrix::identity(2, 5).unwrap());
+    /// ```
+    pub fn rref(&self) -> GfpMatrix {
+        self.reduce(self.cols).0
+    }
+
+    /// Returns the rank of the matrix over GF(p)
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::GfpMatrix;
+    /// // Full rank over the rationals but not modulo 3
+    /// let m = GfpMatrix::from_rows(vec![vec![1, 2], vec![2, 1]], 3).unwrap();
+    /// assert_eq!(m.rank(), 1);
+    /// ```
+    pub fn rank(&self) -> usize {
+        self.reduce(self.cols).1.len()
+    }
+
+    /// Returns the inverse of a square matrix modulo p
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::GfpMatrix;
+    /// let m = GfpMatrix::from_rows(vec![vec![1, 2], vec![3, 4]], 7).unwrap();
+    /// let inv = m.inverse().unwrap();
+    /// let id = GfpMatrix::multiply(&m, &inv).unwrap();
+    /// assert_eq!(id, GfpMatrix::identity(2, 7).unwrap());
+    /// ```
+    pub fn inverse(&self) -> Result<GfpMatrix, MatrixError> {
+        if self.rows != self.cols {
+            return Err(MatrixError::new(
+                "The matrix is not a square matrix".to_string(),
+            ));
+        }
+        self.solve(&GfpMatrix::identity(self.rows, self.p)?)
+    }
+
+    /// Solves AX = B modulo p for a square, invertible A
+    ///
+    /// # Arguments
+    /// `b` - The right hand side. Must have as many rows as the matrix.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::GfpMatrix;
+    /// let a = GfpMatrix::from_rows(vec![vec![1, 1], vec![1, 2]], 11).unwrap();
+    /// let b = GfpMatrix::from_rows(vec![vec![3], vec![5]], 11).unwrap();
+    /// let x = a.solve(&b).unwrap();
+    /// assert_eq!(x.get_row(0).unwrap(), vec![1]);
+    /// assert_eq!(x.get_row(1).unwrap(), vec![2]);
+    /// ```
+    pub fn solve(&self, b: &GfpMatrix) -> Result<GfpMatrix, MatrixError> {
+        if self.rows != self.cols {
+            return Err(MatrixError::new(
+                "The matrix is not a square matrix".to_string(),
+            ));
+        }
+        if b.p != self.p {
+            return Err(modulus_error("solve", self.p, b.p));
+        }
+        if b.rows != self.rows {
+            return Err(dimension_error(
+                "solve", self.rows, self.cols, b.rows, b.cols,
+            ));
+        }
+        let n = self.rows;
+        let mut augmented = self.clone();
+        for (row, rhs) in augmented.mat.iter_mut().zip(b.mat.iter()) {
+            row.extend_from_slice(rhs);
+        }
+        augmented.cols += b.cols;
+        let (reduced, pivots) = augmented.reduce(n);
+        if pivots.len() < n {
+            return Err(MatrixError::new("The matrix is singular".to_string()));
+        }
+        Ok(GfpMatrix {
+            rows: n,
+            cols: b.cols,
+            p: self.p,
+            mat: reduced.mat.into_iter().map(|r| r[n..].to_vec()).collect(),
+        })
+    }
+
+    /// Gauss-Jordan elimination with pivots taken from the first
+    /// `pivot_cols` columns. Returns the reduced matrix and the pivot columns.
+    fn reduce(&self, pivot_cols: usize) -> (GfpMatrix, Vec<usize>) {
+        let p = self.p;
+        let mut m = self.clone();
+        let mut pivots = Vec::new();
+        for c in 0..pivot_cols {
+            let r = pivots.len();
+            if r == m.rows {
+                break;
+            }
+            let pr = match (r..m.rows).find(|&i| m.mat[i][c] != 0) {
+                Some(i) => i,
+                None => continue,
+            };
+            m.mat.swap(r, pr);
+            let inv = pow_mod(m.mat[r][c], p - 2, p);
+            for v in m.mat[r].iter_mut() {
+                *v = mul_mod(*v, inv, p);
+            }
+            let pivot_row = m.mat[r].clone();
+            for (i, row) in m.mat.iter_mut().enumerate() {
+                if i == r || row[c] == 0 {
+                    continue;
+                }
+                let f = row[c];
+                for (v, pv) in row.iter_mut().zip(pivot_row.iter()) {
+                    *v = sub_mod(*v, mul_mod(f, *pv, p), p);
+                }
+            }
+            pivots.push(c);
+        }
+        (m, pivots)
+    }
+
+    fn same_shape(&self, other: &GfpMatrix, op: &str) -> Result<(), MatrixError> {
+        if self.p != other.p {
+            Err(modulus_error(op, self.p, other.p))
+        } else if self.rows != other.rows || self.cols != other.cols {
+            Err(dimension_error(
+                op, self.rows, self.cols, other.rows, other.cols,
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Gf2Matrix {
+    /// Returns a matrix over GF(2) with all 0 values
+    ///
+    /// # Arguments
+    /// `rows` - The number of rows in a matrix.
+    /// `cols` - The number of columns in a matrix.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::Gf2Matrix;
+    /// let m = Gf2Matrix::new(3, 100);
+    /// assert!(!m.get(2, 99));
+    /// ```
+    pub fn new(rows: usize, cols: usize) -> Gf2Matrix {
+        Gf2Matrix {
+            rows,
+            cols,
+            mat: vec![vec![0; cols.div_ceil(64)]; rows],
+        }
+    }
+
+    /// Returns the n x n identity matrix over GF(2)
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::Gf2Matrix;
+    /// let m = Gf2Matrix::identity(3);
+    /// assert!(m.get(1, 1));
+    /// ```
+    pub fn identity(n: usize) -> Gf2Matrix {
+        let mut m = Gf2Matrix::new(n, n);
+        for i in 0..n {
+            m.set(i, i, true);
+        }
+        m
+    }
+
+    /// Creates a matrix over GF(2) from rows of 0/1 values.
+    /// Every value is taken modulo 2.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::Gf2Matrix;
+    /// let m = Gf2Matrix::from_rows(vec![vec![1, 0, 1], vec![0, 1, 3]]).unwrap();
+    /// assert_eq!(m.get_row(1).unwrap(), vec![0, 1, 1]);
+    /// ```
+    pub fn from_rows(rows: Vec<Vec<u8>>) -> Result<Gf2Matrix, MatrixError> {
+        let cols = rows.first().map_or(0, |r| r.len());
+        if rows.iter().any(|r| r.len() != cols) {
+            return Err(MatrixError::new(
+                "All the rows should have the same length".to_string(),
+            ));
+        }
+        let mut m = Gf2Matrix::new(rows.len(), cols);
+        for (r, row) in rows.iter().enumerate() {
+            for (c, v) in row.iter().enumerate() {
+                m.set(r, c, v % 2 == 1);
+            }
+        }
+        Ok(m)
+    }
+
+    /// Returns the element at (row, col).
+    /// Panics if the position is outside the matrix.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::Gf2Matrix;
+    /// assert!(Gf2Matrix::identity(2).get(0, 0));
+    /// ```
+    pub fn get(&self, row: usize, col: usize) -> bool {
+        self.bounds_check(row, col);
+        self.mat[row][col / 64] >> (col % 64) & 1 == 1
+    }
+
+    /// Sets the element at (row, col).
+    /// Panics if the position is outside the matrix, as the padding bits
+    /// of the last word would otherwise be silently written.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::Gf2Matrix;
+    /// let mut m = Gf2Matrix::new(2, 2);
+    /// m.set(1, 0, true);
+    /// assert!(m.get(1, 0));
+    /// ```
+    pub fn set(&mut self, row: usize, col: usize, value: bool) {
+        self.bounds_check(row, col);
+        let bit = 1u64 << (col % 64);
+        if value {
+            self.mat[row][col / 64] |= bit;
+        } else {
+            self.mat[row][col / 64] &= !bit;
+        }
+    }
+
+    /// Will return a row from the matrix as 0/1 values
+    /// # Arguments
+    /// `row_num` - The row number to return. Indexing starts from 0.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::Gf2Matrix;
+    /// assert_eq!(Gf2Matrix::identity(3).get_row(2).unwrap(), vec![0, 0, 1]);
+    /// ```
+    pub fn get_row(&self, row_num: usize) -> Result<Vec<u8>, MatrixError> {
+        if row_num >= self.rows {
+            return Err(MatrixError::new(format!(
+                "The matrix does not contain row: {}",
+                row_num
+            )));
+        }
+        Ok((0..self.cols).map(|c| self.get(row_num, c) as u8).collect())
+    }
+
+    /// Adds 2 matrices over GF(2), which is an element-wise XOR
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::Gf2Matrix;
+    /// let m = Gf2Matrix::identity(2);
+    /// let s = Gf2Matrix::add(&m, &m).unwrap();
+    /// assert_eq!(s, Gf2Matrix::new(2, 2));
+    /// ```
+    pub fn add(m1: &Gf2Matrix, m2: &Gf2Matrix) -> Result<Gf2Matrix, MatrixError> {
+        if m1.rows != m2.rows || m1.cols != m2.cols {
+            return Err(dimension_error("add", m1.rows, m1.cols, m2.rows, m2.cols));
+        }
+        let mut res = m1.clone();
+        for (row, other) in res.mat.iter_mut().zip(m2.mat.iter()) {
+            xor_into(row, other);
+        }
+        Ok(res)
+    }
+
+    /// Multiplies 2 matrices over GF(2).
+    /// Each result row is the XOR of the rows of m2 selected by m1.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::Gf2Matrix;
+    /// let m1 = Gf2Matrix::from_rows(vec![vec![1, 1], vec![0, 1]]).unwrap();
+    /// let m2 = Gf2Matrix::multiply(&m1, &m1).unwrap();
+    /// assert_eq!(m2, Gf2Matrix::identity(2));
+    /// ```
+    pub fn multiply(m1: &Gf2Matrix, m2: &Gf2Matrix) -> Result<Gf2Matrix, MatrixError> {
+        if m1.cols != m2.rows {
+            return Err(dimension_error(
+                "multiply", m1.rows, m1.cols, m2.rows, m2.cols,
+            ));
+        }
+        let mut res = Gf2Matrix::new(m1.rows, m2.cols);
+        for i in 0..m1.rows {
+            for k in 0..m1.cols {
+                if m1.get(i, k) {
+                    xor_into(&mut res.mat[i], &m2.mat[k]);
+                }
+            }
+        }
+        Ok(res)
+    }
+
+    /// Returns the transposed matrix
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::Gf2Matrix;
+    /// let m = Gf2Matrix::from_rows(vec![vec![1, 0, 1]]).unwrap();
+    /// assert_eq!(m.transpose().get_row(2).unwrap(), vec![1]);
+    /// ```
+    pub fn transpose(&self) -> Gf2Matrix {
+        let mut res = Gf2Matrix::new(self.cols, self.rows);
+        for r in 0..self.rows {
+            for c in 0..self.cols {
+                if self.get(r, c) {
+                    res.set(c, r, true);
+                }
+            }
+        }
+        res
+    }
+
+    /// Returns the reduced row echelon form over GF(2)
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::Gf2Matrix;
+    /// let m = Gf2Matrix::from_rows(vec![vec![1, 1], vec![1, 0]]).unwrap();
+    /// assert_eq!(m.rref(), Gf2Matrix::identity(2));
+    /// ```
+    pub fn rref(&self) -> Gf2Matrix {
+        self.reduce(self.cols).0
+    }
+
+    /// Returns the rank of the matrix over GF(2)
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::Gf2Matrix;
+    /// let m = Gf2Matrix::from_rows(vec![vec![1, 1, 0], vec![0, 1, 1], vec![1, 0, 1]]).unwrap();
+    /// assert_eq!(m.rank(), 2);
+    /// ```
+    pub fn rank(&self) -> usize {
+        self.reduce(self.cols).1.len()
+    }
+
+    /// Returns the inverse of a square matrix over GF(2)
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::Gf2Matrix;
+    /// let m = Gf2Matrix::from_rows(vec![vec![1, 1], vec![0, 1]]).unwrap();
+    /// assert_eq!(m.inverse().unwrap(), m);
+    /// ```
+    pub fn inverse(&self) -> Result<Gf2Matrix, MatrixError> {
+        if self.rows != self.cols {
+            return Err(MatrixError::new(
+                "The matrix is not a square matrix".to_string(),
+            ));
+        }
+        self.solve(&Gf2Matrix::identity(self.rows))
+    }
+
+    /// Solves AX = B over GF(2) for a square, invertible A
+    ///
+    /// # Arguments
+    /// `b` - The right hand side. Must have as many rows as the matrix.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::finite_field::Gf2Matrix;
+    /// let a = Gf2Matrix::from_rows(vec![vec![1, 1], vec![0, 1]]).unwrap();
+    /// let b = Gf2Matrix::from_rows(vec![vec![0], vec![1]]).unwrap();
+    /// assert_eq!(a.solve(&b).unwrap().get_row(0).unwrap(), vec![1]);
+    /// ```
+    pub fn solve(&self, b: &Gf2Matrix) -> Result<Gf2Matrix, MatrixError> {
+        if self.rows != self.cols {
+            return Err(MatrixError::new(
+                "The matrix is not a square matrix".to_string(),
+            ));
+        }
+        if b.rows != self.rows {
+            return Err(dimension_error(
+                "solve", self.rows, self.cols, b.rows, b.cols,
+            ));
+        }
+        let n = self.rows;
+        let mut augmented = Gf2Matrix::new(n, n + b.cols);
+        for r in 0..n {
+            for c in 0..n {
+                augmented.set(r, c, self.get(r, c));
+            }
+            for c in 0..b.cols {
+                augmented.set(r, n + c, b.get(r, c));
+            }
+        }
+        let (reduced, pivots) = augmented.reduce(n);
+        if pivots.len() < n {
+            return Err(MatrixError::new("The matrix is singular".to_string()));
+        }
+        let mut x = Gf2Matrix::new(n, b.cols);
+        for r in 0..n {
+            for c in 0..b.cols {
+                x.set(r, c, reduced.get(r, n + c));
+            }
+        }
+        Ok(x)
+    }
+
+    fn reduce(&self, pivot_cols: usize) -> (Gf2Matrix, Vec<usize>) {
+        let mut m = self.clone();
+        let mut pivots = Vec::new();
+        for c in 0..pivot_cols {
+            let r = pivots.len();
+            if r == m.rows {
+                break;
+            }
+            let pr = match (r..m.rows).find(|&i| m.get(i, c)) {
+                Some(i) => i,
+                None => continue,
+            };
+            m.mat.swap(r, pr);
+            let pivot_row = m.mat[r].clone();
+            for i in 0..m.rows {
+                if i != r && m.get(i, c) {
+                    xor_into(&mut m.mat[i], &pivot_row);
+                }
+            }
+            pivots.push(c);
+        }
+        (m, pivots)
+    }
+
+    fn bounds_check(&self, row: usize, col: usize) {
+        if row >= self.rows || col >= self.cols {
+            panic!(
+                "Position ({}, {}) is outside the {}x{} matrix",
+                row, col, self.rows, self.cols
+            );
+        }
+    }
+}
+
+fn xor_into(row: &mut [u64], other: &[u64]) {
+    for (w, o) in row.iter_mut().zip(other.iter()) {
+        *w ^= o;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::finite_field::{is_prime, Gf2Matrix, GfpMatrix};
+
+    #[test]
+    fn primes() {
+        let small: Vec<u64> = (0..30).filter(|n| is_prime(*n)).collect();
+        assert_eq!(small, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert!(is_prime(18_446_744_073_709_551_557));
+        assert!(!is_prime(3_215_031_751));
+        assert!(!is_prime(u64::MAX));
+    }
+
+    #[test]
+    fn gfp_construction() {
+        let m = GfpMatrix::from_rows(vec![vec![-8, 15], vec![7, 0]], 7).unwrap();
+        assert_eq!(m.get_row(0).unwrap(), vec![6, 1]);
+        assert_eq!(m.get_row(1).unwrap(), vec![0, 0]);
+        assert!(m.get_row(2).is_err());
+        assert!(GfpMatrix::from_rows(vec![vec![1], vec![1, 2]], 7).is_err());
+        assert!(GfpMatrix::identity(2, 1).is_err());
+        assert_eq!(format!("{}", m), "[6 1]\n[0 0]\n");
+    }
+
+    #[test]
+    fn gfp_arithmetic() {
+        let a = GfpMatrix::from_rows(vec![vec![1, 2], vec![3, 4]], 5).unwrap();
+        let b = GfpMatrix::from_rows(vec![vec![4, 4], vec![0, 1]], 5).unwrap();
+        let s = GfpMatrix::add(&a, &b).unwrap();
+        assert_eq!(
+            s,
+            GfpMatrix::from_rows(vec![vec![0, 1], vec![3, 0]], 5).unwrap()
+        );
+        assert_eq!(GfpMatrix::subtract(&s, &b).unwrap(), a);
+        let p = GfpMatrix::multiply(&a, &b).unwrap();
+        assert_eq!(
+            p,
+            GfpMatrix::from_rows(vec![vec![4, 6], vec![12, 16]], 5).unwrap()
+        );
+        assert_eq!(a.transpose().get_row(0).unwrap(), vec![1, 3]);
+
+        let other_field = GfpMatrix::identity(2, 7).unwrap();
+        assert!(GfpMatrix::add(&a, &other_field).is_err());
+        assert!(GfpMatrix::multiply(&a, &other_field).is_err());
+        assert!(a.solve(&other_field).is_err());
+        let message = format!("{:?}", GfpMatrix::subtract(&a, &other_field));
+        assert!(message.contains("GF(5)") && message.contains("GF(7)"));
+
+        // Large prime close to u64::MAX must not overflow
+        let p = 18_446_744_073_709_551_557;
+        let big = GfpMatrix::from_rows(vec![vec![-1]], p).unwrap();
+        let sq = GfpMatrix::multiply(&big, &big).unwrap();
+        assert_eq!(sq[(0, 0)], 1);
+        let sum = GfpMatrix::add(&big, &big).unwrap();
+        assert_eq!(sum[(0, 0)], p - 2);
+        assert_eq!(GfpMatrix::subtract(&sum, &big).unwrap(), big);
+        let zero = GfpMatrix::new(1, 1, p).unwrap();
+        assert_eq!(GfpMatrix::subtract(&zero, &big).unwrap()[(0, 0)], 1);
+    }
+
+    #[test]
+    fn gfp_elimination_large_prime() {
+        let p = 18_446_744_073_709_551_557;
+        let m = GfpMatrix::from_rows(vec![vec![-1, -2, 3], vec![-4, 5, -6], vec![7, -8, -10]], p)
+            .unwrap();
+        assert_eq!(m.rank(), 3);
+        assert_eq!(m.rref(), GfpMatrix::identity(3, p).unwrap());
+        let inv = m.inverse().unwrap();
+        assert_eq!(
+            GfpMatrix::multiply(&m, &inv).unwrap(),
+            GfpMatrix::identity(3, p).unwrap()
+        );
+        let singular = GfpMatrix::from_rows(vec![vec![-1, -2], vec![-2, -4]], p).unwrap();
+        assert_eq!(singular.rank(), 1);
+        assert!(singular.inverse().is_err());
+    }
+
+    #[test]
+    fn gfp_elimination() {
+        let m =
+            GfpMatrix::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 10]], 11).unwrap();
+        assert_eq!(m.rank(), 3);
+        let inv = m.inverse().unwrap();
+        assert_eq!(
+            GfpMatrix::multiply(&m, &inv).unwrap(),
+            GfpMatrix::identity(3, 11).unwrap()
+        );
+        let b = GfpMatrix::from_rows(vec![vec![1], vec![0], vec![5]], 11).unwrap();
+        let x = m.solve(&b).unwrap();
+        assert_eq!(GfpMatrix::multiply(&m, &x).unwrap(), b);
+
+        // Singular modulo 3 only
+        let m = GfpMatrix::from_rows(vec![vec![1, 1], vec![1, 4]], 3).unwrap();
+        assert_eq!(m.rank(), 1);
+        assert!(m.inverse().is_err());
+        assert_eq!(
+            m.rref(),
+            GfpMatrix::from_rows(vec![vec![1, 1], vec![0, 0]], 3).unwrap()
+        );
+        assert!(GfpMatrix::new(2, 3, 3).unwrap().inverse().is_err());
+    }
+
+    #[test]
+    fn gf2_construction() {
+        let mut m = Gf2Matrix::new(2, 130);
+        m.set(1, 129, true);
+        m.set(0, 64, true);
+        assert!(m.get(1, 129));
+        assert!(m.get(0, 64));
+        m.set(0, 64, false);
+        assert!(!m.get(0, 64));
+        let m = Gf2Matrix::from_rows(vec![vec![1, 0], vec![2, 3]]).unwrap();
+        assert_eq!(format!("{}", m), "10\n01\n");
+        assert!(m.get_row(2).is_err());
+        assert!(Gf2Matrix::from_rows(vec![vec![1], vec![]]).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn gf2_set_outside_columns() {
+        // Column 2 lives in the padding bits of the first word
+        let mut m = Gf2Matrix::new(1, 2);
+        m.set(0, 2, true);
+    }
+
+    #[test]
+    fn gf2_hamming_code() {
+        // Generator and parity check matrices of the [7, 4] Hamming code
+        let g = Gf2Matrix::from_rows(vec![
+            vec![1, 0, 0, 0, 1, 1, 0],
+            vec![0, 1, 0, 0, 1, 0, 1],
+            vec![0, 0, 1, 0, 0, 1, 1],
+            vec![0, 0, 0, 1, 1, 1, 1],
+        ])
+        .unwrap();
+        let h = Gf2Matrix::from_rows(vec![
+            vec![1, 1, 0, 1, 1, 0, 0],
+            vec![1, 0, 1, 1, 0, 1, 0],
+            vec![0, 1, 1, 1, 0, 0, 1],
+        ])
+        .unwrap();
+        let product = Gf2Matrix::multiply(&g, &h.transpose()).unwrap();
+        assert_eq!(product, Gf2Matrix::new(4, 3));
+        assert_eq!(g.rank(), 4);
+        assert_eq!(h.rank(), 3);
+        assert!(Gf2Matrix::add(&g, &h).is_err());
+    }
+
+    #[test]
+    fn gf2_elimination() {
+        let m = Gf2Matrix::from_rows(vec![vec![1, 1, 0], vec![1, 0, 1], vec![0, 1, 0]]).unwrap();
+        let inv = m.inverse().unwrap();
+        assert_eq!(
+            Gf2Matrix::multiply(&m, &inv).unwrap(),
+            Gf2Matrix::identity(3)
+        );
+        let b = Gf2Matrix::from_rows(vec![vec![1], vec![1], vec![1]]).unwrap();
+        let x = m.solve(&b).unwrap();
+        assert_eq!(Gf2Matrix::multiply(&m, &x).unwrap(), b);
+        assert_eq!(m.rref(), Gf2Matrix::identity(3));
+
+        let singular = Gf2Matrix::from_rows(vec![vec![1, 1], vec![1, 1]]).unwrap();
+        assert!(singular.inverse().is_err());
+        assert_eq!(singular.rank(), 1);
+    }
+}
@@ -0,0 +1,488 @@
+use crate::matrix::{Matrix, MatrixError};
+use crate::point::Point;
+use crate::polynomial::Polynomial;
+
+#[derive(Debug, Clone)]
+/// The result of a least squares fit
+pub struct Fit {
+    /// The fitted coefficients, one per column of the design matrix
+    pub coefficients: Vec<f64>,
+    /// The residuals y - Xβ, one per observation
+    pub residuals: Vec<f64>,
+    /// The coefficient of determination, 1 - RSS / TSS around the mean.
+    /// NaN when every observation is the same.
+    pub r_squared: f64,
+    /// The standard error of each coefficient
+    pub standard_errors: Vec<f64>,
+}
+
+impl Fit {
+    /// Returns the predictions Xβ for a design matrix with
+    /// the same columns as the one that was fitted
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::fitting;
+    /// use ralgeb::matrix::Matrix;
+    /// let x = fitting::polynomial_design(&[0., 1., 2.], 1);
+    /// let fit = fitting::least_squares(&x, &[1., 3., 5.]).unwrap();
+    /// let p = fit.predict(&fitting::polynomial_design(&[10.], 1)).unwrap();
+    /// assert!((p[0] - 21.).abs() < 1e-12);
+    /// ```
+    pub fn predict(&self, design: &Matrix) -> Result<Vec<f64>, MatrixError> {
+        if design.cols != self.coefficients.len() {
+            return Err(MatrixError::new(format!(
+                "Expected {} columns but got {}",
+                self.coefficients.len(),
+                design.cols
+            )));
+        }
+        Ok((0..design.rows)
+            .map(|r| Matrix::dot_product(&design.get_row(r).unwrap(), &self.coefficients))
+            .collect())
+    }
+
+    /// Returns the coefficients as a polynomial, which is the fitted
+    /// curve when the design matrix came from `polynomial_design`
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::fitting;
+    /// use ralgeb::point::Point;
+    /// let pts: Vec<Point> = (0..4).map(|i| Point::new(i as f64, (i * i + 1) as f64)).collect();
+    /// let curve = fitting::polynomial_fit(&pts, 2).unwrap().polynomial();
+    /// assert!((curve.evaluate(4.) - 17.).abs() < 1e-9);
+    /// ```
+    pub fn polynomial(&self) -> Polynomial {
+        Polynomial::new(self.coefficients.clone())
+    }
+}
+
+/// Returns the design matrix [1, x, x², ..., x^degree] with one row per value
+///
+/// # Examples
+/// ```
+/// use ralgeb::fitting;
+/// let x = fitting::polynomial_design(&[2., 3.], 2);
+/// assert_eq!(x.get_row(1).unwrap(), vec![1., 3., 9.]);
+/// ```
+pub fn polynomial_design(x: &[f64], degree: usize) -> Matrix {
+    Matrix::from_fn(x.len(), degree + 1, |r, c| x[r].powi(c as i32))
+}
+
+/// Returns the features with a leading column of ones for the intercept
+///
+/// # Examples
+/// ```
+/// use ralgeb::fitting;
+/// use ralgeb::matrix::Matrix;
+/// let x = fitting::with_intercept(&Matrix::new_mat(vec![vec![5., 6.]]));
+/// assert_eq!(x.get_row(0).unwrap(), vec![1., 5., 6.]);
+/// ```
+pub fn with_intercept(features: &Matrix) -> Matrix {
+    Matrix::from_fn(features.rows, features.cols + 1, |r, c| {
+        if c == 0 {
+            1.
+        } else {
+            features[(r, c - 1)]
+        }
+    })
+}
+
+/// Solves the ordinary least squares problem min ‖Xβ - y‖
+/// with a Householder QR factorization
+///
+/// # Arguments
+/// `design` - The design matrix X, one row per observation.
+/// `y` - The observations.
+///
+/// # Examples
+/// ```
+/// use ralgeb::fitting;
+/// use ralgeb::matrix::Matrix;
+/// let x = fitting::with_intercept(&Matrix::new_mat(vec![vec![1.], vec![2.], vec![3.], vec![4.]]));
+/// let fit = fitting::least_squares(&x, &[3.1, 4.9, 7.2, 8.8]).unwrap();
+/// assert!((fit.coefficients[1] - 1.94).abs() < 1e-9);
+/// assert!(fit.r_squared > 0.99);
+/// ```
+pub fn least_squares(design: &Matrix, y: &[f64]) -> Result<Fit, MatrixError> {
+    fit(design, y, None, 0.)
+}
+
+/// Solves the weighted least squares problem min Σ wᵢ (xᵢβ - yᵢ)²
+/// Observations with zero weight do not count as residual
+/// degrees of freedom for the standard errors.
+///
+/// # Arguments
+/// `design` - The design matrix X, one row per observation.
+/// `y` - The observations.
+/// `weights` - A non negative weight per observation, usually 1 / variance.
+///
+/// # Examples
+/// ```
+/// use ralgeb::fitting;
+/// let x = fitting::polynomial_design(&[0., 1., 2.], 0);
+/// // A weighted mean
+/// let fit = fitting::weighted_least_squares(&x, &[1., 2., 6.], &[1., 1., 2.]).unwrap();
+/// assert!((fit.coefficients[0] - 3.75).abs() < 1e-12);
+/// ```
+pub fn weighted_least_squares(
+    design: &Matrix,
+    y: &[f64],
+    weights: &[f64],
+) -> Result<Fit, MatrixError> {
+    fit(design, y, Some(weights), 0.)
+}
+
+/// Solves the ridge (Tikhonov) problem min ‖Xβ - y‖² + λ‖β‖².
+/// Every coefficient is penalized, so center the data or leave out
+/// the intercept column when it should not be shrunk.
+///
+/// # Arguments
+/// `design` - The design matrix X, one row per observation.
+/// `y` - The observations.
+/// `lambda` - The non negative regularization strength.
+///
+/// # Examples
+/// ```
+/// use ralgeb::fitting;
+/// use ralgeb::matrix::Matrix;
+/// let x = Matrix::new_mat(vec![vec![1.], vec![1.]]);
+/// let fit = fitting::ridge(&x, &[2., 2.], 2.).unwrap();
+/// // (XᵀX + λ)⁻¹ Xᵀy = 4 / 4
+/// assert!((fit.coefficients[0] - 1.).abs() < 1e-12);
+/// ```
+pub fn ridge(design: &Matrix, y: &[f64], lambda: f64) -> Result<Fit, MatrixError> {
+    if lambda.is_nan() || lambda < 0. {
+        return Err(MatrixError::new(format!(
+            "The regularization strength {} is negative",
+            lambda
+        )));
+    }
+    fit(design, y, None, lambda)
+}
+
+/// Fits a polynomial of the given degree through points by least squares.
+/// The coefficients start with the constant term.
+///
+/// # Examples
+/// ```
+/// use ralgeb::fitting;
+/// use ralgeb::point::Point;
+/// let pts: Vec<Point> = (0..5).map(|i| Point::new(i as f64, 2. * i as f64 + 1.)).collect();
+/// let fit = fitting::polynomial_fit(&pts, 1).unwrap();
+/// assert!((fit.coefficients[0] - 1.).abs() < 1e-12);
+/// assert!((fit.coefficients[1] - 2.).abs() < 1e-12);
+/// ```
+pub fn polynomial_fit(points: &[Point], degree: usize) -> Result<Fit, MatrixError> {
+    let x: Vec<f64> = points.iter().map(|p| p.x).collect();
+    let y: Vec<f64> = points.iter().map(|p| p.y).collect();
+    least_squares(&polynomial_design(&x, degree), &y)
+}
+
+fn fit(
+    design: &Matrix,
+    y: &[f64],
+    weights: Option<&[f64]>,
+    lambda: f64,
+) -> Result<Fit, MatrixError> {
+    let (n, p) = (design.rows, design.cols);
+    if y.len() != n {
+        return Err(MatrixError::new(format!(
+            "Expected {} observations but got {}",
+            n,
+            y.len()
+        )));
+    }
+    let w: Vec<f64> = match weights {
+        Some(w) if w.len() != n => {
+            return Err(MatrixError::new(format!(
+                "Expected {} weights but got {}",
+                n,
+                w.len()
+            )))
+        }
+        Some(w) if w.iter().any(|v| v.is_nan() || *v < 0.) => {
+            return Err(MatrixError::new(
+                "The weights should be non negative".to_string(),
+            ))
+        }
+        Some(w) => w.to_vec(),
+        None => vec![1.; n],
+    };
+    // Observations with zero weight carry no information about the
+    // residual variance, so they do not count towards n
+    let observations = w.iter().filter(|v| **v > 0.).count();
+    if observations <= p {
+        return Err(MatrixError::new(format!(
+            "Expected more than {} observations with a positive weight but got {}",
+            p, observations
+        )));
+    }
+
+    // Scale the rows by √w and append √λ I to reduce every variant
+    // to an ordinary least squares problem
+    let mut a: Vec<Vec<f64>> = (0..n)
+        .map(|r| (0..p).map(|c| w[r].sqrt() * design[(r, c)]).collect())
+        .collect();
+    let mut b: Vec<f64> = (0..n).map(|r| w[r].sqrt() * y[r]).collect();
+    if lambda > 0. {
+        for i in 0..p {
+            let mut row = vec![0.; p];
+            row[i] = lambda.sqrt();
+            a.push(row);
+            b.push(0.);
+        }
+    }
+    let (coefficients, r) = householder_solve(a, b, p)?;
+
+    let residuals: Vec<f64> = (0..n)
+        .map(|r| {
+            y[r] - (0..p)
+                .map(|c| design[(r, c)] * coefficients[c])
+                .sum::<f64>()
+        })
+        .collect();
+    let total_weight: f64 = w.iter().sum();
+    let mean = w.iter().zip(y).map(|(w, y)| w * y).sum::<f64>() / total_weight;
+    let rss: f64 = w.iter().zip(&residuals).map(|(w, r)| w * r * r).sum();
+    let tss: f64 = w.iter().zip(y).map(|(w, y)| w * (y - mean).powi(2)).sum();
+    let r_squared = if tss > 0. { 1. - rss / tss } else { f64::NAN };
+
+    // Cov(β) = σ² A⁻¹ XᵀWX A⁻¹ with A = XᵀWX + λI = RᵀR, which is
+    // σ² (S - λS²) with S = R⁻¹R⁻ᵀ. Using R avoids squaring the
+    // condition number by forming XᵀWX. Rounding can push the
+    // difference slightly below zero, so it is clamped.
+    let sigma2 = rss / (observations - p) as f64;
+    let r_inv = upper_triangular_inverse(&r);
+    let s: Vec<Vec<f64>> = (0..p)
+        .map(|i| {
+            (0..p)
+                .map(|j| (i.max(j)..p).map(|k| r_inv[i][k] * r_inv[j][k]).sum())
+                .collect()
+        })
+        .collect();
+    let standard_errors = (0..p)
+        .map(|i| {
+            let s2: f64 = s[i].iter().map(|v| v * v).sum();
+            (sigma2 * (s[i][i] - lambda * s2)).max(0.).sqrt()
+        })
+        .collect();
+
+    Ok(Fit {
+        coefficients,
+        residuals,
+        r_squared,
+        standard_errors,
+    })
+}
+
+/// Least squares solution of an m x p system by Householder reflections.
+/// Also returns the p x p upper triangular factor R of the QR decomposition.
+fn householder_solve(
+    mut a: Vec<Vec<f64>>,
+    mut b: Vec<f64>,
+    p: usize,
+) -> Result<(Vec<f64>, Vec<Vec<f64>>), MatrixError> {
+    let m = a.len();
+    let rank_error =
+        || MatrixError::new("The design matrix does not have full column rank".to_string());
+    if m < p {
+        return Err(rank_error());
+    }
+    let largest = (0..p)
+        .map(|c| a.iter().map(|row| row[c] * row[c]).sum::<f64>().sqrt())
+        .fold(0., f64::max);
+    let tolerance = f64::EPSILON * m as f64 * largest;
+    for k in 0..p {
+        let norm = a[k..].iter().map(|row| row[k] * row[k]).sum::<f64>().sqrt();
+        if norm <= tolerance {
+            return Err(rank_error());
+        }
+        let alpha = if a[k][k] > 0. { -norm } else { norm };
+        let mut v: Vec<f64> = a[k..].iter().map(|row| row[k]).collect();
+        v[0] -= alpha;
+        let vv: f64 = v.iter().map(|x| x * x).sum();
+        for j in k..p {
+            let f = 2.
+                * v.iter()
+                    .zip(&a[k..])
+                    .map(|(v, row)| v * row[j])
+                    .sum::<f64>()
+                / vv;
+            for (row, v) in a[k..].iter_mut().zip(&v) {
+                row[j] -= f * v;
+            }
+        }
+        let f = 2. * v.iter().zip(&b[k..]).map(|(v, b)| v * b).sum::<f64>() / vv;
+        for (b, v) in b[k..].iter_mut().zip(&v) {
+            *b -= f * v;
+        }
+    }
+    let mut x = vec![0.; p];
+    for k in (0..p).rev() {
+        let s: f64 = (k + 1..p).map(|j| a[k][j] * x[j]).sum();
+        x[k] = (b[k] - s) / a[k][k];
+    }
+    a.truncate(p);
+    Ok((x, a))
+}
+
+/// Inverts a non-singular upper triangular matrix by back substitution,
+/// from the last row up
+fn upper_triangular_inverse(r: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let p = r.len();
+    let mut inv: Vec<Vec<f64>> = vec![Vec::new(); p];
+    for (k, row) in r.iter().enumerate().rev() {
+        inv[k] = (0..p)
+            .map(|c| {
+                let e = if k == c { 1. } else { 0. };
+                let s: f64 = (k + 1..p).map(|j| row[j] * inv[j][c]).sum();
+                (e - s) / row[k]
+            })
+            .collect();
+    }
+    inv
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fitting;
+    use crate::matrix;
+    use crate::point::Point;
+
+    #[test]
+    fn ordinary() {
+        // y = 1 + 2a - b exactly
+        let features = matrix::Matrix::new_mat(vec![
+            vec![0., 1.],
+            vec![1., 0.],
+            vec![2., 3.],
+            vec![3., 1.],
+            vec![4., 4.],
+        ]);
+        let y: Vec<f64> = (0..5)
+            .map(|r| 1. + 2. * features[(r, 0)] - features[(r, 1)])
+            .collect();
+        let x = fitting::with_intercept(&features);
+        let fit = fitting::least_squares(&x, &y).unwrap();
+        for (c, e) in fit.coefficients.iter().zip(&[1., 2., -1.]) {
+            assert!((c - e).abs() < 1e-12);
+        }
+        assert!(fit.residuals.iter().all(|r| r.abs() < 1e-12));
+        assert!((fit.r_squared - 1.).abs() < 1e-12);
+        assert!(fit.standard_errors.iter().all(|s| *s < 1e-6));
+
+        assert!(fitting::least_squares(&x, &y[..4]).is_err());
+        let dependent = fitting::with_intercept(&matrix::Matrix::new_mat(vec![vec![1.], vec![1.]]));
+        assert!(fitting::least_squares(&dependent, &[1., 2.]).is_err());
+    }
+
+    #[test]
+    fn standard_errors() {
+        // Simple regression, compared with the closed form
+        let xs = [1., 2., 3., 4., 5., 6.];
+        let ys = [2.1, 3.9, 6.2, 7.8, 10.1, 12.2];
+        let pts: Vec<Point> = xs
+            .iter()
+            .zip(&ys)
+            .map(|(x, y)| Point::new(*x, *y))
+            .collect();
+        let fit = fitting::polynomial_fit(&pts, 1).unwrap();
+        let n = 6.;
+        let mx = xs.iter().sum::<f64>() / n;
+        let sxx: f64 = xs.iter().map(|x| (x - mx).powi(2)).sum();
+        let rss: f64 = fit.residuals.iter().map(|r| r * r).sum();
+        let s2 = rss / (n - 2.);
+        assert!((fit.standard_errors[1] - (s2 / sxx).sqrt()).abs() < 1e-12);
+        assert!((fit.standard_errors[0] - (s2 * (1. / n + mx * mx / sxx)).sqrt()).abs() < 1e-12);
+        let residual_sum: f64 = fit.residuals.iter().sum();
+        assert!(residual_sum.abs() < 1e-12);
+
+        // No residual degrees of freedom
+        assert!(fitting::polynomial_fit(&pts[..2], 1).is_err());
+    }
+
+    #[test]
+    fn ill_conditioned_standard_errors() {
+        // Forming XᵀX for a quartic at x ≈ 1000 loses every digit.
+        // The leading coefficient, and so its standard error,
+        // does not change when x is shifted to x - 1000.
+        let noise = |i: usize| 0.01 * ((i * 7919 % 13) as f64 - 6.);
+        let points = |shift: f64| -> Vec<Point> {
+            (0..30)
+                .map(|i| {
+                    let x = 1000. + i as f64;
+                    let y = 2. + 1e-3 * x - 1e-9 * x.powi(4) + noise(i);
+                    Point::new(x - shift, y)
+                })
+                .collect()
+        };
+        let raw = fitting::polynomial_fit(&points(0.), 4).unwrap();
+        let shifted = fitting::polynomial_fit(&points(1000.), 4).unwrap();
+        assert!(raw.standard_errors.iter().all(|s| s.is_finite() && *s > 0.));
+        let (a, b) = (raw.standard_errors[4], shifted.standard_errors[4]);
+        assert!((a - b).abs() < 1e-6 * b);
+    }
+
+    #[test]
+    fn polynomial_calibration() {
+        // An ill conditioned cubic that the normal equations would struggle with
+        let pts: Vec<Point> = (0..20)
+            .map(|i| {
+                let x = 1000. + i as f64;
+                Point::new(x, 0.5 - 0.25 * x + 1e-3 * x * x)
+            })
+            .collect();
+        let fit = fitting::polynomial_fit(&pts, 2).unwrap();
+        let curve = fit.polynomial();
+        for p in pts.iter() {
+            assert!((curve.evaluate(p.x) - p.y).abs() < 1e-6);
+        }
+        assert!(fitting::polynomial_fit(&pts[..2], 2).is_err());
+    }
+
+    #[test]
+    fn weighted_and_ridge() {
+        let x = fitting::polynomial_design(&[0., 1., 2., 3.], 1);
+        let y = [0., 1., 2., 10.];
+        let unweighted = fitting::least_squares(&x, &y).unwrap();
+        let weighted = fitting::weighted_least_squares(&x, &y, &[1., 1., 1., 0.]).unwrap();
+        assert!((weighted.coefficients[1] - 1.).abs() < 1e-12);
+        // The zero weight observation does not add a degree of freedom
+        assert!(weighted.standard_errors.iter().all(|s| *s < 1e-12));
+        assert!(fitting::weighted_least_squares(&x, &y, &[1., 1., 0., 0.]).is_err());
+        assert!(unweighted.coefficients[1] > 2.);
+        let unit = fitting::weighted_least_squares(&x, &y, &[1.; 4]).unwrap();
+        assert!((unit.coefficients[1] - unweighted.coefficients[1]).abs() < 1e-12);
+        assert!(fitting::weighted_least_squares(&x, &y, &[1., -1., 1., 1.]).is_err());
+        assert!(fitting::weighted_least_squares(&x, &y, &[1.]).is_err());
+
+        // Ridge matches the normal equations (XᵀX + λI)β = Xᵀy
+        let lambda = 0.7;
+        let fit = fitting::ridge(&x, &y, lambda).unwrap();
+        let xt = matrix::Matrix::transpose(x.clone());
+        let mut a = matrix::Matrix::multiply(&xt, &x).unwrap();
+        for i in 0..2 {
+            a[(i, i)] += lambda;
+        }
+        let rhs =
+            matrix::Matrix::multiply(&xt, &matrix::Matrix::from_fn(4, 1, |r, _| y[r])).unwrap();
+        let beta = a.solve(&rhs).unwrap().get_col(0);
+        for (c, e) in fit.coefficients.iter().zip(&beta) {
+            assert!((c - e).abs() < 1e-12);
+        }
+        // and its standard errors the sandwich σ² A⁻¹ XᵀX A⁻¹
+        let inv = a.inverse().unwrap();
+        let xtx = matrix::Matrix::multiply(&xt, &x).unwrap();
+        let cov =
+            matrix::Matrix::multiply(&matrix::Matrix::multiply(&inv, &xtx).unwrap(), &inv).unwrap();
+        let sigma2 = fit.residuals.iter().map(|r| r * r).sum::<f64>() / 2.;
+        for (i, s) in fit.standard_errors.iter().enumerate() {
+            assert!((s - (sigma2 * cov[(i, i)]).sqrt()).abs() < 1e-12);
+        }
+        let zero = fitting::ridge(&x, &y, 0.).unwrap();
+        assert!((zero.coefficients[0] - unweighted.coefficients[0]).abs() < 1e-12);
+        assert!(fitting::ridge(&x, &y, -1.).is_err());
+    }
+}
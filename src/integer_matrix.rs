@@ -0,0 +1,551 @@
+use crate::matrix::{Matrix, MatrixError};
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::Index;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Represents a rows x cols matrix of integers.
+/// All the operations are exact and report an error instead of
+/// overflowing, intermediate values are kept in i128.
+pub struct IntegerMatrix {
+    pub rows: usize,
+    pub cols: usize,
+    mat: Vec<Vec<i64>>,
+}
+
+type Work = Vec<Vec<i128>>;
+
+impl Index<(usize, usize)> for IntegerMatrix {
+    type Output = i64;
+
+    /// Returns the element at (row, col)
+    fn index(&self, (row, col): (usize, usize)) -> &i64 {
+        &self.mat[row][col]
+    }
+}
+
+impl fmt::Display for IntegerMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.mat.iter() {
+            let row: Vec<String> = row.iter().map(|v| v.to_string()).collect();
+            writeln!(f, "[{}]", row.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+fn overflow() -> MatrixError {
+    MatrixError::new("Integer overflow".to_string())
+}
+
+impl IntegerMatrix {
+    /// Returns an integer matrix with all 0 values
+    ///
+    /// # Arguments
+    /// `rows` - The number of rows in a matrix.
+    /// `cols` - The number of columns in a matrix.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::integer_matrix::IntegerMatrix;
+    /// let m = IntegerMatrix::new(2, 3);
+    /// assert_eq!(m[(1, 2)], 0);
+    /// ```
+    pub fn new(rows: usize, cols: usize) -> IntegerMatrix {
+        IntegerMatrix {
+            rows,
+            cols,
+            mat: vec![vec![0; cols]; rows],
+        }
+    }
+
+    /// Returns the n x n identity matrix
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::integer_matrix::IntegerMatrix;
+    /// assert_eq!(IntegerMatrix::identity(2)[(1, 1)], 1);
+    /// ```
+    pub fn identity(n: usize) -> IntegerMatrix {
+        let mut m = IntegerMatrix::new(n, n);
+        for i in 0..n {
+            m.mat[i][i] = 1;
+        }
+        m
+    }
+
+    /// Creates an integer matrix from a list of rows
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::integer_matrix::IntegerMatrix;
+    /// let m = IntegerMatrix::from_rows(vec![vec![1, 2], vec![3, 4]]).unwrap();
+    /// assert_eq!(m[(1, 0)], 3);
+    /// ```
+    pub fn from_rows(rows: Vec<Vec<i64>>) -> Result<IntegerMatrix, MatrixError> {
+        let cols = rows.first().map_or(0, |r| r.len());
+        if rows.iter().any(|r| r.len() != cols) {
+            return Err(MatrixError::new(
+                "All the rows should have the same length".to_string(),
+            ));
+        }
+        Ok(IntegerMatrix {
+            rows: rows.len(),
+            cols,
+            mat: rows,
+        })
+    }
+
+    /// Sets the element at (row, col)
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::integer_matrix::IntegerMatrix;
+    /// let mut m = IntegerMatrix::new(1, 1);
+    /// m.set(0, 0, -4);
+    /// assert_eq!(m[(0, 0)], -4);
+    /// ```
+    pub fn set(&mut self, row: usize, col: usize, value: i64) {
+        self.mat[row][col] = value;
+    }
+
+    /// Will return a row from the matrix
+    /// # Arguments
+    /// `row_num` - The row number to return. Indexing starts from 0.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::integer_matrix::IntegerMatrix;
+    /// assert_eq!(IntegerMatrix::identity(2).get_row(0).unwrap(), vec![1, 0]);
+    /// ```
+    pub fn get_row(&self, row_num: usize) -> Result<Vec<i64>, MatrixError> {
+        if row_num >= self.rows {
+            Err(MatrixError::new(format!(
+                "The matrix does not contain row: {}",
+                row_num
+            )))
+        } else {
+            Ok(self.mat[row_num].to_owned())
+        }
+    }
+
+    /// Converts the matrix into a floating point `Matrix`
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::integer_matrix::IntegerMatrix;
+    /// let m = IntegerMatrix::identity(2).to_matrix();
+    /// assert_eq!(m.trace().unwrap(), 2.0);
+    /// ```
+    pub fn to_matrix(&self) -> Matrix {
+        Matrix::from_fn(self.rows, self.cols, |r, c| self.mat[r][c] as f64)
+    }
+
+    /// Returns the transposed matrix
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::integer_matrix::IntegerMatrix;
+    /// let m = IntegerMatrix::from_rows(vec![vec![1, 2, 3]]).unwrap();
+    /// assert_eq!(m.transpose().rows, 3);
+    /// ```
+    pub fn transpose(&self) -> IntegerMatrix {
+        let mut res = IntegerMatrix::new(self.cols, self.rows);
+        for (r, row) in self.mat.iter().enumerate() {
+            for (c, v) in row.iter().enumerate() {
+                res.mat[c][r] = *v;
+            }
+        }
+        res
+    }
+
+    /// Multiplies 2 integer matrices, failing on overflow
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::integer_matrix::IntegerMatrix;
+    /// let m = IntegerMatrix::from_rows(vec![vec![1, 1], vec![0, 1]]).unwrap();
+    /// let p = IntegerMatrix::multiply(&m, &m).unwrap();
+    /// assert_eq!(p.get_row(0).unwrap(), vec![1, 2]);
+    /// let big = IntegerMatrix::from_rows(vec![vec![i64::MAX]]).unwrap();
+    /// assert!(IntegerMatrix::multiply(&big, &big).is_err());
+    /// ```
+    pub fn multiply(m1: &IntegerMatrix, m2: &IntegerMatrix) -> Result<IntegerMatrix, MatrixError> {
+        if m1.cols != m2.rows {
+            return Err(MatrixError::new(format!(
+                "Cannot multiply a {}x{} matrix with a {}x{} matrix",
+                m1.rows, m1.cols, m2.rows, m2.cols
+            )));
+        }
+        let mut res = vec![vec![0i128; m2.cols]; m1.rows];
+        for (i, row) in res.iter_mut().enumerate() {
+            for (j, v) in row.iter_mut().enumerate() {
+                for k in 0..m1.cols {
+                    let p = (m1.mat[i][k] as i128) * (m2.mat[k][j] as i128);
+                    *v = v.checked_add(p).ok_or_else(overflow)?;
+                }
+            }
+        }
+        IntegerMatrix::from_work(res, m2.cols)
+    }
+
+    /// Returns the exact determinant of a square matrix.
+    /// Uses Bareiss elimination, every intermediate value is a minor
+    /// of the matrix, so an error is returned only if one of them
+    /// or the result does not fit.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::integer_matrix::IntegerMatrix;
+    /// let m = IntegerMatrix::from_rows(vec![vec![2, 0, 1], vec![1, 3, 2], vec![1, 1, 2]]).unwrap();
+    /// assert_eq!(m.determinant().unwrap(), 6);
+    /// ```
+    pub fn determinant(&self) -> Result<i64, MatrixError> {
+        if self.rows != self.cols {
+            return Err(MatrixError::new(
+                "The matrix is not a square matrix".to_string(),
+            ));
+        }
+        let n = self.rows;
+        let mut a = self.to_work();
+        let mut sign = 1;
+        let mut prev: i128 = 1;
+        for k in 0..n {
+            if a[k][k] == 0 {
+                match (k + 1..n).find(|&i| a[i][k] != 0) {
+                    Some(i) => {
+                        a.swap(k, i);
+                        sign = -sign;
+                    }
+                    None => return Ok(0),
+                }
+            }
+            for i in k + 1..n {
+                for j in k + 1..n {
+                    let lhs = a[i][j].checked_mul(a[k][k]).ok_or_else(overflow)?;
+                    let rhs = a[i][k].checked_mul(a[k][j]).ok_or_else(overflow)?;
+                    a[i][j] = lhs.checked_sub(rhs).ok_or_else(overflow)? / prev;
+                }
+            }
+            prev = a[k][k];
+        }
+        let det = if n == 0 { 1 } else { sign * a[n - 1][n - 1] };
+        i64::try_from(det).map_err(|_| overflow())
+    }
+
+    /// Returns the row style Hermite normal form H together with a
+    /// unimodular matrix U such that U * A = H.
+    /// H is in row echelon form, every pivot is positive and the
+    /// entries above a pivot lie in 0..pivot.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::integer_matrix::IntegerMatrix;
+    /// let a = IntegerMatrix::from_rows(vec![vec![2, 4], vec![3, 5]]).unwrap();
+    /// let (h, u) = a.hermite_normal_form().unwrap();
+    /// assert_eq!(h.get_row(0).unwrap(), vec![1, 1]);
+    /// assert_eq!(h.get_row(1).unwrap(), vec![0, 2]);
+    /// assert_eq!(IntegerMatrix::multiply(&u, &a).unwrap(), h);
+    /// ```
+    pub fn hermite_normal_form(&self) -> Result<(IntegerMatrix, IntegerMatrix), MatrixError> {
+        let m = self.rows;
+        let mut h = self.to_work();
+        let mut u = IntegerMatrix::identity(m).to_work();
+        let mut r = 0;
+        for c in 0..self.cols {
+            if r == m {
+                break;
+            }
+            loop {
+                let pivot = (r..m)
+                    .filter(|&i| h[i][c] != 0)
+                    .min_by_key(|&i| h[i][c].abs());
+                let pivot = match pivot {
+                    Some(i) => i,
+                    None => break,
+                };
+                h.swap(r, pivot);
+                u.swap(r, pivot);
+                let mut cleared = true;
+                for i in r + 1..m {
+                    let q = h[i][c] / h[r][c];
+                    row_sub(&mut h, i, r, q)?;
+                    row_sub(&mut u, i, r, q)?;
+                    cleared &= h[i][c] == 0;
+                }
+                if cleared {
+                    break;
+                }
+            }
+            if h[r][c] == 0 {
+                continue;
+            }
+            if h[r][c] < 0 {
+                row_sub(&mut h, r, r, 2)?;
+                row_sub(&mut u, r, r, 2)?;
+            }
+            for i in 0..r {
+                let q = h[i][c].div_euclid(h[r][c]);
+                row_sub(&mut h, i, r, q)?;
+                row_sub(&mut u, i, r, q)?;
+            }
+            r += 1;
+        }
+        Ok((
+            IntegerMatrix::from_work(h, self.cols)?,
+            IntegerMatrix::from_work(u, m)?,
+        ))
+    }
+
+    /// Returns the Smith normal form S together with unimodular matrices
+    /// U and V such that U * A * V = S.
+    /// S is diagonal, its entries are non negative and each one
+    /// divides the next.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::integer_matrix::IntegerMatrix;
+    /// let a = IntegerMatrix::from_rows(vec![vec![2, 4, 4], vec![-6, 6, 12], vec![10, -4, -16]]).unwrap();
+    /// let (u, s, v) = a.smith_normal_form().unwrap();
+    /// assert_eq!(s.invariant_factors(), vec![2, 6, 12]);
+    /// let usv = IntegerMatrix::multiply(&IntegerMatrix::multiply(&u, &a).unwrap(), &v).unwrap();
+    /// assert_eq!(usv, s);
+    /// ```
+    pub fn smith_normal_form(
+        &self,
+    ) -> Result<(IntegerMatrix, IntegerMatrix, IntegerMatrix), MatrixError> {
+        let (m, n) = (self.rows, self.cols);
+        let mut s = self.to_work();
+        let mut u = IntegerMatrix::identity(m).to_work();
+        let mut v = IntegerMatrix::identity(n).to_work();
+        for t in 0..m.min(n) {
+            loop {
+                let pivot = (t..m)
+                    .flat_map(|i| (t..n).map(move |j| (i, j)))
+                    .filter(|&(i, j)| s[i][j] != 0)
+                    .min_by_key(|&(i, j)| s[i][j].abs());
+                let (pi, pj) = match pivot {
+                    Some(p) => p,
+                    None => {
+                        return Ok((
+                            IntegerMatrix::from_work(u, m)?,
+                            IntegerMatrix::from_work(s, n)?,
+                            IntegerMatrix::from_work(v, n)?,
+                        ))
+                    }
+                };
+                s.swap(t, pi);
+                u.swap(t, pi);
+                col_swap(&mut s, t, pj);
+                col_swap(&mut v, t, pj);
+
+                let mut cleared = true;
+                for i in t + 1..m {
+                    let q = s[i][t] / s[t][t];
+                    row_sub(&mut s, i, t, q)?;
+                    row_sub(&mut u, i, t, q)?;
+                    cleared &= s[i][t] == 0;
+                }
+                for j in t + 1..n {
+                    let q = s[t][j] / s[t][t];
+                    col_sub(&mut s, j, t, q)?;
+                    col_sub(&mut v, j, t, q)?;
+                    cleared &= s[t][j] == 0;
+                }
+                if !cleared {
+                    continue;
+                }
+                let d = s[t][t];
+                let offender = (t + 1..m).find(|&i| (t + 1..n).any(|j| s[i][j] % d != 0));
+                match offender {
+                    Some(i) => {
+                        row_sub(&mut s, t, i, -1)?;
+                        row_sub(&mut u, t, i, -1)?;
+                    }
+                    None => break,
+                }
+            }
+            if s[t][t] < 0 {
+                row_sub(&mut s, t, t, 2)?;
+                row_sub(&mut u, t, t, 2)?;
+            }
+        }
+        Ok((
+            IntegerMatrix::from_work(u, m)?,
+            IntegerMatrix::from_work(s, n)?,
+            IntegerMatrix::from_work(v, n)?,
+        ))
+    }
+
+    /// Returns the non zero diagonal entries of the matrix.
+    /// On a Smith normal form these are the invariant factors.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::integer_matrix::IntegerMatrix;
+    /// let a = IntegerMatrix::from_rows(vec![vec![2, 0], vec![0, 3]]).unwrap();
+    /// let (_, s, _) = a.smith_normal_form().unwrap();
+    /// assert_eq!(s.invariant_factors(), vec![1, 6]);
+    /// ```
+    pub fn invariant_factors(&self) -> Vec<i64> {
+        (0..self.rows.min(self.cols))
+            .map(|i| self.mat[i][i])
+            .filter(|d| *d != 0)
+            .collect()
+    }
+
+    fn to_work(&self) -> Work {
+        self.mat
+            .iter()
+            .map(|r| r.iter().map(|v| *v as i128).collect())
+            .collect()
+    }
+
+    // The column count is passed in because it cannot be read
+    // from a matrix without rows
+    fn from_work(work: Work, cols: usize) -> Result<IntegerMatrix, MatrixError> {
+        let rows = work.len();
+        let mut mat = Vec::with_capacity(rows);
+        for row in work {
+            let row: Result<Vec<i64>, _> = row.into_iter().map(i64::try_from).collect();
+            mat.push(row.map_err(|_| overflow())?);
+        }
+        Ok(IntegerMatrix { rows, cols, mat })
+    }
+}
+
+/// row[target] -= q * row[src]
+fn row_sub(m: &mut Work, target: usize, src: usize, q: i128) -> Result<(), MatrixError> {
+    if q == 0 {
+        return Ok(());
+    }
+    let src_row = m[src].clone();
+    for (v, s) in m[target].iter_mut().zip(src_row.iter()) {
+        let p = s.checked_mul(q).ok_or_else(overflow)?;
+        *v = v.checked_sub(p).ok_or_else(overflow)?;
+    }
+    Ok(())
+}
+
+/// col[target] -= q * col[src]
+fn col_sub(m: &mut Work, target: usize, src: usize, q: i128) -> Result<(), MatrixError> {
+    if q == 0 {
+        return Ok(());
+    }
+    for row in m.iter_mut() {
+        let p = row[src].checked_mul(q).ok_or_else(overflow)?;
+        row[target] = row[target].checked_sub(p).ok_or_else(overflow)?;
+    }
+    Ok(())
+}
+
+fn col_swap(m: &mut Work, a: usize, b: usize) {
+    for row in m.iter_mut() {
+        row.swap(a, b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::integer_matrix::IntegerMatrix;
+
+    fn is_unimodular(m: &IntegerMatrix) -> bool {
+        m.determinant().unwrap().abs() == 1
+    }
+
+    #[test]
+    fn construction() {
+        assert!(IntegerMatrix::from_rows(vec![vec![1], vec![1, 2]]).is_err());
+        let m = IntegerMatrix::from_rows(vec![vec![1, -2], vec![3, 4]]).unwrap();
+        assert_eq!(format!("{}", m), "[1 -2]\n[3 4]\n");
+        assert_eq!(m.transpose().get_row(1).unwrap(), vec![-2, 4]);
+        assert!(m.get_row(2).is_err());
+        assert_eq!(m.to_matrix()[(0, 1)], -2.0);
+    }
+
+    #[test]
+    fn determinant() {
+        let m =
+            IntegerMatrix::from_rows(vec![vec![0, 2, 1], vec![3, 1, 0], vec![1, 1, 1]]).unwrap();
+        assert_eq!(m.determinant().unwrap(), -4);
+        let singular = IntegerMatrix::from_rows(vec![vec![1, 2], vec![2, 4]]).unwrap();
+        assert_eq!(singular.determinant().unwrap(), 0);
+        assert_eq!(IntegerMatrix::new(0, 0).determinant().unwrap(), 1);
+        assert!(IntegerMatrix::new(2, 3).determinant().is_err());
+
+        // Values near i64::MAX whose f64 determinant would be wrong
+        let big = 3_037_000_499;
+        let m = IntegerMatrix::from_rows(vec![vec![big, 1], vec![1, big]]).unwrap();
+        assert_eq!(m.determinant().unwrap(), big * big - 1);
+        let m = IntegerMatrix::from_rows(vec![vec![i64::MAX, 0], vec![0, 2]]).unwrap();
+        assert!(m.determinant().is_err());
+    }
+
+    #[test]
+    fn hermite() {
+        let a = IntegerMatrix::from_rows(vec![
+            vec![3, 3, 1, 4],
+            vec![0, 1, 0, 0],
+            vec![0, 0, 19, 16],
+            vec![0, 0, 0, 3],
+        ])
+        .unwrap();
+        let (h, u) = a.hermite_normal_form().unwrap();
+        let expected = IntegerMatrix::from_rows(vec![
+            vec![3, 0, 1, 1],
+            vec![0, 1, 0, 0],
+            vec![0, 0, 19, 1],
+            vec![0, 0, 0, 3],
+        ])
+        .unwrap();
+        assert_eq!(h, expected);
+        assert_eq!(IntegerMatrix::multiply(&u, &a).unwrap(), h);
+        assert!(is_unimodular(&u));
+
+        // Rank deficient and rectangular
+        let a =
+            IntegerMatrix::from_rows(vec![vec![2, 4, 6], vec![-1, -2, -3], vec![0, 0, 5]]).unwrap();
+        let (h, u) = a.hermite_normal_form().unwrap();
+        assert_eq!(h.get_row(0).unwrap(), vec![1, 2, 3]);
+        assert_eq!(h.get_row(1).unwrap(), vec![0, 0, 5]);
+        assert_eq!(h.get_row(2).unwrap(), vec![0, 0, 0]);
+        assert_eq!(IntegerMatrix::multiply(&u, &a).unwrap(), h);
+        assert!(is_unimodular(&u));
+
+        // No rows keeps the columns
+        let empty = IntegerMatrix::new(0, 3);
+        let (h, u) = empty.hermite_normal_form().unwrap();
+        assert_eq!((h.rows, h.cols, u.rows, u.cols), (0, 3, 0, 0));
+        let product = IntegerMatrix::multiply(&empty, &IntegerMatrix::new(3, 2)).unwrap();
+        assert_eq!((product.rows, product.cols), (0, 2));
+    }
+
+    #[test]
+    fn smith() {
+        // Z^3 / rows of a is Z/2 x Z/6 x Z
+        let a = IntegerMatrix::from_rows(vec![vec![2, 4, 4], vec![-6, 6, 12]]).unwrap();
+        let (u, s, v) = a.smith_normal_form().unwrap();
+        assert_eq!(s.invariant_factors(), vec![2, 6]);
+        assert_eq!(s.get_row(0).unwrap(), vec![2, 0, 0]);
+        let usv = IntegerMatrix::multiply(&IntegerMatrix::multiply(&u, &a).unwrap(), &v).unwrap();
+        assert_eq!(usv, s);
+        assert!(is_unimodular(&u));
+        assert!(is_unimodular(&v));
+
+        let zero = IntegerMatrix::new(2, 2);
+        let (u, s, v) = zero.smith_normal_form().unwrap();
+        assert_eq!(s, zero);
+        assert_eq!(u, IntegerMatrix::identity(2));
+        assert_eq!(v, IntegerMatrix::identity(2));
+
+        let a = IntegerMatrix::from_rows(vec![vec![4, 0], vec![0, 6], vec![0, 0]]).unwrap();
+        let (u, s, v) = a.smith_normal_form().unwrap();
+        assert_eq!(s.invariant_factors(), vec![2, 12]);
+        let usv = IntegerMatrix::multiply(&IntegerMatrix::multiply(&u, &a).unwrap(), &v).unwrap();
+        assert_eq!(usv, s);
+
+        let (u, s, v) = IntegerMatrix::new(0, 2).smith_normal_form().unwrap();
+        assert_eq!((u.rows, u.cols, s.rows, s.cols), (0, 0, 0, 2));
+        assert_eq!(v, IntegerMatrix::identity(2));
+    }
+}
@@ -0,0 +1,732 @@
+use crate::approx::{ApproxEq, Tolerance};
+use crate::scalar::{Real, Scalar};
+use std::cmp::Ordering;
+use std::f64::consts::PI;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Interval represents the closed set of reals [lo, hi].
+/// Every operation rounds its bounds outward, so the result of a
+/// computation is guaranteed to contain the exact real result.
+/// A wide result signals that the f64 answer might be wrong.
+///
+/// Construction panics on invalid bounds, like `Rational` on a zero
+/// denominator. Use `checked_new` to detect it instead.
+///
+/// # Examples
+/// [1, 2] contains every x with 1 <= x <= 2
+pub struct Interval {
+    lo: f64,
+    hi: f64,
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {}]", self.lo, self.hi)
+    }
+}
+
+/// Below this size the residual of a product, quotient or square root
+/// may itself be rounded, so results are always widened
+const TINY: f64 = 1e-290;
+
+/// Returns the rounded result r of an operation whose exact value
+/// differs from r by a quantity with the sign of err, rounded down.
+/// A non-finite err means the error is unknown.
+fn round_down(r: f64, err: f64) -> f64 {
+    if err.is_finite() && err >= 0. {
+        r
+    } else {
+        r.next_down()
+    }
+}
+
+/// The upward counterpart of `round_down`
+fn round_up(r: f64, err: f64) -> f64 {
+    if err.is_finite() && err <= 0. {
+        r
+    } else {
+        r.next_up()
+    }
+}
+
+/// Returns a + b and its exact rounding error (TwoSum)
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    let bb = s - a;
+    (s, (a - (s - bb)) + (b - bb))
+}
+
+/// Returns a · b and the sign of its rounding error, taking 0 · ∞ as 0
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    if a == 0. || b == 0. {
+        return (0., 0.);
+    }
+    let p = a * b;
+    if p.abs() < TINY {
+        return (p, f64::NAN);
+    }
+    (p, a.mul_add(b, -p))
+}
+
+/// Returns a / b and the sign of its rounding error
+fn two_quotient(a: f64, b: f64) -> (f64, f64) {
+    let q = a / b;
+    if a == 0. && b != 0. {
+        return (q, 0.);
+    }
+    if q.abs() < TINY || a.abs() < TINY || !q.is_finite() {
+        return (q, f64::NAN);
+    }
+    // a - q·b is exact, and a / b - q has its sign times the sign of b
+    let r = (-q).mul_add(b, a);
+    (q, if b < 0. { -r } else { r })
+}
+
+fn add_down(a: f64, b: f64) -> f64 {
+    let (s, e) = two_sum(a, b);
+    round_down(s, e)
+}
+
+fn add_up(a: f64, b: f64) -> f64 {
+    let (s, e) = two_sum(a, b);
+    round_up(s, e)
+}
+
+fn mul_down(a: f64, b: f64) -> f64 {
+    let (p, e) = two_product(a, b);
+    round_down(p, e)
+}
+
+fn mul_up(a: f64, b: f64) -> f64 {
+    let (p, e) = two_product(a, b);
+    round_up(p, e)
+}
+
+/// Returns a lower bound of x^n for x >= 0
+fn pow_down(x: f64, n: u32) -> f64 {
+    (0..n).fold(1., |acc, _| mul_down(acc, x).max(0.))
+}
+
+/// Returns an upper bound of x^n for x >= 0
+fn pow_up(x: f64, n: u32) -> f64 {
+    (0..n).fold(1., |acc, _| mul_up(acc, x))
+}
+
+impl Interval {
+    /// Returns the interval [lo, hi]
+    ///
+    /// # Arguments
+    /// * `lo` - The lower bound
+    /// * `hi` - The upper bound. It should not be less than lo
+    ///
+    /// ```
+    /// use ralgeb::interval::Interval;
+    /// let x = Interval::new(1., 2.);
+    /// assert_eq!(x.to_string(), "[1, 2]");
+    /// ```
+    pub fn new(lo: f64, hi: f64) -> Interval {
+        match Interval::checked_new(lo, hi) {
+            Some(x) => x,
+            None => panic!("Invalid interval [{}, {}]", lo, hi),
+        }
+    }
+
+    /// Returns the interval [lo, hi] or None if lo > hi, a bound is NaN,
+    /// or the interval holds no real number because lo = ∞ or hi = -∞
+    ///
+    /// ```
+    /// use ralgeb::interval::Interval;
+    /// assert!(Interval::checked_new(2., 1.).is_none());
+    /// assert!(Interval::checked_new(f64::NAN, 1.).is_none());
+    /// assert!(Interval::checked_new(f64::INFINITY, f64::INFINITY).is_none());
+    /// assert!(Interval::checked_new(1., f64::INFINITY).is_some());
+    /// ```
+    pub fn checked_new(lo: f64, hi: f64) -> Option<Interval> {
+        if lo <= hi && lo != f64::INFINITY && hi != f64::NEG_INFINITY {
+            Some(Interval { lo, hi })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the degenerate interval [x, x]
+    ///
+    /// ```
+    /// use ralgeb::interval::Interval;
+    /// assert_eq!(Interval::point(3.).width(), 0.);
+    /// ```
+    pub fn point(x: f64) -> Interval {
+        Interval::new(x, x)
+    }
+
+    /// Returns the interval [-∞, ∞], the result when nothing
+    /// is known about a value
+    ///
+    /// ```
+    /// use ralgeb::interval::Interval;
+    /// assert!(Interval::entire().contains(1e300));
+    /// ```
+    pub fn entire() -> Interval {
+        Interval::new(f64::NEG_INFINITY, f64::INFINITY)
+    }
+
+    /// Returns the lower bound
+    pub fn lo(&self) -> f64 {
+        self.lo
+    }
+
+    /// Returns the upper bound
+    pub fn hi(&self) -> f64 {
+        self.hi
+    }
+
+    /// Returns the midpoint, the best f64 estimate of the value
+    ///
+    /// ```
+    /// use ralgeb::interval::Interval;
+    /// assert_eq!(Interval::new(1., 2.).mid(), 1.5);
+    /// ```
+    pub fn mid(&self) -> f64 {
+        self.lo / 2. + self.hi / 2.
+    }
+
+    /// Returns the width hi - lo, rounded up
+    ///
+    /// ```
+    /// use ralgeb::interval::Interval;
+    /// assert_eq!(Interval::new(1., 2.5).width(), 1.5);
+    /// ```
+    pub fn width(&self) -> f64 {
+        add_up(self.hi, -self.lo)
+    }
+
+    /// Returns true if x lies in the interval
+    ///
+    /// ```
+    /// use ralgeb::interval::Interval;
+    /// assert!(Interval::new(1., 2.).contains(2.));
+    /// assert!(!Interval::new(1., 2.).contains(2.5));
+    /// ```
+    pub fn contains(&self, x: f64) -> bool {
+        self.lo <= x && x <= self.hi
+    }
+
+    /// Returns true if every element of other lies in the interval
+    ///
+    /// ```
+    /// use ralgeb::interval::Interval;
+    /// assert!(Interval::new(0., 3.).encloses(&Interval::new(1., 2.)));
+    /// ```
+    pub fn encloses(&self, other: &Interval) -> bool {
+        self.lo <= other.lo && other.hi <= self.hi
+    }
+
+    /// Returns the smallest interval containing both intervals
+    ///
+    /// ```
+    /// use ralgeb::interval::Interval;
+    /// let h = Interval::new(0., 1.).hull(&Interval::new(3., 4.));
+    /// assert_eq!(h, Interval::new(0., 4.));
+    /// ```
+    pub fn hull(&self, other: &Interval) -> Interval {
+        Interval::new(self.lo.min(other.lo), self.hi.max(other.hi))
+    }
+
+    /// Returns the common part of both intervals or None if they are disjoint
+    ///
+    /// ```
+    /// use ralgeb::interval::Interval;
+    /// let a = Interval::new(0., 2.);
+    /// assert_eq!(a.intersect(&Interval::new(1., 3.)), Some(Interval::new(1., 2.)));
+    /// assert_eq!(a.intersect(&Interval::new(3., 4.)), None);
+    /// ```
+    pub fn intersect(&self, other: &Interval) -> Option<Interval> {
+        Interval::checked_new(self.lo.max(other.lo), self.hi.min(other.hi))
+    }
+
+    /// Returns the range of |x| over the interval
+    ///
+    /// ```
+    /// use ralgeb::interval::Interval;
+    /// assert_eq!(Interval::new(-3., 1.).abs(), Interval::new(0., 3.));
+    /// ```
+    pub fn abs(&self) -> Interval {
+        if self.lo >= 0. {
+            *self
+        } else if self.hi <= 0. {
+            -*self
+        } else {
+            Interval::new(0., self.hi.max(-self.lo))
+        }
+    }
+
+    /// Returns the square root of the non-negative part of the interval.
+    /// Panics if the interval is entirely negative.
+    ///
+    /// ```
+    /// use ralgeb::interval::Interval;
+    /// let r = Interval::point(2.).sqrt();
+    /// assert!(r.contains(std::f64::consts::SQRT_2));
+    /// assert!(r.width() < 1e-15);
+    /// ```
+    pub fn sqrt(&self) -> Interval {
+        if self.hi < 0. {
+            panic!("Square root of the negative interval {}", self);
+        }
+        let root = |x: f64, upward: bool| {
+            let r = x.sqrt();
+            // x - r² is exact and has the sign of √x - r
+            let e = if x < TINY {
+                f64::NAN
+            } else {
+                (-r).mul_add(r, x)
+            };
+            if upward {
+                round_up(r, e)
+            } else {
+                round_down(r, e)
+            }
+        };
+        let lo = if self.lo <= 0. {
+            0.
+        } else {
+            root(self.lo, false).max(0.)
+        };
+        Interval::new(lo, root(self.hi, true))
+    }
+
+    /// Returns the range of x^n over the interval
+    ///
+    /// ```
+    /// use ralgeb::interval::Interval;
+    /// let x = Interval::new(-1., 2.);
+    /// assert!(x.powi(2).encloses(&Interval::new(0., 4.)));
+    /// assert_eq!(x.powi(2).lo(), 0.);
+    /// assert!(x.powi(3).encloses(&Interval::new(-1., 8.)));
+    /// ```
+    pub fn powi(&self, n: i32) -> Interval {
+        if n < 0 {
+            return Interval::one() / self.powi(-n);
+        }
+        let n = n as u32;
+        if n == 0 {
+            return Interval::one();
+        }
+        if n % 2 == 1 {
+            // Odd powers are increasing
+            let bound = |x: f64, upward: bool| {
+                if x >= 0. {
+                    if upward {
+                        pow_up(x, n)
+                    } else {
+                        pow_down(x, n)
+                    }
+                } else if upward {
+                    -pow_down(-x, n)
+                } else {
+                    -pow_up(-x, n)
+                }
+            };
+            Interval::new(bound(self.lo, false), bound(self.hi, true))
+        } else {
+            let a = self.abs();
+            Interval::new(pow_down(a.lo, n), pow_up(a.hi, n))
+        }
+    }
+
+    /// Returns the range of the four quadrant arctangent atan2(y, x)
+    /// over the box self (y) times x, in radians.
+    /// The whole range [-π, π] is returned if the box touches the
+    /// origin or crosses the negative x axis.
+    ///
+    /// ```
+    /// use ralgeb::interval::Interval;
+    /// let theta = Interval::point(1.).atan2(Interval::point(1.));
+    /// assert!(theta.contains(std::f64::consts::FRAC_PI_4));
+    /// ```
+    pub fn atan2(&self, x: Interval) -> Interval {
+        // PI is below π, so the next f64 up bounds π from above
+        let pi = PI.next_up();
+        if x.lo < 0. && self.lo <= 0. && self.hi >= 0. {
+            return Interval::new(-pi, pi);
+        }
+        let corners = [
+            self.lo.atan2(x.lo),
+            self.lo.atan2(x.hi),
+            self.hi.atan2(x.lo),
+            self.hi.atan2(x.hi),
+        ];
+        let lo = corners.iter().cloned().fold(f64::INFINITY, f64::min);
+        let hi = corners.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        // atan2 is not correctly rounded, so allow a second ulp
+        Interval::new(
+            lo.next_down().next_down().max(-pi),
+            hi.next_up().next_up().min(pi),
+        )
+    }
+}
+
+impl From<f64> for Interval {
+    fn from(x: f64) -> Interval {
+        Interval::point(x)
+    }
+}
+
+/// An interval is less than another if all of its elements are.
+/// Equal intervals compare equal and other overlapping intervals
+/// are not comparable.
+impl PartialOrd for Interval {
+    fn partial_cmp(&self, other: &Interval) -> Option<Ordering> {
+        if self == other {
+            Some(Ordering::Equal)
+        } else if self.hi < other.lo {
+            Some(Ordering::Less)
+        } else if self.lo > other.hi {
+            Some(Ordering::Greater)
+        } else {
+            None
+        }
+    }
+}
+
+impl Add for Interval {
+    type Output = Interval;
+    fn add(self, rhs: Interval) -> Interval {
+        Interval::new(add_down(self.lo, rhs.lo), add_up(self.hi, rhs.hi))
+    }
+}
+
+impl Sub for Interval {
+    type Output = Interval;
+    fn sub(self, rhs: Interval) -> Interval {
+        Interval::new(add_down(self.lo, -rhs.hi), add_up(self.hi, -rhs.lo))
+    }
+}
+
+impl Mul for Interval {
+    type Output = Interval;
+    fn mul(self, rhs: Interval) -> Interval {
+        let pairs = [
+            (self.lo, rhs.lo),
+            (self.lo, rhs.hi),
+            (self.hi, rhs.lo),
+            (self.hi, rhs.hi),
+        ];
+        Interval::new(
+            pairs
+                .iter()
+                .map(|&(a, b)| mul_down(a, b))
+                .fold(f64::INFINITY, f64::min),
+            pairs
+                .iter()
+                .map(|&(a, b)| mul_up(a, b))
+                .fold(f64::NEG_INFINITY, f64::max),
+        )
+    }
+}
+
+impl Div for Interval {
+    type Output = Interval;
+    /// Returns the entire real line if the divisor contains 0
+    fn div(self, rhs: Interval) -> Interval {
+        if rhs.contains(0.) {
+            return Interval::entire();
+        }
+        let pairs = [
+            (self.lo, rhs.lo),
+            (self.lo, rhs.hi),
+            (self.hi, rhs.lo),
+            (self.hi, rhs.hi),
+        ];
+        let bound = |&(a, b): &(f64, f64), upward: bool| {
+            let (q, e) = two_quotient(a, b);
+            if upward {
+                round_up(q, e)
+            } else {
+                round_down(q, e)
+            }
+        };
+        Interval::new(
+            pairs
+                .iter()
+                .map(|p| bound(p, false))
+                .fold(f64::INFINITY, f64::min),
+            pairs
+                .iter()
+                .map(|p| bound(p, true))
+                .fold(f64::NEG_INFINITY, f64::max),
+        )
+    }
+}
+
+impl Neg for Interval {
+    type Output = Interval;
+    fn neg(self) -> Interval {
+        Interval::new(-self.hi, -self.lo)
+    }
+}
+
+impl Add<f64> for Interval {
+    type Output = Interval;
+    fn add(self, rhs: f64) -> Interval {
+        self + Interval::point(rhs)
+    }
+}
+
+impl Sub<f64> for Interval {
+    type Output = Interval;
+    fn sub(self, rhs: f64) -> Interval {
+        self - Interval::point(rhs)
+    }
+}
+
+impl Mul<f64> for Interval {
+    type Output = Interval;
+    fn mul(self, rhs: f64) -> Interval {
+        self * Interval::point(rhs)
+    }
+}
+
+impl Div<f64> for Interval {
+    type Output = Interval;
+    fn div(self, rhs: f64) -> Interval {
+        self / Interval::point(rhs)
+    }
+}
+
+impl Scalar for Interval {
+    fn zero() -> Interval {
+        Interval::point(0.)
+    }
+    fn one() -> Interval {
+        Interval::point(1.)
+    }
+    /// Returns the smallest |x| in the interval, so that a pivot
+    /// which might be zero is treated as zero
+    fn magnitude(&self) -> f64 {
+        if self.contains(0.) {
+            0.
+        } else {
+            self.lo.abs().min(self.hi.abs())
+        }
+    }
+}
+
+impl Real for Interval {
+    /// Returns an interval containing every real that rounds to x,
+    /// so that rounded constants such as `PI` are enclosed
+    fn from_f64(x: f64) -> Interval {
+        Interval::new(x.next_down(), x.next_up())
+    }
+    fn sqrt(self) -> Interval {
+        Interval::sqrt(&self)
+    }
+    fn powi(self, n: i32) -> Interval {
+        Interval::powi(&self, n)
+    }
+    fn atan2(self, x: Interval) -> Interval {
+        Interval::atan2(&self, x)
+    }
+}
+
+impl ApproxEq for Interval {
+    /// Compares the lower and the upper bounds separately
+    fn approx_eq(&self, other: &Interval, tolerance: Tolerance) -> bool {
+        self.lo.approx_eq(&other.lo, tolerance) && self.hi.approx_eq(&other.hi, tolerance)
+    }
+
+    fn ulps_eq(&self, other: &Interval, ulps: u64) -> bool {
+        self.lo.ulps_eq(&other.lo, ulps) && self.hi.ulps_eq(&other.hi, ulps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interval::Interval;
+
+    #[test]
+    fn new_interval() {
+        let x = Interval::new(-1., 2.);
+        assert_eq!((x.lo(), x.hi()), (-1., 2.));
+        assert_eq!(Interval::from(3.), Interval::point(3.));
+        assert_eq!(format!("{}", x), "[-1, 2]");
+        assert!(Interval::checked_new(1., 0.).is_none());
+        assert!(Interval::checked_new(0., f64::NAN).is_none());
+        assert!(x.contains(0.) && !x.contains(-1.5));
+        assert_eq!(x.mid(), 0.5);
+        assert_eq!(x.width(), 3.);
+        assert!(Interval::new(0., 1.) < Interval::new(2., 3.));
+        assert!(Interval::new(2., 3.) > Interval::new(0., 1.));
+        assert!(Interval::new(0., 2.)
+            .partial_cmp(&Interval::new(1., 3.))
+            .is_none());
+        assert!(Interval::point(1.) <= Interval::point(1.));
+        let y = Interval::new(-1., 2.);
+        assert_eq!(x.partial_cmp(&y), Some(std::cmp::Ordering::Equal));
+        assert!(x <= y);
+
+        // Neither contains a real number
+        assert!(Interval::checked_new(f64::INFINITY, f64::INFINITY).is_none());
+        assert!(Interval::checked_new(f64::NEG_INFINITY, f64::NEG_INFINITY).is_none());
+        let big = Interval::new(f64::MAX, f64::INFINITY);
+        assert!((big - big).contains(0.));
+    }
+
+    #[test]
+    #[should_panic]
+    fn infinite_point() {
+        Interval::point(f64::INFINITY);
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_interval() {
+        Interval::new(2., 1.);
+    }
+
+    #[test]
+    fn arithmetic_encloses() {
+        let a = Interval::new(1., 2.);
+        let b = Interval::new(-3., 0.5);
+        assert!((a + b).encloses(&Interval::new(-2., 2.5)));
+        assert!((a - b).encloses(&Interval::new(0.5, 5.)));
+        assert!((a * b).encloses(&Interval::new(-6., 1.)));
+        assert!((a / Interval::new(2., 4.)).encloses(&Interval::new(0.25, 1.)));
+        assert_eq!(a / b, Interval::entire());
+        assert_eq!(-b, Interval::new(-0.5, 3.));
+        assert!((a * 2.).encloses(&Interval::new(2., 4.)));
+        assert!((a + 1.).encloses(&Interval::new(2., 3.)));
+        assert!((a - 1.).encloses(&Interval::new(0., 1.)));
+        assert!((a / 2.).encloses(&Interval::new(0.5, 1.)));
+        assert_eq!(
+            Interval::point(0.) * Interval::entire(),
+            Interval::new(-0., 0.)
+        );
+
+        // 0.1 + 0.2 is not 0.3 in f64, but the enclosure contains both
+        let sum = Interval::point(0.1) + Interval::point(0.2);
+        assert!(sum.contains(0.1 + 0.2) && sum.contains(0.3));
+        assert!(sum.width() < 1e-16);
+    }
+
+    #[test]
+    fn catastrophic_cancellation_is_visible() {
+        // (1e16 + 1) - 1e16 is 0 in f64, the enclosure reveals the doubt
+        let big = Interval::point(1e16);
+        let r = (big + Interval::point(1.)) - big;
+        assert!(r.contains(1.) && r.contains(0.));
+        assert!(r.width() >= 1.);
+    }
+
+    #[test]
+    fn functions() {
+        let x = Interval::new(-2., 3.);
+        assert_eq!(x.abs(), Interval::new(0., 3.));
+        assert_eq!(Interval::new(-3., -2.).abs(), Interval::new(2., 3.));
+        assert_eq!(Interval::new(-1., 4.).sqrt().lo(), 0.);
+        assert!(Interval::new(-1., 4.)
+            .sqrt()
+            .encloses(&Interval::new(0., 2.)));
+        assert!(x.powi(2).encloses(&Interval::new(0., 9.)));
+        assert_eq!(x.powi(2).lo(), 0.);
+        assert!(x.powi(3).encloses(&Interval::new(-8., 27.)));
+        assert!(Interval::new(-3., -2.)
+            .powi(3)
+            .encloses(&Interval::new(-27., -8.)));
+        assert!(Interval::new(2., 4.)
+            .powi(-1)
+            .encloses(&Interval::new(0.25, 0.5)));
+        assert_eq!(x.powi(0), Interval::point(1.));
+        for k in 1..40 {
+            let v = 1.1_f64;
+            assert!(Interval::point(v).powi(k).contains(v.powi(k)));
+        }
+
+        let pi = std::f64::consts::PI;
+        let full = Interval::new(-1., 1.).atan2(Interval::new(-2., -1.));
+        assert!(full.contains(pi) && full.contains(-pi));
+        let q1 = Interval::new(1., 2.).atan2(Interval::new(1., 2.));
+        assert!(q1.contains(0.5_f64.atan()) && q1.contains(2_f64.atan()));
+        assert!(q1.lo() > 0.4 && q1.hi() < 1.2);
+        let left = Interval::point(1.).atan2(Interval::point(-1.));
+        assert!(left.contains(3. * pi / 4.) && left.hi() <= pi.next_up());
+    }
+
+    #[test]
+    #[should_panic]
+    fn sqrt_of_negative() {
+        Interval::new(-2., -1.).sqrt();
+    }
+
+    #[test]
+    fn matrix() {
+        use crate::matrix::Matrix;
+        let a = Matrix::new_mat(vec![vec![4., 1., 0.], vec![1., 3., 1.], vec![0., 1., 2.]]);
+        let x = Matrix::new_mat(vec![vec![1.], vec![-2.], vec![3.]]);
+        let b = Matrix::multiply(&a, &x).unwrap();
+        let ia = Matrix::from_fn(3, 3, |r, c| Interval::point(a[(r, c)]));
+        let ib = Matrix::from_fn(3, 1, |r, c| Interval::point(b[(r, c)]));
+        let solution = ia.solve(&ib).unwrap();
+        for r in 0..3 {
+            assert!(solution[(r, 0)].contains(x[(r, 0)]));
+            assert!(solution[(r, 0)].width() < 1e-12);
+        }
+        let det = ia.determinant().unwrap();
+        assert!(det.contains(a.determinant().unwrap()));
+        let product = Matrix::multiply(&ia, &ia.inverse().unwrap()).unwrap();
+        for r in 0..3 {
+            for c in 0..3 {
+                assert!(product[(r, c)].contains(if r == c { 1. } else { 0. }));
+            }
+        }
+
+        // A pivot that might be zero is rejected
+        let unsure = Matrix::new_mat(vec![
+            vec![Interval::new(-1e-3, 1e-3), Interval::point(1.)],
+            vec![Interval::point(1.), Interval::new(-1e-3, 1e-3)],
+        ]);
+        assert!(unsure
+            .solve(&Matrix::from_fn(2, 1, |_, _| Interval::point(1.)))
+            .is_ok());
+        let singular = Matrix::new_mat(vec![
+            vec![Interval::new(0.9, 1.1), Interval::point(1.)],
+            vec![Interval::point(1.), Interval::new(0.9, 1.1)],
+        ]);
+        assert!(singular
+            .solve(&Matrix::from_fn(2, 1, |_, _| Interval::point(1.)))
+            .is_err());
+    }
+
+    #[test]
+    fn geometry() {
+        use crate::circle::Circle;
+        use crate::line::Line;
+        use crate::point::Point;
+        use crate::scalar::Scalar;
+        use std::f64::consts::PI;
+        let p = |x: f64, y: f64| Point::new(Interval::point(x), Interval::point(y));
+        let line = Line::new(p(1., 2.), p(3., -4.));
+        let length = line.length();
+        assert!(length.contains(40_f64.sqrt()) && length.width() < 1e-14);
+        assert!(line.slope().contains(-3.));
+        assert!(line.theta().contains((-6_f64).atan2(2.)));
+
+        let circle = Circle::new(Interval::point(0.1), p(0., 0.));
+        // The enclosures hold the true π r², not just the f64 result
+        assert!(circle.area().contains(PI * 0.01) && circle.area().width() < 1e-16);
+        assert!(circle.circumference().contains(2. * 0.1 * PI));
+        let pi = Circle::new(Interval::one(), p(0., 0.)).area();
+        assert!(pi.lo() <= PI && PI < pi.hi());
+    }
+
+    #[test]
+    fn approx_eq() {
+        use crate::approx::{ApproxEq, Tolerance};
+        let a = Interval::new(0.1 + 0.2, 1.);
+        assert!(a.approx_eq(&Interval::new(0.3, 1.), Tolerance::default()));
+        assert!(!a.approx_eq(&Interval::new(0.3, 1.1), Tolerance::default()));
+        assert!(a.ulps_eq(&Interval::new(0.3, 1.), 1));
+    }
+}
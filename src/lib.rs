@@ -1,8 +1,23 @@
+pub mod approx;
+pub mod biguint;
 pub mod circle;
 pub mod combinatorics;
+pub mod complex;
+pub mod dual;
+pub mod expr;
+pub mod finite_field;
+pub mod fitting;
+pub mod integer_matrix;
+pub mod interval;
 pub mod line;
 pub mod matrix;
 pub mod point;
+pub mod polynomial;
+pub mod random;
+pub mod rational;
+pub mod scalar;
+pub mod stats;
+pub mod structured;
 pub mod utils;
 
 #[cfg(test)]
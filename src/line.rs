@@ -1,4 +1,6 @@
+use crate::approx::{ApproxEq, Tolerance};
 use crate::point::Point;
+use crate::scalar::Real;
 use crate::utils::delta_coord;
 use std::fmt;
 
@@ -6,18 +8,18 @@ use std::fmt;
 // Line is representation of a line in 2D coordinate system with
 // each point having x and y coordinates
 
-pub struct Line {
-    pub point1: Point,
-    pub point2: Point,
+pub struct Line<T = f64> {
+    pub point1: Point<T>,
+    pub point2: Point<T>,
 }
 
-impl fmt::Display for Line {
+impl<T: fmt::Display> fmt::Display for Line<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "[({}) -> ({})]", self.point1, self.point2)
     }
 }
 
-impl Line {
+impl<T: Real> Line<T> {
     /// Returns a new line with point p1 and point p2 as endpoints
     ///
     /// # Examples
@@ -26,7 +28,7 @@ impl Line {
     /// use ralgeb::line::Line;
     /// let l = Line::new(Point{x: 1., y: 2.}, Point{x: 3., y: -4.});
     /// ```
-    pub fn new(p1: Point, p2: Point) -> Line {
+    pub fn new(p1: Point<T>, p2: Point<T>) -> Line<T> {
         Line {
             point1: p1,
             point2: p2,
@@ -42,11 +44,11 @@ impl Line {
     /// let len = l.length();
     /// assert_eq!(len, f64::sqrt(40.));
     /// ```
-    pub fn length(&self) -> f64 {
+    pub fn length(&self) -> T {
         let del_y = self.point2.y - self.point1.y;
         let del_x = self.point2.x - self.point1.x;
 
-        f64::sqrt((del_x.powi(2) + del_y.powi(2)) as f64)
+        (del_x.powi(2) + del_y.powi(2)).sqrt()
     }
 
     /// Returns the slope of the line
@@ -59,10 +61,10 @@ impl Line {
     /// let l = Line::new(Point{x:1., y: 2.}, Point{x:3., y: -4.});
     /// assert_eq!(l.slope(), -3.);
     /// ```
-    pub fn slope(&self) -> f64 {
+    pub fn slope(&self) -> T {
         let del_y = delta_coord(self.point2.y, self.point1.y);
         let del_x = delta_coord(self.point2.x, self.point1.x);
-        (del_y / del_x) as f64
+        del_y / del_x
     }
 
     /// Returns the angle of the line with the x-axis in radians
@@ -74,13 +76,25 @@ impl Line {
     /// let l = Line::new(Point{x: 1., y: 2.}, Point{x: 3., y: -4.});
     /// assert_eq!(l.theta(), -1.2490457723982544);
     /// ```
-    pub fn theta(&self) -> f64 {
-        let del_y = delta_coord(self.point2.y, self.point1.y) as f64;
-        let del_x = delta_coord(self.point2.x, self.point1.x) as f64;
+    pub fn theta(&self) -> T {
+        let del_y = delta_coord(self.point2.y, self.point1.y);
+        let del_x = delta_coord(self.point2.x, self.point1.x);
         del_y.atan2(del_x)
     }
 }
 
+impl<T: ApproxEq> ApproxEq for Line<T> {
+    /// Compares both endpoints in order
+    fn approx_eq(&self, other: &Line<T>, tolerance: Tolerance) -> bool {
+        self.point1.approx_eq(&other.point1, tolerance)
+            && self.point2.approx_eq(&other.point2, tolerance)
+    }
+
+    fn ulps_eq(&self, other: &Line<T>, ulps: u64) -> bool {
+        self.point1.ulps_eq(&other.point1, ulps) && self.point2.ulps_eq(&other.point2, ulps)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::line;
@@ -97,7 +111,7 @@ mod tests {
     #[test]
     fn line_length() {
         let line = line::Line::new(point::Point::new(0., 0.), point::Point::new(1., 1.));
-        assert_eq!(line.length(), 1.4142135623730951);
+        crate::assert_ulps_eq!(line.length(), std::f64::consts::SQRT_2, 1);
     }
 
     #[test]
@@ -110,8 +124,19 @@ mod tests {
     #[test]
     fn theta() {
         let line = line::Line::new(point::Point::new(0., 0.), point::Point::new(1., 1.));
-        assert_eq!(line.theta(), 0.7853981633974483);
+        crate::assert_ulps_eq!(line.theta(), std::f64::consts::FRAC_PI_4, 1);
         let line = line::Line::new(point::Point::new(0., 45.), point::Point::new(1., 0.));
-        assert_eq!(line.theta(), -1.5485777614681775);
+        crate::assert_approx_eq!(line.theta(), -1.5485777614681775);
+    }
+
+    #[test]
+    fn approx_eq() {
+        use crate::approx::{ApproxEq, Tolerance};
+        let l1 = line::Line::new(point::Point::new(0., 0.), point::Point::new(0.1 + 0.2, 1.));
+        let l2 = line::Line::new(point::Point::new(0., 0.), point::Point::new(0.3, 1.));
+        assert!(l1.approx_eq(&l2, Tolerance::default()));
+        assert!(!l1.approx_eq(&l2, Tolerance::exact()));
+        assert!(l1.ulps_eq(&l2, 1));
+        crate::assert_approx_eq!(l1, l2);
     }
 }
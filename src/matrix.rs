@@ -1,12 +1,59 @@
+use crate::approx::{ApproxEq, Tolerance};
+use crate::complex::Complex;
+use crate::polynomial::Polynomial;
+use crate::scalar::Scalar;
+use crate::structured::Tridiagonal;
 use std::error::Error;
 use std::fmt;
+use std::ops::{Index, IndexMut};
 
-#[derive(Debug)]
-/// Represents a rows x cols matrix
-pub struct Matrix {
+const MAX_ITERATIONS: usize = 100;
+const ITERATION_TOLERANCE: f64 = 1e-12;
+
+#[derive(Debug, Clone)]
+/// Represents a rows x cols matrix.
+/// Elements are f64 unless another `Scalar` type, such as `Complex`, is given.
+pub struct Matrix<T = f64> {
     pub rows: usize,
     pub cols: usize,
-    mat: Vec<Vec<f64>>,
+    mat: Vec<Vec<T>>,
+}
+
+/// Selects whether a reduction runs along every row or every column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    /// Reduce each row to a single value
+    Row,
+    /// Reduce each column to a single value
+    Col,
+}
+
+/// Selects the Gram-Schmidt variant used to orthonormalize columns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GramSchmidt {
+    /// Projects each column against the original column. Cheapest,
+    /// but orthogonality degrades for ill conditioned inputs
+    Classical,
+    /// Projects against the progressively updated column
+    Modified,
+    /// Classical Gram-Schmidt applied twice, which restores
+    /// orthogonality to working precision
+    Reorthogonalized,
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    /// Returns the element at (row, col)
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        &self.mat[row][col]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        &mut self.mat[row][col]
+    }
 }
 
 #[derive(Debug)]
@@ -14,6 +61,14 @@ pub struct MatrixError {
     reason: ErrorCause,
 }
 
+impl MatrixError {
+    pub(crate) fn new(cause: String) -> MatrixError {
+        MatrixError {
+            reason: ErrorCause { cause },
+        }
+    }
+}
+
 impl fmt::Display for MatrixError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "Matrix Error: {}", self.reason)
@@ -39,8 +94,9 @@ impl fmt::Display for ErrorCause {
 
 impl Error for ErrorCause {}
 
-impl Matrix {
-    /// Returns a matrix with all 0 values
+impl<T: Scalar> Matrix<T> {
+    /// Returns a matrix with all zero values for any scalar type.
+    /// `Matrix::new` is the f64 shorthand.
     ///
     /// # Arguments
     /// `rows` - The number of rows in a matrix.
@@ -48,25 +104,33 @@ impl Matrix {
     ///
     /// # Examples
     /// ```
+    /// use ralgeb::complex::Complex;
     /// use ralgeb::matrix::Matrix;
-    /// let m = Matrix::new(3, 4);
+    /// let m = Matrix::<Complex>::zeros(2, 2);
+    /// assert_eq!(m.get_row(0).unwrap(), vec![Complex::new(0., 0.); 2]);
     /// ```
-    pub fn new(rows: usize, cols: usize) -> Matrix {
-        let mut r = 0;
-        let mut outer_vec: Vec<Vec<f64>> = Vec::new();
-        loop {
-            let inner_vec: Vec<f64> = vec![0.; cols];
-            if r >= rows {
-                break Matrix {
-                    rows: outer_vec.len(),
-                    cols: inner_vec.len(),
-                    mat: outer_vec,
-                };
-            }
-            outer_vec.push(inner_vec);
-            r += 1;
-        }
+    pub fn zeros(rows: usize, cols: usize) -> Matrix<T> {
+        Matrix::filled(rows, cols, T::zero())
+    }
+
+    /// Returns an identity-like matrix for any scalar type.
+    /// `Matrix::identity` is the f64 shorthand.
+    ///
+    /// # Arguments
+    /// `rows` - The number of rows in a matrix.
+    /// `cols` - The number of columns in a matrix.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::complex::Complex;
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::<Complex>::eye(2, 2);
+    /// assert_eq!(m.get_principal().unwrap(), vec![Complex::new(1., 0.); 2]);
+    /// ```
+    pub fn eye(rows: usize, cols: usize) -> Matrix<T> {
+        Matrix::from_fn(rows, cols, |r, c| if r == c { T::one() } else { T::zero() })
     }
+
     /// Creates a Matrix instance from a given Vector of vector
     /// if the vector does not have elements then a simply
     /// 3x3 matrix with all elements as 0.0
@@ -85,9 +149,9 @@ impl Matrix {
     /// let m = Matrix::new_mat(v);
     /// ```
     ///
-    pub fn new_mat(v: Vec<Vec<f64>>) -> Matrix {
-        if v.len() <= 0 {
-            Matrix::new(3, 3)
+    pub fn new_mat(v: Vec<Vec<T>>) -> Matrix<T> {
+        if v.is_empty() {
+            Matrix::zeros(3, 3)
         } else {
             Matrix {
                 rows: v.len(),
@@ -96,40 +160,188 @@ impl Matrix {
             }
         }
     }
-    ///
-    /// Returns an Identity matrix
-    /// Identity matrix are always square matrix
-    /// with same number of rows and columns
+
+    /// Returns a matrix whose element at (r, c) is `f(r, c)`
     ///
     /// # Arguments
     /// `rows` - The number of rows in a matrix.
     /// `cols` - The number of columns in a matrix.
+    /// `f` - Computes the element from its row and column index.
     ///
     /// # Examples
     /// ```
     /// use ralgeb::matrix::Matrix;
-    /// let m = match Matrix::identity(3, 3) {
-    /// Some(m) => m,
-    /// None => Matrix::new(3,3),
-    /// };
+    /// let m = Matrix::from_fn(2, 3, |r, c| (r * 3 + c) as f64);
+    /// assert_eq!(m.get_row(1).unwrap(), vec![3., 4., 5.]);
     /// ```
+    pub fn from_fn<F: FnMut(usize, usize) -> T>(rows: usize, cols: usize, mut f: F) -> Matrix<T> {
+        Matrix {
+            rows,
+            cols,
+            mat: (0..rows)
+                .map(|r| (0..cols).map(|c| f(r, c)).collect())
+                .collect(),
+        }
+    }
+
+    /// Returns a square matrix with the given values on
+    /// the principal diagonal and 0.0 elsewhere
     ///
-    pub fn identity(rows: usize, cols: usize) -> Option<Matrix> {
-        if rows == cols {
-            let mut m = Matrix::new(rows, cols);
-            let mut r = 0;
-            loop {
-                if r >= rows {
-                    break;
-                }
-                m.mat[r][r] = 1.0;
-                r += 1;
-            }
-            Some(m)
-        } else {
-            None
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::from_diagonal(&[1., 2., 3.]);
+    /// assert_eq!(m.get_principal().unwrap(), vec![1., 2., 3.]);
+    /// ```
+    pub fn from_diagonal(diagonal: &[T]) -> Matrix<T> {
+        let n = diagonal.len();
+        Matrix::from_fn(n, n, |r, c| if r == c { diagonal[r] } else { T::zero() })
+    }
+
+    /// Returns a matrix with every element set to `value`
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::filled(2, 3, 7.);
+    /// assert_eq!(m.get_col(2), vec![7., 7.]);
+    /// ```
+    pub fn filled(rows: usize, cols: usize, value: T) -> Matrix<T> {
+        Matrix {
+            rows,
+            cols,
+            mat: vec![vec![value; cols]; rows],
+        }
+    }
+
+    /// Creates a matrix from a list of rows.
+    /// Unlike `new_mat` every row must have the same length
+    /// and an empty list gives a 0x0 matrix.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::from_rows(vec![vec![1., 2.], vec![3., 4.]]).unwrap();
+    /// assert_eq!(m.get_col(0), vec![1., 3.]);
+    /// assert!(Matrix::from_rows(vec![vec![1., 2.], vec![3.]]).is_err());
+    /// ```
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Matrix<T>, MatrixError> {
+        let cols = rows.first().map_or(0, |r| r.len());
+        if let Some(i) = rows.iter().position(|r| r.len() != cols) {
+            return Err(MatrixError::new(format!(
+                "Row {} has {} elements but {} were expected",
+                i,
+                rows[i].len(),
+                cols
+            )));
+        }
+        Ok(Matrix {
+            rows: rows.len(),
+            cols,
+            mat: rows,
+        })
+    }
+
+    /// Creates a matrix from a list of columns.
+    /// Every column must have the same length.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::from_cols(vec![vec![1., 2.], vec![3., 4.]]).unwrap();
+    /// assert_eq!(m.get_row(0).unwrap(), vec![1., 3.]);
+    /// ```
+    pub fn from_cols(cols: Vec<Vec<T>>) -> Result<Matrix<T>, MatrixError> {
+        Ok(Matrix::transpose(Matrix::from_rows(cols)?))
+    }
+
+    /// Joins matrices side by side.
+    /// All the matrices must have the same number of rows.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let a = Matrix::ones(2, 1);
+    /// let b = Matrix::identity(2, 2).unwrap();
+    /// let m = Matrix::hstack(&[&a, &b]).unwrap();
+    /// assert_eq!(m.get_row(0).unwrap(), vec![1., 1., 0.]);
+    /// ```
+    pub fn hstack(matrices: &[&Matrix<T>]) -> Result<Matrix<T>, MatrixError> {
+        let rows = matrices.first().map_or(0, |m| m.rows);
+        if let Some(m) = matrices.iter().find(|m| m.rows != rows) {
+            return Err(MatrixError::new(format!(
+                "Cannot stack horizontally a matrix with {} rows onto {} rows",
+                m.rows, rows
+            )));
+        }
+        let mat: Vec<Vec<T>> = (0..rows)
+            .map(|r| {
+                matrices
+                    .iter()
+                    .flat_map(|m| m.mat[r].iter().cloned())
+                    .collect()
+            })
+            .collect();
+        Ok(Matrix {
+            rows,
+            cols: matrices.iter().map(|m| m.cols).sum(),
+            mat,
+        })
+    }
+
+    /// Joins matrices one below the other.
+    /// All the matrices must have the same number of columns.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let a = Matrix::ones(1, 2);
+    /// let b = Matrix::identity(2, 2).unwrap();
+    /// let m = Matrix::vstack(&[&a, &b]).unwrap();
+    /// assert_eq!(m.get_col(0), vec![1., 1., 0.]);
+    /// ```
+    pub fn vstack(matrices: &[&Matrix<T>]) -> Result<Matrix<T>, MatrixError> {
+        let cols = matrices.first().map_or(0, |m| m.cols);
+        if let Some(m) = matrices.iter().find(|m| m.cols != cols) {
+            return Err(MatrixError::new(format!(
+                "Cannot stack vertically a matrix with {} columns onto {} columns",
+                m.cols, cols
+            )));
         }
+        let mat: Vec<Vec<T>> = matrices
+            .iter()
+            .flat_map(|m| m.mat.iter().cloned())
+            .collect();
+        Ok(Matrix {
+            rows: mat.len(),
+            cols,
+            mat,
+        })
+    }
+
+    /// Assembles a matrix from a grid of blocks.
+    /// Blocks in the same block row must have the same number of rows
+    /// and every block row must add up to the same number of columns.
+    ///
+    /// # Arguments
+    /// `blocks` - The block rows, each a list of matrices.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let a = Matrix::identity(2, 2).unwrap();
+    /// let z = Matrix::new(2, 2);
+    /// let m = Matrix::block(&[vec![&a, &z], vec![&z, &a]]).unwrap();
+    /// assert_eq!(m.get_principal().unwrap(), vec![1.; 4]);
+    /// ```
+    pub fn block(blocks: &[Vec<&Matrix<T>>]) -> Result<Matrix<T>, MatrixError> {
+        let block_rows = blocks
+            .iter()
+            .map(|row| Matrix::hstack(row))
+            .collect::<Result<Vec<Matrix<T>>, MatrixError>>()?;
+        Matrix::vstack(&block_rows.iter().collect::<Vec<&Matrix<T>>>())
     }
+
     /// is_square takes in a Matrix and returns whether
     /// the matrix is a square matrix i.e nxn
     /// or not
@@ -160,8 +372,8 @@ impl Matrix {
     ///
     /// ```
     ///
-    pub fn get_principal(&self) -> Result<Vec<f64>, MatrixError> {
-        let mut principal: Vec<f64> = Vec::new();
+    pub fn get_principal(&self) -> Result<Vec<T>, MatrixError> {
+        let mut principal: Vec<T> = Vec::new();
         if self.is_square() {
             let mut r = 0;
             loop {
@@ -199,7 +411,7 @@ impl Matrix {
     /// };
     /// ```
     ///
-    pub fn replace_row(mut self, row_num: usize, row: Vec<f64>) -> Result<Matrix, MatrixError> {
+    pub fn replace_row(mut self, row_num: usize, row: Vec<T>) -> Result<Matrix<T>, MatrixError> {
         if self.cols != row.len() {
             Err(MatrixError {
                 reason: ErrorCause {
@@ -218,6 +430,7 @@ impl Matrix {
             })
         }
     }
+
     /// Multiplies a row of matrix with a scalar value
     ///
     /// # Arguments
@@ -235,28 +448,9 @@ impl Matrix {
     ///
     /// };
     /// ```
-    pub fn scalar_row_mul(mut self, row_num: usize, scalar: f64) -> Result<Matrix, MatrixError> {
-        if scalar == 0.0 {
-            return Err(MatrixError {
-                reason: ErrorCause {
-                    cause: format!("The should be non-zero"),
-                },
-            });
-        }
-        if row_num <= self.rows {
-            self.mat[row_num] = self.mat[row_num].iter().map(|x| x * scalar).collect();
-            Ok(Matrix {
-                rows: self.rows,
-                cols: self.cols,
-                mat: self.mat,
-            })
-        } else {
-            Err(MatrixError {
-                reason: ErrorCause {
-                    cause: format!("The row {} does not exists", row_num),
-                },
-            })
-        }
+    pub fn scalar_row_mul(mut self, row_num: usize, scalar: T) -> Result<Matrix<T>, MatrixError> {
+        self.scalar_row_mul_inplace(row_num, scalar)?;
+        Ok(self)
     }
 
     /// Adds the given 2 matrix
@@ -276,31 +470,12 @@ impl Matrix {
     /// Err(e) => panic!(e),
     /// };
     /// ```
-    pub fn add(m1: &Matrix, m2: &Matrix) -> Result<Matrix, MatrixError> {
-        if m1.rows == m2.rows && m1.cols == m2.cols {
-            let mut res = Matrix::new(m1.rows, m1.cols);
-            let mut i = 0;
-            while i < m2.rows {
-                let mut j = 0;
-                while j < m2.cols {
-                    res.mat[i][j] = m1.mat[i][j] + m2.mat[i][j];
-                    j += 1;
-                }
-                i += 1;
-            }
-            Ok(res)
-        } else {
-            Err(MatrixError {
-                reason: ErrorCause {
-                    cause: format!(
-                        "The dimensions are different. Row Diff: {}, Col Diff: {}",
-                        (m1.rows as isize - m2.rows as isize).abs(),
-                        (m1.cols as isize - m2.cols as isize).abs()
-                    ),
-                },
-            })
-        }
+    pub fn add(m1: &Matrix<T>, m2: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        let mut res = Matrix::zeros(m1.rows, m1.cols);
+        Matrix::add_into(m1, m2, &mut res)?;
+        Ok(res)
     }
+
     /// Subtracts the given 2 matrix
     ///
     /// # Arguments
@@ -318,31 +493,12 @@ impl Matrix {
     /// Err(e) => panic!(e),
     /// };
     /// ```
-    pub fn subtract(m1: &Matrix, m2: &Matrix) -> Result<Matrix, MatrixError> {
-        if m1.rows == m2.rows && m1.cols == m2.cols {
-            let mut res = Matrix::new(m1.rows, m1.cols);
-            let mut i = 0;
-            while i < m2.rows {
-                let mut j = 0;
-                while j < m2.cols {
-                    res.mat[i][j] = m1.mat[i][j] - m2.mat[i][j];
-                    j += 1;
-                }
-                i += 1;
-            }
-            Ok(res)
-        } else {
-            Err(MatrixError {
-                reason: ErrorCause {
-                    cause: format!(
-                        "The dimensions are different. Row Diff: {}, Col Diff: {}",
-                        (m1.rows as isize - m2.rows as isize).abs(),
-                        (m1.cols as isize - m2.cols as isize).abs()
-                    ),
-                },
-            })
-        }
+    pub fn subtract(m1: &Matrix<T>, m2: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        let mut res = Matrix::zeros(m1.rows, m1.cols);
+        Matrix::subtract_into(m1, m2, &mut res)?;
+        Ok(res)
     }
+
     /// Transposes a matrix
     ///
     /// # Examples
@@ -353,12 +509,12 @@ impl Matrix {
     /// m = Matrix::transpose(m);
     ///
     /// ```
-    pub fn transpose(m: Matrix) -> Matrix {
+    pub fn transpose(m: Matrix<T>) -> Matrix<T> {
         let mut c = 0;
-        let mut mat = Matrix::new(m.cols, m.rows);
+        let mut mat = Matrix::zeros(m.cols, m.rows);
         while m.cols > c {
             let mut r = 0;
-            let mut v: Vec<f64> = Vec::new();
+            let mut v: Vec<T> = Vec::new();
             while m.rows > r {
                 v.push(m.mat[r][c]);
                 r += 1;
@@ -369,6 +525,7 @@ impl Matrix {
         }
         mat
     }
+
     /// Multiplies a matrix with a scalar value
     ///
     /// # Arguments
@@ -384,25 +541,9 @@ impl Matrix {
     /// Err(e) => panic!(e),
     /// };
     /// ```
-    pub fn scalar_mat_mul(mut self, scalar: f64) -> Result<Matrix, MatrixError> {
-        if scalar == 0.0 {
-            Err(MatrixError {
-                reason: ErrorCause {
-                    cause: format!("The should be non-zero"),
-                },
-            })
-        } else {
-            let mut r = 0;
-            while r < self.rows {
-                self = self.scalar_row_mul(r, scalar)?;
-                r += 1;
-            }
-            Ok(Matrix {
-                rows: self.rows,
-                cols: self.cols,
-                mat: (*self.mat).to_vec(),
-            })
-        }
+    pub fn scalar_mat_mul(mut self, scalar: T) -> Result<Matrix<T>, MatrixError> {
+        self.scalar_mat_mul_inplace(scalar)?;
+        Ok(self)
     }
 
     /// Will return a row from the matrix
@@ -418,7 +559,7 @@ impl Matrix {
     ///   Err(e) => panic!(e),
     /// };
     /// ```
-    pub fn get_row(&self, row_num: usize) -> Result<Vec<f64>, MatrixError> {
+    pub fn get_row(&self, row_num: usize) -> Result<Vec<T>, MatrixError> {
         if row_num >= self.rows {
             Err(MatrixError {
                 reason: ErrorCause {
@@ -446,92 +587,2908 @@ impl Matrix {
     /// assert_eq!(result.cols, 2);
     /// ```
     ///
-    pub fn multiply(m1: &Matrix, m2: &Matrix) -> Result<Matrix, MatrixError> {
-        if m1.cols != m2.rows {
-            return Err(MatrixError{reason: ErrorCause{cause: format!("The multiplication cannot be performed. The columns of matrix1 {} should be equal to rows of matrix2 {}", m1.cols, m2.rows)}});
-        }
-        let mut result = Matrix::new(m1.rows, m2.cols);
-
-        for i in 0..m1.rows {
-            for j in 0..m2.cols {
-                result.mat[i][j] = Matrix::dot_product(&m1.mat[i], &m2.get_col(j));
-            }
-        }
+    pub fn multiply(m1: &Matrix<T>, m2: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        let mut result = Matrix::zeros(m1.rows, m2.cols);
+        Matrix::multiply_into(m1, m2, &mut result)?;
         Ok(result)
     }
-    /// Returns a column from a matrix as a Vector
+
+    /// Adds 2 matrices into an existing matrix of the same dimensions
+    /// without allocating
     ///
     /// # Arguments
-    /// `col_num` - The column number to be reeturned (0 indexed)
+    /// `m1` - 1st matrix
+    /// `m2` - 2nd matrix
+    /// `out` - The destination
     ///
     /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::identity(2, 2).unwrap();
+    /// let mut out = Matrix::new(2, 2);
+    /// Matrix::add_into(&m, &m, &mut out).unwrap();
+    /// assert_eq!(out.get_row(0).unwrap(), vec![2., 0.]);
+    /// ```
+    pub fn add_into(
+        m1: &Matrix<T>,
+        m2: &Matrix<T>,
+        out: &mut Matrix<T>,
+    ) -> Result<(), MatrixError> {
+        Matrix::same_dimensions(m1, m2)?;
+        out.output_check(m1.rows, m1.cols)?;
+        Matrix::zip_rows_into(m1, m2, out, |a, b| a + b);
+        Ok(())
+    }
+
+    /// Subtracts m2 from m1 into an existing matrix of the same
+    /// dimensions without allocating
+    ///
+    /// # Arguments
+    /// `m1` - 1st matrix
+    /// `m2` - 2nd matrix
+    /// `out` - The destination
     ///
+    /// # Examples
     /// ```
     /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::identity(2, 2).unwrap();
+    /// let mut out = Matrix::new(2, 2);
+    /// Matrix::subtract_into(&m, &m, &mut out).unwrap();
+    /// assert_eq!(out.max_abs(), 0.);
+    /// ```
+    pub fn subtract_into(
+        m1: &Matrix<T>,
+        m2: &Matrix<T>,
+        out: &mut Matrix<T>,
+    ) -> Result<(), MatrixError> {
+        Matrix::same_dimensions(m1, m2)?;
+        out.output_check(m1.rows, m1.cols)?;
+        Matrix::zip_rows_into(m1, m2, out, |a, b| a - b);
+        Ok(())
+    }
+
+    /// Multiplies 2 matrices into an existing matrix of dimensions
+    /// m1.rows x m2.cols without allocating
     ///
-    /// let m = Matrix::identity(3, 3).unwrap();
-    /// assert_eq!(m.get_col(2), vec![0.,0.,1.]);
+    /// # Arguments
+    /// `m1` - Matrix 1
+    /// `m2` - Matrix 2
+    /// `out` - The destination
+    ///
+    /// # Examples
     /// ```
-    pub fn get_col(&self, col_num: usize) -> Vec<f64> {
-        let mut c: Vec<f64> = vec![];
-        if col_num >= self.cols {
-            vec![0.; self.cols]
-        } else {
-            for r in self.mat.iter() {
-                c.push(r[col_num])
+    /// use ralgeb::matrix::Matrix;
+    /// let m1 = Matrix::new_mat(vec![vec![1., 2.]]);
+    /// let m2 = Matrix::new_mat(vec![vec![3.], vec![4.]]);
+    /// let mut out = Matrix::new(1, 1);
+    /// Matrix::multiply_into(&m1, &m2, &mut out).unwrap();
+    /// assert_eq!(out[(0, 0)], 11.);
+    /// ```
+    pub fn multiply_into(
+        m1: &Matrix<T>,
+        m2: &Matrix<T>,
+        out: &mut Matrix<T>,
+    ) -> Result<(), MatrixError> {
+        if m1.cols != m2.rows {
+            return Err(MatrixError::new(format!("The multiplication cannot be performed. The columns of matrix1 {} should be equal to rows of matrix2 {}", m1.cols, m2.rows)));
+        }
+        out.output_check(m1.rows, m2.cols)?;
+        for (row, out_row) in m1.mat.iter().zip(out.mat.iter_mut()) {
+            for (j, v) in out_row.iter_mut().enumerate() {
+                *v = row
+                    .iter()
+                    .zip(m2.mat.iter())
+                    .fold(T::zero(), |acc, (a, b_row)| acc + *a * b_row[j]);
             }
-            c
         }
+        Ok(())
     }
-    /// Returns the dot product of 2 vectors.
-    /// v1 and v2 should have same length
-    /// # Arguments
-    /// `v1` - Vector 1 of length n
-    /// `v2` - Vector 2 of length n
+
+    /// Adds another matrix of the same dimensions to this one
     ///
     /// # Examples
-    ///
     /// ```
     /// use ralgeb::matrix::Matrix;
-    /// let v1 = vec![1.,2.,3.];
-    /// let v2 = vec![1.,2.,3.];
-    /// assert_eq!(Matrix::dot_product(&v1, &v2), 14.);
+    /// let mut m = Matrix::identity(2, 2).unwrap();
+    /// m.add_inplace(&Matrix::ones(2, 2)).unwrap();
+    /// assert_eq!(m.get_row(0).unwrap(), vec![2., 1.]);
     /// ```
-    ///
-    pub fn dot_product(v1: &Vec<f64>, v2: &Vec<f64>) -> f64 {
-        if v1.len() != v2.len() {
-            0.
-        } else {
-            let mut result = 0.;
-            for i in 0..v1.len() {
-                result += v1[i] * v2[i];
+    pub fn add_inplace(&mut self, other: &Matrix<T>) -> Result<(), MatrixError> {
+        Matrix::same_dimensions(self, other)?;
+        for (row, o) in self.mat.iter_mut().zip(other.mat.iter()) {
+            for (v, o) in row.iter_mut().zip(o) {
+                *v = *v + *o;
             }
-            result
         }
+        Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::matrix;
-    #[test]
-    fn new_zero_matrix() {
-        let m = matrix::Matrix::new(2, 3);
-        let v: Vec<f64> = vec![0.0; 3];
-        assert_eq!(m.mat[0], v);
-        assert_eq!(m.rows, 2);
-        assert_eq!(m.cols, 3);
+    /// Subtracts another matrix of the same dimensions from this one
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let mut m = Matrix::identity(2, 2).unwrap();
+    /// m.subtract_inplace(&Matrix::ones(2, 2)).unwrap();
+    /// assert_eq!(m.get_row(0).unwrap(), vec![0., -1.]);
+    /// ```
+    pub fn subtract_inplace(&mut self, other: &Matrix<T>) -> Result<(), MatrixError> {
+        Matrix::same_dimensions(self, other)?;
+        for (row, o) in self.mat.iter_mut().zip(other.mat.iter()) {
+            for (v, o) in row.iter_mut().zip(o) {
+                *v = *v - *o;
+            }
+        }
+        Ok(())
     }
 
-    #[test]
-    fn identity() {
-        // Identity matrix is always square matrix
-
-        // Provide rectangular row and col
-        let m = matrix::Matrix::identity(4, 3);
-        assert_eq!(true, m.is_none());
+    /// Writes the transpose of a matrix into an existing matrix
+    /// of dimensions m.cols x m.rows without allocating
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., 2., 3.]]);
+    /// let mut out = Matrix::new(3, 1);
+    /// Matrix::transpose_into(&m, &mut out).unwrap();
+    /// assert_eq!(out.get_col(0), vec![1., 2., 3.]);
+    /// ```
+    pub fn transpose_into(m: &Matrix<T>, out: &mut Matrix<T>) -> Result<(), MatrixError> {
+        out.output_check(m.cols, m.rows)?;
+        for (r, row) in m.mat.iter().enumerate() {
+            for (c, v) in row.iter().enumerate() {
+                out.mat[c][r] = *v;
+            }
+        }
+        Ok(())
+    }
 
-        // Provide square row and col
+    /// Transposes a square matrix in place by swapping elements
+    /// across the principal diagonal
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let mut m = Matrix::new_mat(vec![vec![1., 2.], vec![3., 4.]]);
+    /// m.transpose_inplace().unwrap();
+    /// assert_eq!(m.get_row(0).unwrap(), vec![1., 3.]);
+    /// assert!(Matrix::new(2, 3).transpose_inplace().is_err());
+    /// ```
+    pub fn transpose_inplace(&mut self) -> Result<(), MatrixError> {
+        self.square_check()?;
+        for r in 0..self.rows {
+            for c in r + 1..self.cols {
+                let v = self.mat[r][c];
+                self.mat[r][c] = self.mat[c][r];
+                self.mat[c][r] = v;
+            }
+        }
+        Ok(())
+    }
+
+    /// Multiplies the matrix with a scalar value in place
+    ///
+    /// # Arguments
+    /// `scalar` - The multiplier. It should be non-zero
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let mut m = Matrix::identity(2, 2).unwrap();
+    /// m.scalar_mat_mul_inplace(3.).unwrap();
+    /// assert_eq!(m.trace().unwrap(), 6.);
+    /// ```
+    pub fn scalar_mat_mul_inplace(&mut self, scalar: T) -> Result<(), MatrixError> {
+        if scalar == T::zero() {
+            return Err(MatrixError::new(
+                "The scalar should be non-zero".to_string(),
+            ));
+        }
+        self.map_inplace(|x| x * scalar);
+        Ok(())
+    }
+
+    /// Multiplies a row of the matrix with a scalar value in place
+    ///
+    /// # Arguments
+    /// `row_num` - The row on which the scalar multiplication should occur.
+    /// `scalar` - The multiplier. It should be non-zero
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let mut m = Matrix::ones(2, 2);
+    /// m.scalar_row_mul_inplace(1, 5.).unwrap();
+    /// assert_eq!(m.get_col(0), vec![1., 5.]);
+    /// ```
+    pub fn scalar_row_mul_inplace(&mut self, row_num: usize, scalar: T) -> Result<(), MatrixError> {
+        if scalar == T::zero() {
+            return Err(MatrixError::new(
+                "The scalar should be non-zero".to_string(),
+            ));
+        }
+        match self.mat.get_mut(row_num) {
+            Some(row) => {
+                for v in row.iter_mut() {
+                    *v = *v * scalar;
+                }
+                Ok(())
+            }
+            None => Err(MatrixError::new(format!(
+                "The row {} does not exists",
+                row_num
+            ))),
+        }
+    }
+
+    /// Returns a column from a matrix as a Vector
+    ///
+    /// # Arguments
+    /// `col_num` - The column number to be reeturned (0 indexed)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    ///
+    /// let m = Matrix::identity(3, 3).unwrap();
+    /// assert_eq!(m.get_col(2), vec![0.,0.,1.]);
+    /// ```
+    pub fn get_col(&self, col_num: usize) -> Vec<T> {
+        let mut c: Vec<T> = vec![];
+        if col_num >= self.cols {
+            vec![T::zero(); self.cols]
+        } else {
+            for r in self.mat.iter() {
+                c.push(r[col_num])
+            }
+            c
+        }
+    }
+
+    /// Returns the dot product of 2 vectors.
+    /// v1 and v2 should have same length
+    /// # Arguments
+    /// `v1` - Vector 1 of length n
+    /// `v2` - Vector 2 of length n
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let v1 = vec![1.,2.,3.];
+    /// let v2 = vec![1.,2.,3.];
+    /// assert_eq!(Matrix::dot_product(&v1, &v2), 14.);
+    /// ```
+    ///
+    pub fn dot_product(v1: &[T], v2: &[T]) -> T {
+        if v1.len() != v2.len() {
+            T::zero()
+        } else {
+            let mut result = T::zero();
+            for i in 0..v1.len() {
+                result = result + v1[i] * v2[i];
+            }
+            result
+        }
+    }
+
+    /// Returns a matrix with the same elements in row-major order
+    /// but with new dimensions.
+    /// rows * cols must equal the current number of elements.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::from_fn(2, 3, |r, c| (r * 3 + c) as f64);
+    /// let r = m.reshape(3, 2).unwrap();
+    /// assert_eq!(r.get_row(1).unwrap(), vec![2., 3.]);
+    /// ```
+    pub fn reshape(&self, rows: usize, cols: usize) -> Result<Matrix<T>, MatrixError> {
+        if rows * cols != self.rows * self.cols {
+            return Err(MatrixError::new(format!(
+                "Cannot reshape {} elements into {}x{}",
+                self.rows * self.cols,
+                rows,
+                cols
+            )));
+        }
+        let flat: Vec<T> = self.mat.iter().flat_map(|r| r.iter().cloned()).collect();
+        Ok(Matrix::from_fn(rows, cols, |r, c| flat[r * cols + c]))
+    }
+
+    /// Returns a matrix with new dimensions keeping the elements
+    /// at their positions. Elements outside the new dimensions are
+    /// dropped and new positions are filled with 0.0.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::ones(2, 2).resize(2, 3);
+    /// assert_eq!(m.get_row(0).unwrap(), vec![1., 1., 0.]);
+    /// ```
+    pub fn resize(&self, rows: usize, cols: usize) -> Matrix<T> {
+        Matrix::from_fn(rows, cols, |r, c| {
+            if r < self.rows && c < self.cols {
+                self.mat[r][c]
+            } else {
+                T::zero()
+            }
+        })
+    }
+
+    /// Inserts a row before the given index.
+    /// An index equal to the number of rows appends the row.
+    ///
+    /// # Arguments
+    /// `row_num` - The position of the new row (starts with 0 index).
+    /// `row` - The new row. It must have as many elements as the matrix has columns.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new(2, 2).insert_row(1, vec![1., 2.]).unwrap();
+    /// assert_eq!(m.rows, 3);
+    /// assert_eq!(m.get_row(1).unwrap(), vec![1., 2.]);
+    /// ```
+    pub fn insert_row(mut self, row_num: usize, row: Vec<T>) -> Result<Matrix<T>, MatrixError> {
+        if row_num > self.rows {
+            return Err(MatrixError::new(format!(
+                "The row {} does not exists",
+                row_num
+            )));
+        }
+        if row.len() != self.cols {
+            return Err(MatrixError::new(format!(
+                "The number of columns differ by {}",
+                (self.cols as isize - row.len() as isize).abs()
+            )));
+        }
+        self.mat.insert(row_num, row);
+        self.rows += 1;
+        Ok(self)
+    }
+
+    /// Removes a row from the matrix.
+    ///
+    /// # Arguments
+    /// `row_num` - The row to remove (starts with 0 index).
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::identity(3, 3).unwrap().remove_row(0).unwrap();
+    /// assert_eq!(m.get_row(0).unwrap(), vec![0., 1., 0.]);
+    /// ```
+    pub fn remove_row(mut self, row_num: usize) -> Result<Matrix<T>, MatrixError> {
+        if row_num >= self.rows {
+            return Err(MatrixError::new(format!(
+                "The row {} does not exists",
+                row_num
+            )));
+        }
+        self.mat.remove(row_num);
+        self.rows -= 1;
+        Ok(self)
+    }
+
+    /// Inserts a column before the given index.
+    /// An index equal to the number of columns appends the column.
+    ///
+    /// # Arguments
+    /// `col_num` - The position of the new column (starts with 0 index).
+    /// `col` - The new column. It must have as many elements as the matrix has rows.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new(2, 2).insert_col(2, vec![1., 2.]).unwrap();
+    /// assert_eq!(m.get_col(2), vec![1., 2.]);
+    /// ```
+    pub fn insert_col(mut self, col_num: usize, col: Vec<T>) -> Result<Matrix<T>, MatrixError> {
+        if col_num > self.cols {
+            return Err(MatrixError::new(format!(
+                "The column {} does not exists",
+                col_num
+            )));
+        }
+        if col.len() != self.rows {
+            return Err(MatrixError::new(format!(
+                "The number of rows differ by {}",
+                (self.rows as isize - col.len() as isize).abs()
+            )));
+        }
+        for (row, v) in self.mat.iter_mut().zip(col) {
+            row.insert(col_num, v);
+        }
+        self.cols += 1;
+        Ok(self)
+    }
+
+    /// Removes a column from the matrix.
+    ///
+    /// # Arguments
+    /// `col_num` - The column to remove (starts with 0 index).
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::identity(3, 3).unwrap().remove_col(0).unwrap();
+    /// assert_eq!(m.get_row(1).unwrap(), vec![1., 0.]);
+    /// ```
+    pub fn remove_col(mut self, col_num: usize) -> Result<Matrix<T>, MatrixError> {
+        if col_num >= self.cols {
+            return Err(MatrixError::new(format!(
+                "The column {} does not exists",
+                col_num
+            )));
+        }
+        for row in self.mat.iter_mut() {
+            row.remove(col_num);
+        }
+        self.cols -= 1;
+        Ok(self)
+    }
+
+    /// Returns the matrix upside down, i.e with the order of rows reversed.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., 2.], vec![3., 4.]]);
+    /// assert_eq!(m.flip_rows().get_row(0).unwrap(), vec![3., 4.]);
+    /// ```
+    pub fn flip_rows(&self) -> Matrix<T> {
+        Matrix::from_fn(self.rows, self.cols, |r, c| self.mat[self.rows - 1 - r][c])
+    }
+
+    /// Returns the mirror image of the matrix, i.e with the order of columns reversed.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., 2.], vec![3., 4.]]);
+    /// assert_eq!(m.flip_cols().get_row(0).unwrap(), vec![2., 1.]);
+    /// ```
+    pub fn flip_cols(&self) -> Matrix<T> {
+        Matrix::from_fn(self.rows, self.cols, |r, c| self.mat[r][self.cols - 1 - c])
+    }
+
+    /// Rotates the matrix by 90 degrees counter-clockwise `k` times.
+    /// Negative values of `k` rotate clockwise.
+    ///
+    /// # Arguments
+    /// `k` - The number of quarter turns.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., 2.], vec![3., 4.]]);
+    /// let r = m.rotate90(1);
+    /// assert_eq!(r.get_row(0).unwrap(), vec![2., 4.]);
+    /// assert_eq!(r.get_row(1).unwrap(), vec![1., 3.]);
+    /// ```
+    pub fn rotate90(&self, k: i32) -> Matrix<T> {
+        let (rows, cols) = (self.rows, self.cols);
+        match k.rem_euclid(4) {
+            0 => self.clone(),
+            1 => Matrix::from_fn(cols, rows, |r, c| self.mat[c][cols - 1 - r]),
+            2 => Matrix::from_fn(rows, cols, |r, c| self.mat[rows - 1 - r][cols - 1 - c]),
+            _ => Matrix::from_fn(cols, rows, |r, c| self.mat[rows - 1 - c][r]),
+        }
+    }
+
+    /// Returns the Kronecker product of 2 matrices.
+    /// An (m x n) and a (p x q) matrix give an (mp x nq) matrix
+    /// made of blocks `m1[i][j] * m2`.
+    ///
+    /// # Arguments
+    /// `m1` - Matrix 1
+    /// `m2` - Matrix 2
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let x = Matrix::new_mat(vec![vec![0., 1.], vec![1., 0.]]);
+    /// let i = Matrix::identity(2, 2).unwrap();
+    /// let m = Matrix::kronecker(&x, &i);
+    /// assert_eq!(m.get_row(0).unwrap(), vec![0., 0., 1., 0.]);
+    /// ```
+    pub fn kronecker(m1: &Matrix<T>, m2: &Matrix<T>) -> Matrix<T> {
+        Matrix::from_fn(m1.rows * m2.rows, m1.cols * m2.cols, |r, c| {
+            m1.mat[r / m2.rows][c / m2.cols] * m2.mat[r % m2.rows][c % m2.cols]
+        })
+    }
+
+    /// Returns the outer product of 2 vectors,
+    /// the matrix whose element at (i, j) is `v1[i] * v2[j]`.
+    ///
+    /// # Arguments
+    /// `v1` - Vector 1 of length m
+    /// `v2` - Vector 2 of length n
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::outer(&[1., 2.], &[3., 4., 5.]);
+    /// assert_eq!(m.get_row(1).unwrap(), vec![6., 8., 10.]);
+    /// ```
+    pub fn outer(v1: &[T], v2: &[T]) -> Matrix<T> {
+        Matrix::from_fn(v1.len(), v2.len(), |r, c| v1[r] * v2[c])
+    }
+
+    /// Returns the direct sum of 2 matrices, the block diagonal matrix
+    /// with m1 in the top left and m2 in the bottom right corner.
+    ///
+    /// # Arguments
+    /// `m1` - Matrix 1
+    /// `m2` - Matrix 2
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let a = Matrix::ones(1, 1);
+    /// let b = Matrix::filled(2, 2, 2.);
+    /// let m = Matrix::direct_sum(&a, &b);
+    /// assert_eq!(m.get_row(0).unwrap(), vec![1., 0., 0.]);
+    /// assert_eq!(m.get_row(2).unwrap(), vec![0., 2., 2.]);
+    /// ```
+    pub fn direct_sum(m1: &Matrix<T>, m2: &Matrix<T>) -> Matrix<T> {
+        Matrix::from_fn(m1.rows + m2.rows, m1.cols + m2.cols, |r, c| {
+            if r < m1.rows && c < m1.cols {
+                m1.mat[r][c]
+            } else if r >= m1.rows && c >= m1.cols {
+                m2.mat[r - m1.rows][c - m1.cols]
+            } else {
+                T::zero()
+            }
+        })
+    }
+
+    /// Returns the commutator [A, B] = AB - BA of 2 square matrices
+    /// of the same size.
+    ///
+    /// # Arguments
+    /// `a` - Matrix A
+    /// `b` - Matrix B
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let x = Matrix::new_mat(vec![vec![0., 1.], vec![1., 0.]]);
+    /// let z = Matrix::new_mat(vec![vec![1., 0.], vec![0., -1.]]);
+    /// let c = Matrix::commutator(&x, &z).unwrap();
+    /// assert_eq!(c.get_row(0).unwrap(), vec![0., -2.]);
+    /// ```
+    pub fn commutator(a: &Matrix<T>, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        a.square_check()?;
+        b.square_check()?;
+        let ab = Matrix::multiply(a, b)?;
+        let ba = Matrix::multiply(b, a)?;
+        Matrix::subtract(&ab, &ba)
+    }
+
+    /// Returns the inverse of a square matrix.
+    /// The matrix must be non-singular.
+    /// Uses Gauss-Jordan elimination on [A | I]. Exact elements such as
+    /// `Rational` are reduced fraction-free (Bareiss), so the inverse is exact,
+    /// and floating point elements with partial pivoting.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// use ralgeb::rational::Rational;
+    /// let m = Matrix::new_mat(vec![vec![4., 7.], vec![2., 6.]]);
+    /// let inv = m.inverse().unwrap();
+    /// assert!((inv.get_row(0).unwrap()[0] - 0.6).abs() < 1e-12);
+    ///
+    /// let q = |n| Rational::from_integer(n);
+    /// let m = Matrix::new_mat(vec![vec![q(4), q(7)], vec![q(2), q(6)]]);
+    /// let inv = m.inverse().unwrap();
+    /// assert_eq!(inv[(0, 1)].to_string(), "-7/10");
+    /// ```
+    pub fn inverse(&self) -> Result<Matrix<T>, MatrixError> {
+        self.square_check()?;
+        let n = self.rows;
+        let augmented = Matrix::hstack(&[self, &Matrix::eye(n, n)])?;
+        let (reduced, pivots) = augmented.reduce(n);
+        if pivots.len() < n {
+            return Err(MatrixError::new("The matrix is singular".to_string()));
+        }
+        Ok(Matrix::from_fn(n, n, |r, c| {
+            reduced.mat[r][n + c] / reduced.mat[r][r]
+        }))
+    }
+
+    /// Solves the system AX = B where A is this matrix.
+    /// Uses Gauss-Jordan elimination with partial pivoting.
+    ///
+    /// # Arguments
+    /// `b` - The right hand side. Must have as many rows as the matrix.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let a = Matrix::new_mat(vec![vec![2., 1.], vec![1., 3.]]);
+    /// let b = Matrix::new_mat(vec![vec![3.], vec![5.]]);
+    /// let x = a.solve(&b).unwrap();
+    /// assert!((x.get_col(0)[0] - 0.8).abs() < 1e-12);
+    /// assert!((x.get_col(0)[1] - 1.4).abs() < 1e-12);
+    /// ```
+    pub fn solve(&self, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        self.square_check()?;
+        if b.rows != self.rows {
+            return Err(MatrixError::new(format!(
+                "The right hand side should have {} rows but has {}",
+                self.rows, b.rows
+            )));
+        }
+        let n = self.rows;
+        let mut a = self.mat.clone();
+        let mut x = b.mat.clone();
+        let scale = self.max_magnitude();
+        for k in 0..n {
+            let p = (k..n)
+                .max_by(|&i, &j| a[i][k].magnitude().total_cmp(&a[j][k].magnitude()))
+                .unwrap();
+            if a[p][k].magnitude() <= scale * n as f64 * T::epsilon() {
+                return Err(MatrixError::new("The matrix is singular".to_string()));
+            }
+            a.swap(k, p);
+            x.swap(k, p);
+            let pivot_row = a[k].clone();
+            let pivot_rhs = x[k].clone();
+            for i in 0..n {
+                if i == k {
+                    continue;
+                }
+                let factor = a[i][k] / pivot_row[k];
+                if factor == T::zero() {
+                    continue;
+                }
+                for (v, p) in a[i][k..].iter_mut().zip(&pivot_row[k..]) {
+                    *v = *v - factor * *p;
+                }
+                for (v, p) in x[i].iter_mut().zip(&pivot_rhs) {
+                    *v = *v - factor * *p;
+                }
+            }
+        }
+        for (i, row) in x.iter_mut().enumerate() {
+            let pivot = a[i][i];
+            for v in row.iter_mut() {
+                *v = *v / pivot;
+            }
+        }
+        Ok(Matrix {
+            rows: n,
+            cols: b.cols,
+            mat: x,
+        })
+    }
+
+    /// Returns the determinant of a square matrix.
+    /// Exact elements such as `Rational` use fraction-free (Bareiss)
+    /// elimination, where every intermediate value is itself a minor of
+    /// the matrix, so the determinant is exact. Floating point elements
+    /// use Gaussian elimination with partial pivoting and the product
+    /// of the pivots.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// use ralgeb::rational::Rational;
+    /// let m = Matrix::new_mat(vec![vec![1., 2.], vec![3., 4.]]);
+    /// assert!((m.determinant().unwrap() + 2.).abs() < 1e-12);
+    ///
+    /// let q = |n, d| Rational::new(n, d);
+    /// let m = Matrix::new_mat(vec![vec![q(1, 2), q(1, 3)], vec![q(1, 4), q(1, 5)]]);
+    /// assert_eq!(m.determinant().unwrap().to_string(), "1/60");
+    /// ```
+    pub fn determinant(&self) -> Result<T, MatrixError> {
+        self.square_check()?;
+        if T::epsilon() > 0. {
+            return Ok(self.pivoted_determinant());
+        }
+        let n = self.rows;
+        let mut a = self.mat.clone();
+        let mut prev = T::one();
+        let mut negate = false;
+        for k in 0..n {
+            let p = (k..n)
+                .max_by(|&i, &j| a[i][k].magnitude().total_cmp(&a[j][k].magnitude()))
+                .unwrap();
+            if a[p][k] == T::zero() {
+                return Ok(T::zero());
+            }
+            if p != k {
+                a.swap(k, p);
+                negate = !negate;
+            }
+            let pivot_row = a[k].clone();
+            for row in a.iter_mut().skip(k + 1) {
+                let f = row[k];
+                for j in k + 1..n {
+                    row[j] = (pivot_row[k] * row[j] - f * pivot_row[j]) / prev;
+                }
+                row[k] = T::zero();
+            }
+            prev = pivot_row[k];
+        }
+        let det = if n == 0 { T::one() } else { a[n - 1][n - 1] };
+        Ok(if negate { -det } else { det })
+    }
+
+    /// Returns the reduced row echelon form of the matrix.
+    /// Exact elements such as `Rational` are reduced fraction-free (Bareiss),
+    /// so the result is exact. Floating point elements are reduced with
+    /// partial pivoting and values within rounding error of zero are
+    /// treated as zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// use ralgeb::rational::Rational;
+    /// let q = |n| Rational::from_integer(n);
+    /// let m = Matrix::new_mat(vec![
+    ///     vec![q(2), q(1), q(-1), q(8)],
+    ///     vec![q(-3), q(-1), q(2), q(-11)],
+    ///     vec![q(-2), q(1), q(2), q(-3)],
+    /// ]);
+    /// let r = m.rref();
+    /// assert_eq!(r.get_col(3), vec![q(2), q(3), q(-1)]);
+    /// ```
+    pub fn rref(&self) -> Matrix<T> {
+        let (mut reduced, pivots) = self.reduce(self.cols);
+        for (r, &c) in pivots.iter().enumerate() {
+            let pivot = reduced.mat[r][c];
+            for v in reduced.mat[r].iter_mut() {
+                *v = *v / pivot;
+            }
+        }
+        reduced
+    }
+
+    /// Returns the rank, the number of pivots in the reduced row echelon form
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., 2.], vec![2., 4.]]);
+    /// assert_eq!(m.rank(), 1);
+    /// ```
+    pub fn rank(&self) -> usize {
+        self.reduce(self.cols).1.len()
+    }
+
+    /// Raises a square matrix to an integer power using repeated squaring.
+    /// Negative powers are computed through the inverse and
+    /// the 0th power is the identity matrix.
+    ///
+    /// # Arguments
+    /// `n` - The exponent.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// // Transition matrix of a two state Markov chain
+    /// let p = Matrix::new_mat(vec![vec![0.9, 0.1], vec![0.5, 0.5]]);
+    /// let p2 = p.pow(2).unwrap();
+    /// assert!((p2.get_row(0).unwrap()[0] - 0.86).abs() < 1e-12);
+    /// ```
+    pub fn pow(&self, n: i32) -> Result<Matrix<T>, MatrixError> {
+        self.square_check()?;
+        let mut base = if n < 0 { self.inverse()? } else { self.clone() };
+        let mut e = n.unsigned_abs();
+        let mut result = Matrix::eye(self.rows, self.rows);
+        while e > 0 {
+            if e & 1 == 1 {
+                result = Matrix::multiply(&result, &base)?;
+            }
+            e >>= 1;
+            if e > 0 {
+                base = Matrix::multiply(&base, &base)?;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Returns the characteristic polynomial det(xI - A) of a square matrix,
+    /// computed with the Faddeev-LeVerrier algorithm.
+    /// The result is monic and exact for `Rational` matrices.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., 2.], vec![3., 4.]]);
+    /// let p = m.characteristic_polynomial().unwrap();
+    /// // x^2 - 5x - 2
+    /// assert_eq!(p.coefficients(), &[-2., -5., 1.]);
+    /// ```
+    pub fn characteristic_polynomial(&self) -> Result<Polynomial<T>, MatrixError> {
+        self.square_check()?;
+        let n = self.rows;
+        let mut coeffs = vec![T::zero(); n + 1];
+        coeffs[n] = T::one();
+        let mut m = Matrix::zeros(n, n);
+        let mut k_t = T::zero();
+        for k in 1..=n {
+            m = Matrix::multiply(self, &m)?;
+            for i in 0..n {
+                m.mat[i][i] = m.mat[i][i] + coeffs[n - k + 1];
+            }
+            k_t = k_t + T::one();
+            let am = Matrix::multiply(self, &m)?;
+            coeffs[n - k] = -am.trace()? / k_t;
+        }
+        Ok(Polynomial::new(coeffs))
+    }
+
+    /// Returns the minimal polynomial of a square matrix, the monic
+    /// polynomial of least degree that vanishes on the matrix.
+    /// Found as the first power of the matrix that is a linear
+    /// combination of the lower powers.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![2., 0., 0.], vec![0., 2., 0.], vec![0., 0., 3.]]);
+    /// let p = m.minimal_polynomial().unwrap();
+    /// // (x - 2)(x - 3)
+    /// assert_eq!(p.coefficients(), &[6., -5., 1.]);
+    /// ```
+    pub fn minimal_polynomial(&self) -> Result<Polynomial<T>, MatrixError> {
+        self.square_check()?;
+        let n = self.rows;
+        let mut powers = vec![Matrix::eye(n, n)];
+        for d in 1..=n {
+            powers.push(Matrix::multiply(&powers[d - 1], self)?);
+            let k = Matrix::from_fn(n * n, d + 1, |r, c| powers[c].mat[r / n][r % n]);
+            let (reduced, pivots) = k.fraction_free_reduce(d + 1);
+            if pivots.len() == d {
+                let mut coeffs: Vec<T> = (0..d)
+                    .map(|i| -(reduced.mat[i][d] / reduced.mat[i][i]))
+                    .collect();
+                coeffs.push(T::one());
+                return Ok(Polynomial::new(coeffs));
+            }
+        }
+        self.characteristic_polynomial()
+    }
+
+    /// Checks the Cayley-Hamilton theorem on the matrix, i.e. that it is a
+    /// root of its own characteristic polynomial. Every element of p(A) must
+    /// be negligible compared to the n-th power of the largest element of A.
+    ///
+    /// # Arguments
+    /// `tolerance` - Use `Tolerance::exact()` for `Rational`.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::approx::Tolerance;
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., 2.], vec![3., 4.]]);
+    /// assert!(m.satisfies_cayley_hamilton(Tolerance::default()).unwrap());
+    /// ```
+    pub fn satisfies_cayley_hamilton(&self, tolerance: Tolerance) -> Result<bool, MatrixError> {
+        let p = self.characteristic_polynomial()?;
+        let residual = p.evaluate_matrix(self)?;
+        let scale = self.max_magnitude().powi(self.rows as i32);
+        Ok(residual
+            .positions()
+            .all(|(r, c)| tolerance.negligible(residual.mat[r][c], scale)))
+    }
+
+    /// Returns the trace of a square matrix,
+    /// i.e the sum of the elements in the principal diagonal.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., 2.], vec![3., 4.]]);
+    /// assert_eq!(m.trace().unwrap(), 5.);
+    /// ```
+    pub fn trace(&self) -> Result<T, MatrixError> {
+        Ok(self
+            .get_principal()?
+            .into_iter()
+            .fold(T::zero(), |acc, x| acc + x))
+    }
+
+    /// Returns the element-wise (Hadamard) product of 2 matrices
+    /// of the same dimensions.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m1 = Matrix::new_mat(vec![vec![1., 2.], vec![3., 4.]]);
+    /// let m2 = Matrix::new_mat(vec![vec![2., 2.], vec![0., -1.]]);
+    /// let m = Matrix::hadamard(&m1, &m2).unwrap();
+    /// assert_eq!(m.get_row(1).unwrap(), vec![0., -4.]);
+    /// ```
+    pub fn hadamard(m1: &Matrix<T>, m2: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        Matrix::zip_map(m1, m2, |a, b| a * b)
+    }
+
+    /// Returns the element-wise quotient of 2 matrices
+    /// of the same dimensions.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m1 = Matrix::new_mat(vec![vec![1., 2.], vec![3., 4.]]);
+    /// let m2 = Matrix::new_mat(vec![vec![2., 2.], vec![3., -1.]]);
+    /// let m = Matrix::hadamard_div(&m1, &m2).unwrap();
+    /// assert_eq!(m.get_row(0).unwrap(), vec![0.5, 1.]);
+    /// ```
+    pub fn hadamard_div(m1: &Matrix<T>, m2: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        Matrix::zip_map(m1, m2, |a, b| a / b)
+    }
+
+    /// Returns a new matrix with `f` applied to every element.
+    ///
+    /// # Arguments
+    /// `f` - The function applied to each element.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., 4.], vec![9., 16.]]);
+    /// let r = m.map(f64::sqrt);
+    /// assert_eq!(r.get_row(1).unwrap(), vec![3., 4.]);
+    /// ```
+    pub fn map<F: Fn(T) -> T>(&self, f: F) -> Matrix<T> {
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            mat: self
+                .mat
+                .iter()
+                .map(|r| r.iter().map(|x| f(*x)).collect())
+                .collect(),
+        }
+    }
+
+    /// Applies `f` to every element of the matrix in place.
+    ///
+    /// # Arguments
+    /// `f` - The function applied to each element.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let mut m = Matrix::identity(2, 2).unwrap();
+    /// m.map_inplace(|x| x + 1.);
+    /// assert_eq!(m.get_row(0).unwrap(), vec![2., 1.]);
+    /// ```
+    pub fn map_inplace<F: FnMut(T) -> T>(&mut self, mut f: F) {
+        for v in self.mat.iter_mut().flat_map(|r| r.iter_mut()) {
+            *v = f(*v);
+        }
+    }
+
+    /// Combines 2 matrices of the same dimensions element by element.
+    ///
+    /// # Arguments
+    /// `m1` - 1st matrix
+    /// `m2` - 2nd matrix
+    /// `f` - Receives the elements of m1 and m2 at the same position.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m1 = Matrix::new_mat(vec![vec![1., 5.]]);
+    /// let m2 = Matrix::new_mat(vec![vec![3., 2.]]);
+    /// let m = Matrix::zip_map(&m1, &m2, f64::max).unwrap();
+    /// assert_eq!(m.get_row(0).unwrap(), vec![3., 5.]);
+    /// ```
+    pub fn zip_map<F: Fn(T, T) -> T>(
+        m1: &Matrix<T>,
+        m2: &Matrix<T>,
+        f: F,
+    ) -> Result<Matrix<T>, MatrixError> {
+        if m1.rows != m2.rows || m1.cols != m2.cols {
+            return Err(MatrixError::new(format!(
+                "The dimensions are different. Row Diff: {}, Col Diff: {}",
+                (m1.rows as isize - m2.rows as isize).abs(),
+                (m1.cols as isize - m2.cols as isize).abs()
+            )));
+        }
+        Ok(Matrix {
+            rows: m1.rows,
+            cols: m1.cols,
+            mat: m1
+                .mat
+                .iter()
+                .zip(m2.mat.iter())
+                .map(|(r1, r2)| r1.iter().zip(r2.iter()).map(|(a, b)| f(*a, *b)).collect())
+                .collect(),
+        })
+    }
+
+    /// Returns the sum of all the elements.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., 2.], vec![3., 4.]]);
+    /// assert_eq!(m.sum(), 10.);
+    /// ```
+    pub fn sum(&self) -> T {
+        self.mat
+            .iter()
+            .flat_map(|r| r.iter())
+            .fold(T::zero(), |acc, x| acc + *x)
+    }
+
+    /// Returns the product of all the elements.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., 2.], vec![3., 4.]]);
+    /// assert_eq!(m.product(), 24.);
+    /// ```
+    pub fn product(&self) -> T {
+        self.mat
+            .iter()
+            .flat_map(|r| r.iter())
+            .fold(T::one(), |acc, x| acc * *x)
+    }
+
+    /// Returns the sum of every row or of every column.
+    ///
+    /// # Arguments
+    /// `axis` - `Axis::Row` gives one value per row, `Axis::Col` one per column.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::{Axis, Matrix};
+    /// let m = Matrix::new_mat(vec![vec![1., 2.], vec![3., 4.]]);
+    /// assert_eq!(m.sum_axis(Axis::Row), vec![3., 7.]);
+    /// assert_eq!(m.sum_axis(Axis::Col), vec![4., 6.]);
+    /// ```
+    pub fn sum_axis(&self, axis: Axis) -> Vec<T> {
+        self.lanes(axis)
+            .map(|l| l.into_iter().fold(T::zero(), |acc, x| acc + x))
+            .collect()
+    }
+
+    /// Returns the product of every row or of every column.
+    ///
+    /// # Arguments
+    /// `axis` - `Axis::Row` gives one value per row, `Axis::Col` one per column.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::{Axis, Matrix};
+    /// let m = Matrix::new_mat(vec![vec![1., 2.], vec![3., 4.]]);
+    /// assert_eq!(m.product_axis(Axis::Col), vec![3., 8.]);
+    /// ```
+    pub fn product_axis(&self, axis: Axis) -> Vec<T> {
+        self.lanes(axis)
+            .map(|l| l.into_iter().fold(T::one(), |acc, x| acc * x))
+            .collect()
+    }
+
+    /// Returns whether the matrix is square and equal to its transpose
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::approx::Tolerance;
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., 2.], vec![2. + 1e-14, 1.]]);
+    /// assert!(m.is_symmetric(Tolerance::default()));
+    /// ```
+    pub fn is_symmetric(&self, tolerance: Tolerance) -> bool {
+        self.is_square()
+            && self
+                .positions()
+                .all(|(r, c)| tolerance.close(self.mat[r][c], self.mat[c][r]))
+    }
+
+    /// Returns whether the matrix is square and equal to the
+    /// negative of its transpose
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::approx::Tolerance;
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![0., 2.], vec![-2., 0.]]);
+    /// assert!(m.is_skew_symmetric(Tolerance::default()));
+    /// ```
+    pub fn is_skew_symmetric(&self, tolerance: Tolerance) -> bool {
+        self.is_square()
+            && self
+                .positions()
+                .all(|(r, c)| tolerance.close(self.mat[r][c], -self.mat[c][r]))
+    }
+
+    /// Returns whether every element off the principal diagonal is
+    /// negligible compared to the largest element
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::approx::Tolerance;
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., 1e-15], vec![0., 2.]]);
+    /// assert!(m.is_diagonal(Tolerance::default()));
+    /// ```
+    pub fn is_diagonal(&self, tolerance: Tolerance) -> bool {
+        self.negligible_where(tolerance, |r, c| r != c)
+    }
+
+    /// Returns whether every element below the principal diagonal is
+    /// negligible compared to the largest element
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::approx::Tolerance;
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., 2.], vec![0., 3.]]);
+    /// assert!(m.is_upper_triangular(Tolerance::default()));
+    /// assert!(!m.is_lower_triangular(Tolerance::default()));
+    /// ```
+    pub fn is_upper_triangular(&self, tolerance: Tolerance) -> bool {
+        self.negligible_where(tolerance, |r, c| r > c)
+    }
+
+    /// Returns whether every element above the principal diagonal is
+    /// negligible compared to the largest element
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::approx::Tolerance;
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., 0.], vec![2., 3.]]);
+    /// assert!(m.is_lower_triangular(Tolerance::default()));
+    /// ```
+    pub fn is_lower_triangular(&self, tolerance: Tolerance) -> bool {
+        self.negligible_where(tolerance, |r, c| r < c)
+    }
+
+    /// Returns whether the matrix is square and AᵀA is the identity matrix
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::approx::Tolerance;
+    /// use ralgeb::matrix::Matrix;
+    /// let (s, c) = (0.6_f64, 0.8_f64);
+    /// let m = Matrix::new_mat(vec![vec![c, -s], vec![s, c]]);
+    /// assert!(m.is_orthogonal(Tolerance::default()));
+    /// ```
+    pub fn is_orthogonal(&self, tolerance: Tolerance) -> bool
+    where
+        T: ApproxEq,
+    {
+        if !self.is_square() {
+            return false;
+        }
+        let product = Matrix::multiply(&Matrix::transpose(self.clone()), self).unwrap();
+        product.approx_eq(&Matrix::eye(self.rows, self.cols), tolerance)
+    }
+
+    /// Returns whether the matrix is square and A² = A
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::approx::Tolerance;
+    /// use ralgeb::matrix::Matrix;
+    /// // Projection onto the line y = x
+    /// let m = Matrix::new_mat(vec![vec![0.5, 0.5], vec![0.5, 0.5]]);
+    /// assert!(m.is_idempotent(Tolerance::default()));
+    /// ```
+    pub fn is_idempotent(&self, tolerance: Tolerance) -> bool
+    where
+        T: ApproxEq,
+    {
+        self.is_square()
+            && Matrix::multiply(self, self)
+                .unwrap()
+                .approx_eq(self, tolerance)
+    }
+
+    /// Returns whether the matrix is square and no pivot of Gaussian
+    /// elimination with partial pivoting is negligible compared
+    /// to the largest element
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::approx::Tolerance;
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., 2.], vec![1., 2. + 1e-13]]);
+    /// assert!(!m.is_invertible(Tolerance::default()));
+    /// assert!(m.is_invertible(Tolerance::exact()));
+    /// ```
+    pub fn is_invertible(&self, tolerance: Tolerance) -> bool {
+        if !self.is_square() {
+            return false;
+        }
+        let scale = self.max_magnitude();
+        let n = self.rows;
+        let mut a = self.mat.clone();
+        for k in 0..n {
+            let p = (k..n)
+                .max_by(|&i, &j| a[i][k].magnitude().total_cmp(&a[j][k].magnitude()))
+                .unwrap();
+            if a[p][k] == T::zero() || tolerance.negligible(a[p][k], scale) {
+                return false;
+            }
+            a.swap(k, p);
+            let pivot_row = a[k].clone();
+            for row in a.iter_mut().skip(k + 1) {
+                let f = row[k] / pivot_row[k];
+                for (v, p) in row.iter_mut().zip(&pivot_row).skip(k) {
+                    *v = *v - f * *p;
+                }
+            }
+        }
+        true
+    }
+
+    fn same_dimensions(m1: &Matrix<T>, m2: &Matrix<T>) -> Result<(), MatrixError> {
+        if m1.rows == m2.rows && m1.cols == m2.cols {
+            Ok(())
+        } else {
+            Err(MatrixError::new(format!(
+                "The dimensions are different. Row Diff: {}, Col Diff: {}",
+                (m1.rows as isize - m2.rows as isize).abs(),
+                (m1.cols as isize - m2.cols as isize).abs()
+            )))
+        }
+    }
+
+    fn output_check(&self, rows: usize, cols: usize) -> Result<(), MatrixError> {
+        if self.rows == rows && self.cols == cols {
+            Ok(())
+        } else {
+            Err(MatrixError::new(format!(
+                "The output matrix should be {}x{} but is {}x{}",
+                rows, cols, self.rows, self.cols
+            )))
+        }
+    }
+
+    fn zip_rows_into<F: Fn(T, T) -> T>(m1: &Matrix<T>, m2: &Matrix<T>, out: &mut Matrix<T>, f: F) {
+        for ((a, b), o) in m1.mat.iter().zip(m2.mat.iter()).zip(out.mat.iter_mut()) {
+            for ((a, b), o) in a.iter().zip(b).zip(o.iter_mut()) {
+                *o = f(*a, *b);
+            }
+        }
+    }
+
+    fn square_check(&self) -> Result<(), MatrixError> {
+        if self.is_square() {
+            Ok(())
+        } else {
+            Err(MatrixError::new(
+                "The matrix is not a square matrix".to_string(),
+            ))
+        }
+    }
+
+    fn negligible_where<F: Fn(usize, usize) -> bool>(
+        &self,
+        tolerance: Tolerance,
+        select: F,
+    ) -> bool {
+        let scale = self.max_magnitude();
+        self.positions()
+            .filter(|&(r, c)| select(r, c))
+            .all(|(r, c)| tolerance.negligible(self.mat[r][c], scale))
+    }
+
+    fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |r| (0..cols).map(move |c| (r, c)))
+    }
+
+    fn lanes(&self, axis: Axis) -> Box<dyn Iterator<Item = Vec<T>> + '_> {
+        match axis {
+            Axis::Row => Box::new(self.mat.iter().cloned()),
+            Axis::Col => Box::new((0..self.cols).map(move |c| self.get_col(c))),
+        }
+    }
+
+    /// Gauss-Jordan elimination. Only the first `pivot_cols` columns may hold
+    /// pivots. Returns the reduced matrix, where every pivot row still has to
+    /// be divided by its pivot, and the pivot columns.
+    /// Bareiss keeps exact elements small, but with floating point elements
+    /// its intermediate minors overflow or underflow, so those are pivoted.
+    fn reduce(&self, pivot_cols: usize) -> (Matrix<T>, Vec<usize>) {
+        if T::epsilon() == 0. {
+            self.fraction_free_reduce(pivot_cols)
+        } else {
+            self.pivoted_reduce(pivot_cols)
+        }
+    }
+
+    /// Gauss-Jordan elimination with partial pivoting, where a pivot within
+    /// rounding error of the largest element is treated as zero
+    fn pivoted_reduce(&self, pivot_cols: usize) -> (Matrix<T>, Vec<usize>) {
+        let mut a = self.mat.clone();
+        let mut pivots = Vec::new();
+        let scale = a
+            .iter()
+            .flat_map(|row| row[..pivot_cols].iter())
+            .fold(0., |m: f64, x| m.max(x.magnitude()));
+        let tolerance = T::epsilon() * self.rows.max(pivot_cols) as f64 * scale;
+        for c in 0..pivot_cols {
+            let r = pivots.len();
+            if r == self.rows {
+                break;
+            }
+            let p = (r..self.rows)
+                .max_by(|&i, &j| a[i][c].magnitude().total_cmp(&a[j][c].magnitude()))
+                .unwrap();
+            if a[p][c].magnitude() <= tolerance {
+                for row in a.iter_mut().skip(r) {
+                    row[c] = T::zero();
+                }
+                continue;
+            }
+            a.swap(r, p);
+            let pivot_row = a[r].clone();
+            for (i, row) in a.iter_mut().enumerate() {
+                if i == r {
+                    continue;
+                }
+                let f = row[c] / pivot_row[c];
+                if f == T::zero() {
+                    continue;
+                }
+                for (v, p) in row.iter_mut().zip(&pivot_row).skip(c + 1) {
+                    *v = *v - f * *p;
+                }
+                row[c] = T::zero();
+            }
+            pivots.push(c);
+        }
+        (
+            Matrix {
+                rows: self.rows,
+                cols: self.cols,
+                mat: a,
+            },
+            pivots,
+        )
+    }
+
+    /// Determinant of a square matrix as the signed product of the pivots
+    /// of Gaussian elimination with partial pivoting. The product is taken
+    /// by multiplying a large running product with the smallest remaining
+    /// pivot and a small one with the largest, so it only overflows or
+    /// underflows when the determinant itself does.
+    fn pivoted_determinant(&self) -> T {
+        let n = self.rows;
+        let mut a = self.mat.clone();
+        let mut pivots = Vec::with_capacity(n);
+        let mut negate = false;
+        for k in 0..n {
+            let p = (k..n)
+                .max_by(|&i, &j| a[i][k].magnitude().total_cmp(&a[j][k].magnitude()))
+                .unwrap();
+            if a[p][k] == T::zero() {
+                return T::zero();
+            }
+            if p != k {
+                a.swap(k, p);
+                negate = !negate;
+            }
+            let pivot_row = a[k].clone();
+            for row in a.iter_mut().skip(k + 1) {
+                let f = row[k] / pivot_row[k];
+                for (v, p) in row.iter_mut().zip(&pivot_row).skip(k + 1) {
+                    *v = *v - f * *p;
+                }
+                row[k] = T::zero();
+            }
+            pivots.push(pivot_row[k]);
+        }
+        pivots.sort_by(|x, y| x.magnitude().total_cmp(&y.magnitude()));
+        let mut det = T::one();
+        let (mut small, mut large) = (0, pivots.len());
+        while small < large {
+            if det.magnitude() >= 1. {
+                det = det * pivots[small];
+                small += 1;
+            } else {
+                large -= 1;
+                det = det * pivots[large];
+            }
+        }
+        if negate {
+            -det
+        } else {
+            det
+        }
+    }
+
+    /// Fraction-free (Bareiss) Gauss-Jordan elimination, where every
+    /// intermediate value is a minor of the matrix
+    fn fraction_free_reduce(&self, pivot_cols: usize) -> (Matrix<T>, Vec<usize>) {
+        let mut a = self.mat.clone();
+        let mut pivots = Vec::new();
+        let mut prev = T::one();
+        for c in 0..pivot_cols {
+            let r = pivots.len();
+            if r == self.rows {
+                break;
+            }
+            let scale = a
+                .iter()
+                .flat_map(|row| row[..pivot_cols].iter())
+                .fold(0., |m: f64, x| m.max(x.magnitude()));
+            let tolerance = T::epsilon() * self.rows.max(pivot_cols) as f64 * scale;
+            let p = (r..self.rows)
+                .max_by(|&i, &j| a[i][c].magnitude().total_cmp(&a[j][c].magnitude()))
+                .unwrap();
+            if a[p][c] == T::zero() || a[p][c].magnitude() <= tolerance {
+                for row in a.iter_mut().skip(r) {
+                    row[c] = T::zero();
+                }
+                continue;
+            }
+            a.swap(r, p);
+            let pivot_row = a[r].clone();
+            for (i, row) in a.iter_mut().enumerate() {
+                if i == r {
+                    continue;
+                }
+                let f = row[c];
+                for (v, p) in row.iter_mut().zip(&pivot_row) {
+                    *v = (pivot_row[c] * *v - f * *p) / prev;
+                }
+                row[c] = T::zero();
+            }
+            prev = pivot_row[c];
+            pivots.push(c);
+        }
+        (
+            Matrix {
+                rows: self.rows,
+                cols: self.cols,
+                mat: a,
+            },
+            pivots,
+        )
+    }
+
+    fn max_magnitude(&self) -> f64 {
+        self.mat
+            .iter()
+            .flat_map(|r| r.iter())
+            .fold(0., |m: f64, x| m.max(x.magnitude()))
+    }
+}
+
+impl Matrix {
+    /// Returns a matrix with all 0 values
+    ///
+    /// # Arguments
+    /// `rows` - The number of rows in a matrix.
+    /// `cols` - The number of columns in a matrix.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new(3, 4);
+    /// ```
+    pub fn new(rows: usize, cols: usize) -> Matrix {
+        let mut r = 0;
+        let mut outer_vec: Vec<Vec<f64>> = Vec::new();
+        loop {
+            let inner_vec: Vec<f64> = vec![0.; cols];
+            if r >= rows {
+                break Matrix {
+                    rows: outer_vec.len(),
+                    cols: inner_vec.len(),
+                    mat: outer_vec,
+                };
+            }
+            outer_vec.push(inner_vec);
+            r += 1;
+        }
+    }
+
+    ///
+    /// Returns an identity-like matrix with 1.0 on the
+    /// principal diagonal and 0.0 elsewhere.
+    /// For rectangular dimensions the diagonal stops at
+    /// the smaller of rows and cols.
+    /// Always returns Some; the Option is kept for compatibility.
+    ///
+    /// # Arguments
+    /// `rows` - The number of rows in a matrix.
+    /// `cols` - The number of columns in a matrix.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = match Matrix::identity(3, 3) {
+    /// Some(m) => m,
+    /// None => Matrix::new(3,3),
+    /// };
+    /// let r = Matrix::identity(2, 3).unwrap();
+    /// assert_eq!(r.get_row(1).unwrap(), vec![0., 1., 0.]);
+    /// ```
+    ///
+    pub fn identity(rows: usize, cols: usize) -> Option<Matrix> {
+        Some(Matrix::from_fn(
+            rows,
+            cols,
+            |r, c| if r == c { 1. } else { 0. },
+        ))
+    }
+
+    /// Returns a matrix with all 1 values
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::ones(2, 2);
+    /// assert_eq!(m.get_row(0).unwrap(), vec![1., 1.]);
+    /// ```
+    pub fn ones(rows: usize, cols: usize) -> Matrix {
+        Matrix::filled(rows, cols, 1.)
+    }
+
+    /// Returns the matrix exponential e^A of a square matrix.
+    /// Uses scaling and squaring with a [6/6] Padé approximant.
+    /// Returns an error if any entry is infinite or NaN.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let a = Matrix::new_mat(vec![vec![0., 1.], vec![0., 0.]]);
+    /// let e = a.expm().unwrap();
+    /// assert!((e.get_row(0).unwrap()[1] - 1.).abs() < 1e-12);
+    /// ```
+    pub fn expm(&self) -> Result<Matrix, MatrixError> {
+        self.square_check()?;
+        let n = self.rows;
+        if self.mat.iter().flatten().any(|x| !x.is_finite()) {
+            return Err(MatrixError::new(String::from(
+                "The matrix should only contain finite entries",
+            )));
+        }
+        let norm = self.norm_one();
+        let squarings = if norm > 0.5 {
+            (norm / 0.5).log2().ceil() as i32
+        } else {
+            0
+        };
+        let x = self.scale(0.5_f64.powi(squarings));
+
+        let q = 6;
+        let mut c = 1.;
+        let mut numer = Matrix::identity(n, n).unwrap();
+        let mut denom = Matrix::identity(n, n).unwrap();
+        let mut x_pow = Matrix::identity(n, n).unwrap();
+        for k in 1..=q {
+            c *= (q - k + 1) as f64 / (k * (2 * q - k + 1)) as f64;
+            x_pow = Matrix::multiply(&x_pow, &x)?;
+            numer = Matrix::add(&numer, &x_pow.scale(c))?;
+            let sign = if k % 2 == 0 { 1. } else { -1. };
+            denom = Matrix::add(&denom, &x_pow.scale(sign * c))?;
+        }
+        let mut result = denom.solve(&numer)?;
+        for _ in 0..squarings {
+            result = Matrix::multiply(&result, &result)?;
+        }
+        Ok(result)
+    }
+
+    /// Returns the principal square root of a square matrix.
+    /// Uses the Denman-Beavers iteration, which requires the matrix
+    /// to be non-singular with no eigenvalues on the negative real axis.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let a = Matrix::new_mat(vec![vec![4., 0.], vec![0., 9.]]);
+    /// let s = a.sqrtm().unwrap();
+    /// assert!((s.get_row(1).unwrap()[1] - 3.).abs() < 1e-12);
+    /// ```
+    pub fn sqrtm(&self) -> Result<Matrix, MatrixError> {
+        self.square_check()?;
+        let mut y = self.clone();
+        let mut z = Matrix::identity(self.rows, self.rows).unwrap();
+        for _ in 0..MAX_ITERATIONS {
+            let y_inv = y.inverse()?;
+            let z_inv = z.inverse()?;
+            let y_next = Matrix::add(&y, &z_inv)?.scale(0.5);
+            z = Matrix::add(&z, &y_inv)?.scale(0.5);
+            let diff = Matrix::subtract(&y_next, &y)?.norm_one();
+            y = y_next;
+            if diff <= ITERATION_TOLERANCE * y.norm_one() {
+                return Ok(y);
+            }
+        }
+        Err(MatrixError::new(
+            "The square root iteration did not converge".to_string(),
+        ))
+    }
+
+    /// Returns the principal logarithm of a square matrix.
+    /// Uses inverse scaling and squaring: square roots are taken until the
+    /// matrix is close to the identity, then an atanh series is summed.
+    /// The matrix must have no eigenvalues on the closed negative real axis.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let a = Matrix::new_mat(vec![vec![std::f64::consts::E, 0.], vec![0., 1.]]);
+    /// let l = a.logm().unwrap();
+    /// assert!((l.get_row(0).unwrap()[0] - 1.).abs() < 1e-12);
+    /// ```
+    pub fn logm(&self) -> Result<Matrix, MatrixError> {
+        self.square_check()?;
+        let n = self.rows;
+        let id = Matrix::identity(n, n).unwrap();
+        let mut a = self.clone();
+        let mut roots = 0;
+        while Matrix::subtract(&a, &id)?.norm_one() > 0.25 {
+            if roots >= MAX_ITERATIONS {
+                return Err(MatrixError::new(
+                    "The logarithm could not be computed".to_string(),
+                ));
+            }
+            a = a.sqrtm()?;
+            roots += 1;
+        }
+        // log(A) = 2 atanh(Z) with Z = (A - I)(A + I)^-1
+        let z = Matrix::add(&a, &id)?.solve(&Matrix::subtract(&a, &id)?)?;
+        let z2 = Matrix::multiply(&z, &z)?;
+        let mut sum = z.clone();
+        let mut term = z;
+        for k in 1..MAX_ITERATIONS {
+            term = Matrix::multiply(&term, &z2)?;
+            let t = term.scale(1. / (2 * k + 1) as f64);
+            sum = Matrix::add(&sum, &t)?;
+            if t.norm_one() <= f64::EPSILON * sum.norm_one() {
+                break;
+            }
+        }
+        Ok(sum.scale(2. * 2_f64.powi(roots as i32)))
+    }
+
+    /// Returns the Frobenius norm, the square root of
+    /// the sum of squares of all the elements.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., 2.], vec![2., 4.]]);
+    /// assert_eq!(m.norm_frobenius(), 5.);
+    /// ```
+    pub fn norm_frobenius(&self) -> f64 {
+        self.mat
+            .iter()
+            .flat_map(|r| r.iter())
+            .map(|x| x * x)
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    /// Returns the 1-norm, the maximum absolute column sum.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., -2.], vec![3., 4.]]);
+    /// assert_eq!(m.norm_one(), 6.);
+    /// ```
+    pub fn norm_one(&self) -> f64 {
+        (0..self.cols)
+            .map(|c| self.mat.iter().map(|r| r[c].abs()).sum::<f64>())
+            .fold(0., f64::max)
+    }
+
+    /// Returns the ∞-norm, the maximum absolute row sum.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., -2.], vec![3., 4.]]);
+    /// assert_eq!(m.norm_inf(), 7.);
+    /// ```
+    pub fn norm_inf(&self) -> f64 {
+        self.mat
+            .iter()
+            .map(|r| r.iter().map(|x| x.abs()).sum::<f64>())
+            .fold(0., f64::max)
+    }
+
+    /// Returns the spectral norm (2-norm), the largest singular value.
+    /// It is computed from the eigenvalues of AᵀA.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![3., 0.], vec![0., -5.]]);
+    /// assert!((m.norm_spectral() - 5.).abs() < 1e-12);
+    /// ```
+    pub fn norm_spectral(&self) -> f64 {
+        let at = Matrix::transpose(self.clone());
+        let ata = Matrix::multiply(&at, self).unwrap();
+        ata.jacobi_eigenvalues()
+            .into_iter()
+            .fold(0., f64::max)
+            .sqrt()
+    }
+
+    /// Returns the eigenvalues and eigenvectors of a symmetric matrix,
+    /// computed with Jacobi rotations.
+    /// The eigenvalues are sorted in decreasing order and the columns
+    /// of the returned matrix are the matching orthonormal eigenvectors.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![2., 1.], vec![1., 2.]]);
+    /// let (values, vectors) = m.symmetric_eigen().unwrap();
+    /// assert!((values[0] - 3.).abs() < 1e-12 && (values[1] - 1.).abs() < 1e-12);
+    /// let v = vectors.get_col(0);
+    /// assert!((v[0].abs() - v[1].abs()).abs() < 1e-12);
+    /// ```
+    pub fn symmetric_eigen(&self) -> Result<(Vec<f64>, Matrix), MatrixError> {
+        if !self.is_symmetric(Tolerance::default()) {
+            return Err(MatrixError::new(
+                "The matrix is not a symmetric matrix".to_string(),
+            ));
+        }
+        let (values, vectors) = self.jacobi_eigen();
+        let mut order: Vec<usize> = (0..self.rows).collect();
+        order.sort_by(|&i, &j| values[j].total_cmp(&values[i]));
+        Ok((
+            order.iter().map(|&i| values[i]).collect(),
+            Matrix::from_fn(self.rows, self.rows, |r, c| vectors[r][order[c]]),
+        ))
+    }
+
+    /// Reduces a square matrix to upper Hessenberg form with
+    /// Householder reflections.
+    /// Returns (Q, H) where Q is orthogonal, H is zero below the
+    /// first sub-diagonal and A = Q H Qᵀ.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::from_fn(4, 4, |r, c| (r * 4 + c) as f64 + if r == c { 5. } else { 0. });
+    /// let (q, h) = m.hessenberg().unwrap();
+    /// assert_eq!(h[(3, 0)], 0.);
+    /// let qhqt = Matrix::multiply(&Matrix::multiply(&q, &h).unwrap(), &Matrix::transpose(q)).unwrap();
+    /// assert!(Matrix::subtract(&qhqt, &m).unwrap().max_abs() < 1e-12);
+    /// ```
+    pub fn hessenberg(&self) -> Result<(Matrix, Matrix), MatrixError> {
+        self.square_check()?;
+        let n = self.rows;
+        let mut h = self.mat.clone();
+        let mut q = Matrix::eye(n, n).mat;
+        for k in 0..n.saturating_sub(2) {
+            let x: Vec<f64> = (k + 1..n).map(|r| h[r][k]).collect();
+            if let Some((v, beta)) = householder_vector(&x) {
+                reflect_rows(&mut h, &v, beta, k + 1, k..n);
+                reflect_cols(&mut h, &v, beta, k + 1, 0..n);
+                reflect_cols(&mut q, &v, beta, k + 1, 0..n);
+            }
+            for row in h.iter_mut().skip(k + 2) {
+                row[k] = 0.;
+            }
+        }
+        Ok((
+            Matrix::from_fn(n, n, |r, c| q[r][c]),
+            Matrix::from_fn(n, n, |r, c| h[r][c]),
+        ))
+    }
+
+    /// Reduces a symmetric matrix to symmetric tridiagonal form.
+    /// Returns (Q, T) where Q is orthogonal and A = Q T Qᵀ.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![4., 1., 2.], vec![1., 3., 0.], vec![2., 0., 1.]]);
+    /// let (q, t) = m.tridiagonalize().unwrap();
+    /// let qtqt = Matrix::multiply(&Matrix::multiply(&q, &t.to_matrix()).unwrap(), &Matrix::transpose(q)).unwrap();
+    /// assert!(Matrix::subtract(&qtqt, &m).unwrap().max_abs() < 1e-12);
+    /// assert_eq!(t.get(0, 1), t.get(1, 0));
+    /// ```
+    pub fn tridiagonalize(&self) -> Result<(Matrix, Tridiagonal), MatrixError> {
+        if !self.is_symmetric(Tolerance::default()) {
+            return Err(MatrixError::new(
+                "The matrix is not a symmetric matrix".to_string(),
+            ));
+        }
+        let (q, h) = self.hessenberg()?;
+        let n = self.rows;
+        let off: Vec<f64> = (1..n).map(|i| h[(i, i - 1)]).collect();
+        let t = Tridiagonal::new(off.clone(), (0..n).map(|i| h[(i, i)]).collect(), off)?;
+        Ok((q, t))
+    }
+
+    /// Returns the real Schur decomposition of a square matrix,
+    /// computed with the Francis double shift QR algorithm.
+    /// Returns (Q, T) where Q is orthogonal and A = Q T Qᵀ.
+    /// T is quasi upper triangular: real eigenvalues appear on its
+    /// diagonal and each complex conjugate pair as a 2x2 diagonal block.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// // A rotation by 90 degrees has eigenvalues ±i
+    /// let m = Matrix::new_mat(vec![vec![0., -1., 0.], vec![1., 0., 0.], vec![0., 0., 2.]]);
+    /// let (q, t) = m.schur().unwrap();
+    /// let qtqt = Matrix::multiply(&Matrix::multiply(&q, &t).unwrap(), &Matrix::transpose(q)).unwrap();
+    /// assert!(Matrix::subtract(&qtqt, &m).unwrap().max_abs() < 1e-12);
+    /// assert!((t.trace().unwrap() - 2.).abs() < 1e-12);
+    /// ```
+    pub fn schur(&self) -> Result<(Matrix, Matrix), MatrixError> {
+        let (q, h) = self.hessenberg()?;
+        let n = self.rows;
+        let mut q = q.mat;
+        let mut h = h.mat;
+        let mut hi = n;
+        let mut iterations = 0;
+        // The active block is rows and columns lo..hi
+        while hi > 1 {
+            let mut lo = hi - 1;
+            while lo > 0 {
+                let scale = h[lo - 1][lo - 1].abs() + h[lo][lo].abs();
+                if h[lo][lo - 1].abs() <= f64::EPSILON * scale {
+                    h[lo][lo - 1] = 0.;
+                    break;
+                }
+                lo -= 1;
+            }
+            if lo == hi - 1 {
+                hi -= 1;
+                iterations = 0;
+                continue;
+            }
+            if lo == hi - 2 {
+                split_real_block(&mut h, &mut q, lo);
+                hi -= 2;
+                iterations = 0;
+                continue;
+            }
+            iterations += 1;
+            if iterations > MAX_ITERATIONS {
+                return Err(MatrixError::new(
+                    "The Schur decomposition did not converge".to_string(),
+                ));
+            }
+            francis_step(&mut h, &mut q, lo, hi, iterations);
+        }
+        for (r, row) in h.iter_mut().enumerate() {
+            for v in row.iter_mut().take(r.saturating_sub(1)) {
+                *v = 0.;
+            }
+        }
+        Ok((
+            Matrix::from_fn(n, n, |r, c| q[r][c]),
+            Matrix::from_fn(n, n, |r, c| h[r][c]),
+        ))
+    }
+
+    /// Solves the Sylvester equation AX + XB = C for X with the
+    /// Bartels-Stewart algorithm, where A is this n x n matrix,
+    /// B is m x m and C is n x m.
+    /// The solution is unique unless A and -B share an eigenvalue.
+    ///
+    /// # Arguments
+    /// `b` - The m x m matrix B
+    /// `c` - The n x m right hand side C
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let a = Matrix::new_mat(vec![vec![1., 2.], vec![0., 3.]]);
+    /// let b = Matrix::new_mat(vec![vec![4.]]);
+    /// let c = Matrix::new_mat(vec![vec![7.], vec![14.]]);
+    /// let x = a.solve_sylvester(&b, &c).unwrap();
+    /// assert!((x[(0, 0)] - 0.6).abs() < 1e-12 && (x[(1, 0)] - 2.).abs() < 1e-12);
+    /// ```
+    pub fn solve_sylvester(&self, b: &Matrix, c: &Matrix) -> Result<Matrix, MatrixError> {
+        self.square_check()?;
+        b.square_check()?;
+        if c.rows != self.rows || c.cols != b.rows {
+            return Err(MatrixError::new(format!(
+                "The right hand side should be {}x{} but is {}x{}",
+                self.rows, b.rows, c.rows, c.cols
+            )));
+        }
+        if self.rows == 0 || b.rows == 0 {
+            return Ok(Matrix::new(self.rows, b.rows));
+        }
+        let (u, r) = self.schur()?;
+        let (v, s) = b.schur()?;
+        // Transform to R Y + Y S = F with F = Uᵀ C V and X = U Y Vᵀ
+        let ut = Matrix::transpose(u.clone());
+        let f = Matrix::multiply(&Matrix::multiply(&ut, c)?, &v)?;
+        let mut y = Matrix::new(self.rows, b.rows);
+        let row_blocks = quasi_triangular_blocks(&r);
+        // S is quasi upper triangular, so block column j of Y only
+        // depends on the block columns to its left, and within a block
+        // column block row i only depends on the block rows below it.
+        for &(j, q) in quasi_triangular_blocks(&s).iter() {
+            for &(i, p) in row_blocks.iter().rev() {
+                let rhs: Vec<f64> = (0..q)
+                    .flat_map(|jj| (0..p).map(move |ii| (i + ii, j + jj)))
+                    .map(|(ri, cj)| {
+                        let below: f64 = (i + p..self.rows).map(|k| r[(ri, k)] * y[(k, cj)]).sum();
+                        let left: f64 = (0..j).map(|k| y[(ri, k)] * s[(k, cj)]).sum();
+                        f[(ri, cj)] - below - left
+                    })
+                    .collect();
+                // vec(R_ii Y_ij + Y_ij S_jj) = (I ⊗ R_ii + S_jjᵀ ⊗ I) vec(Y_ij)
+                let k = Matrix::from_fn(p * q, p * q, |row, col| {
+                    let (ri, rj) = (row % p, row / p);
+                    let (ci, cj) = (col % p, col / p);
+                    let mut v = 0.;
+                    if rj == cj {
+                        v += r[(i + ri, i + ci)];
+                    }
+                    if ri == ci {
+                        v += s[(j + cj, j + rj)];
+                    }
+                    v
+                });
+                let block = k.solve(&Matrix::from_cols(vec![rhs])?).map_err(|_| {
+                    MatrixError::new(
+                        "The equation has no unique solution: A and -B share an eigenvalue"
+                            .to_string(),
+                    )
+                })?;
+                for jj in 0..q {
+                    for ii in 0..p {
+                        y[(i + ii, j + jj)] = block[(jj * p + ii, 0)];
+                    }
+                }
+            }
+        }
+        Matrix::multiply(&Matrix::multiply(&u, &y)?, &Matrix::transpose(v))
+    }
+
+    /// Solves the continuous Lyapunov equation AX + XAᵀ + Q = 0 for X,
+    /// where A is this matrix. For a stable A and positive semi-definite
+    /// Q the solution is the symmetric positive semi-definite Gramian.
+    /// Fails if Q is not symmetric.
+    ///
+    /// # Arguments
+    /// `q` - The n x n symmetric matrix Q
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let a = Matrix::new_mat(vec![vec![-1., 0.], vec![0., -2.]]);
+    /// let x = a.solve_lyapunov(&Matrix::identity(2, 2).unwrap()).unwrap();
+    /// assert!((x[(0, 0)] - 0.5).abs() < 1e-12 && (x[(1, 1)] - 0.25).abs() < 1e-12);
+    /// ```
+    pub fn solve_lyapunov(&self, q: &Matrix) -> Result<Matrix, MatrixError> {
+        symmetric_check(q)?;
+        let at = Matrix::transpose(self.clone());
+        let x = self.solve_sylvester(&at, &q.map(|v| -v))?;
+        Ok(symmetric_part(&x))
+    }
+
+    /// Solves the discrete Lyapunov (Stein) equation AXAᵀ - X + Q = 0
+    /// for X, where A is this matrix.
+    /// It is mapped to a continuous equation with the Cayley transform
+    /// (A + I)⁻¹(A - I), so A must not have -1 as an eigenvalue.
+    /// Fails if Q is not symmetric.
+    ///
+    /// # Arguments
+    /// `q` - The n x n symmetric matrix Q
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let a = Matrix::new_mat(vec![vec![0.5, 0.], vec![0., 0.]]);
+    /// let x = a.solve_discrete_lyapunov(&Matrix::identity(2, 2).unwrap()).unwrap();
+    /// assert!((x[(0, 0)] - 4. / 3.).abs() < 1e-12 && (x[(1, 1)] - 1.).abs() < 1e-12);
+    /// ```
+    pub fn solve_discrete_lyapunov(&self, q: &Matrix) -> Result<Matrix, MatrixError> {
+        self.square_check()?;
+        symmetric_check(q)?;
+        let n = self.rows;
+        let mut plus = self.clone();
+        let mut minus = self.clone();
+        for i in 0..n {
+            plus[(i, i)] += 1.;
+            minus[(i, i)] -= 1.;
+        }
+        let plus_inv = plus
+            .inverse()
+            .map_err(|_| MatrixError::new("The matrix has -1 as an eigenvalue".to_string()))?;
+        let ac = Matrix::multiply(&plus_inv, &minus)?;
+        let qc = Matrix::multiply(
+            &Matrix::multiply(&plus_inv, q)?,
+            &Matrix::transpose(plus_inv.clone()),
+        )?;
+        ac.solve_lyapunov(&symmetric_part(&qc.map(|v| 2. * v)))
+    }
+
+    /// Returns the largest absolute value among all the elements.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., -7.], vec![3., 4.]]);
+    /// assert_eq!(m.max_abs(), 7.);
+    /// ```
+    pub fn max_abs(&self) -> f64 {
+        self.mat
+            .iter()
+            .flat_map(|r| r.iter())
+            .fold(0., |m: f64, x| m.max(x.abs()))
+    }
+
+    /// Returns whether the matrix is symmetric and positive definite.
+    /// A Cholesky factorization is attempted and every pivot must be
+    /// positive and not negligible compared to the largest element.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::approx::Tolerance;
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![2., -1.], vec![-1., 2.]]);
+    /// assert!(m.is_positive_definite(Tolerance::default()));
+    /// let m = Matrix::new_mat(vec![vec![1., 2.], vec![2., 1.]]);
+    /// assert!(!m.is_positive_definite(Tolerance::default()));
+    /// ```
+    pub fn is_positive_definite(&self, tolerance: Tolerance) -> bool {
+        if !self.is_symmetric(tolerance) {
+            return false;
+        }
+        let scale = self.max_abs();
+        let n = self.rows;
+        let mut l = vec![vec![0.; n]; n];
+        for i in 0..n {
+            for j in 0..=i {
+                let s: f64 = (0..j).map(|k| l[i][k] * l[j][k]).sum();
+                let v = self.mat[i][j] - s;
+                if i == j {
+                    if v <= 0. || tolerance.negligible(v, scale) {
+                        return false;
+                    }
+                    l[i][i] = v.sqrt();
+                } else {
+                    l[i][j] = v / l[j][j];
+                }
+            }
+        }
+        true
+    }
+
+    /// Returns an estimate of the 1-norm condition number ‖A‖₁‖A⁻¹‖₁.
+    /// Uses Hager's estimator on an LU factorization so that the inverse
+    /// is never formed. The estimate is a lower bound that is usually exact
+    /// or within a small factor. Singular matrices give infinity.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., 0.], vec![0., 1e-10]]);
+    /// assert!(m.condition_estimate().unwrap() > 1e9);
+    /// ```
+    pub fn condition_estimate(&self) -> Result<f64, MatrixError> {
+        self.square_check()?;
+        let n = self.rows;
+        if n == 0 {
+            return Ok(0.);
+        }
+        let (lu, perm) = match self.lu_factor() {
+            Some(f) => f,
+            None => return Ok(f64::INFINITY),
+        };
+        let norm1 = |v: &[f64]| v.iter().map(|x| x.abs()).sum::<f64>();
+
+        let mut x = vec![1. / n as f64; n];
+        let mut estimate = 0.;
+        let mut last_index = None;
+        for _ in 0..5 {
+            let y = lu_solve(&lu, &perm, &x, false);
+            estimate = norm1(&y);
+            let signs: Vec<f64> = y.iter().map(|v| if *v >= 0. { 1. } else { -1. }).collect();
+            let z = lu_solve(&lu, &perm, &signs, true);
+            let (j, z_max) = z
+                .iter()
+                .map(|v| v.abs())
+                .enumerate()
+                .fold((0, 0.), |m, (i, v)| if v > m.1 { (i, v) } else { m });
+            if z_max <= Matrix::dot_product(&z, &x) || last_index == Some(j) {
+                break;
+            }
+            x = vec![0.; n];
+            x[j] = 1.;
+            last_index = Some(j);
+        }
+        // Higham's alternating vector guards against unlucky cancellation
+        if n > 1 {
+            let alt: Vec<f64> = (0..n)
+                .map(|i| {
+                    let sign = if i % 2 == 0 { 1. } else { -1. };
+                    sign * (1. + i as f64 / (n - 1) as f64)
+                })
+                .collect();
+            let y = lu_solve(&lu, &perm, &alt, false);
+            estimate = f64::max(estimate, 2. * norm1(&y) / (3. * n as f64));
+        }
+        Ok(estimate * self.norm_one())
+    }
+
+    /// Returns the mean of all the elements,
+    /// or None if the matrix has no elements.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., 2.], vec![3., 4.]]);
+    /// assert_eq!(m.mean(), Some(2.5));
+    /// ```
+    pub fn mean(&self) -> Option<f64> {
+        let n = self.rows * self.cols;
+        if n == 0 {
+            None
+        } else {
+            Some(self.sum() / n as f64)
+        }
+    }
+
+    /// Returns the smallest element,
+    /// or None if the matrix has no elements.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., -2.], vec![3., 4.]]);
+    /// assert_eq!(m.min(), Some(-2.));
+    /// ```
+    pub fn min(&self) -> Option<f64> {
+        self.argmin().map(|(r, c)| self.mat[r][c])
+    }
+
+    /// Returns the largest element,
+    /// or None if the matrix has no elements.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., -2.], vec![3., 4.]]);
+    /// assert_eq!(m.max(), Some(4.));
+    /// ```
+    pub fn max(&self) -> Option<f64> {
+        self.argmax().map(|(r, c)| self.mat[r][c])
+    }
+
+    /// Returns the (row, col) position of the smallest element.
+    /// The first position is returned on ties.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., -2.], vec![3., 4.]]);
+    /// assert_eq!(m.argmin(), Some((0, 1)));
+    /// ```
+    pub fn argmin(&self) -> Option<(usize, usize)> {
+        self.positions()
+            .reduce(|a, b| if self[b] < self[a] { b } else { a })
+    }
+
+    /// Returns the (row, col) position of the largest element.
+    /// The first position is returned on ties.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., -2.], vec![3., 4.]]);
+    /// assert_eq!(m.argmax(), Some((1, 1)));
+    /// ```
+    pub fn argmax(&self) -> Option<(usize, usize)> {
+        self.positions()
+            .reduce(|a, b| if self[b] > self[a] { b } else { a })
+    }
+
+    /// Returns the mean of every row or of every column.
+    ///
+    /// # Arguments
+    /// `axis` - `Axis::Row` gives one value per row, `Axis::Col` one per column.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::{Axis, Matrix};
+    /// let m = Matrix::new_mat(vec![vec![1., 2.], vec![3., 4.]]);
+    /// assert_eq!(m.mean_axis(Axis::Row), vec![1.5, 3.5]);
+    /// ```
+    pub fn mean_axis(&self, axis: Axis) -> Vec<f64> {
+        self.lanes(axis)
+            .map(|l| l.iter().sum::<f64>() / l.len() as f64)
+            .collect()
+    }
+
+    /// Returns the smallest element of every row or of every column.
+    ///
+    /// # Arguments
+    /// `axis` - `Axis::Row` gives one value per row, `Axis::Col` one per column.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::{Axis, Matrix};
+    /// let m = Matrix::new_mat(vec![vec![1., 2.], vec![3., 0.]]);
+    /// assert_eq!(m.min_axis(Axis::Col), vec![1., 0.]);
+    /// ```
+    pub fn min_axis(&self, axis: Axis) -> Vec<f64> {
+        self.lanes(axis)
+            .map(|l| l.into_iter().fold(f64::INFINITY, f64::min))
+            .collect()
+    }
+
+    /// Returns the largest element of every row or of every column.
+    ///
+    /// # Arguments
+    /// `axis` - `Axis::Row` gives one value per row, `Axis::Col` one per column.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::{Axis, Matrix};
+    /// let m = Matrix::new_mat(vec![vec![1., 2.], vec![3., 0.]]);
+    /// assert_eq!(m.max_axis(Axis::Row), vec![2., 3.]);
+    /// ```
+    pub fn max_axis(&self, axis: Axis) -> Vec<f64> {
+        self.lanes(axis)
+            .map(|l| l.into_iter().fold(f64::NEG_INFINITY, f64::max))
+            .collect()
+    }
+
+    /// Returns the index of the largest element within every row
+    /// (a column index) or within every column (a row index).
+    ///
+    /// # Arguments
+    /// `axis` - `Axis::Row` gives one value per row, `Axis::Col` one per column.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::{Axis, Matrix};
+    /// let m = Matrix::new_mat(vec![vec![1., 2.], vec![3., 0.]]);
+    /// assert_eq!(m.argmax_axis(Axis::Row), vec![1, 0]);
+    /// assert_eq!(m.argmax_axis(Axis::Col), vec![1, 0]);
+    /// ```
+    pub fn argmax_axis(&self, axis: Axis) -> Vec<usize> {
+        self.lanes(axis)
+            .map(|l| {
+                (0..l.len())
+                    .reduce(|a, b| if l[b] > l[a] { b } else { a })
+                    .unwrap_or(0)
+            })
+            .collect()
+    }
+
+    fn scale(&self, k: f64) -> Matrix {
+        let mat = self
+            .mat
+            .iter()
+            .map(|r| r.iter().map(|x| x * k).collect())
+            .collect();
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            mat,
+        }
+    }
+
+    /// LU factorization with partial pivoting, packed into one array
+    /// with the unit lower factor below the diagonal.
+    /// Returns None if the matrix is singular.
+    fn lu_factor(&self) -> Option<(Vec<Vec<f64>>, Vec<usize>)> {
+        let n = self.rows;
+        let mut a = self.mat.clone();
+        let mut perm: Vec<usize> = (0..n).collect();
+        let scale = self.max_abs();
+        for k in 0..n {
+            let p = (k..n)
+                .max_by(|&i, &j| a[i][k].abs().total_cmp(&a[j][k].abs()))
+                .unwrap();
+            if a[p][k].abs() <= scale * n as f64 * f64::EPSILON {
+                return None;
+            }
+            a.swap(k, p);
+            perm.swap(k, p);
+            let pivot_row = a[k].clone();
+            for row in a.iter_mut().skip(k + 1) {
+                row[k] /= pivot_row[k];
+                let factor = row[k];
+                for (v, p) in row[k + 1..].iter_mut().zip(&pivot_row[k + 1..]) {
+                    *v -= factor * p;
+                }
+            }
+        }
+        Some((a, perm))
+    }
+
+    /// Eigenvalues of a symmetric matrix by the cyclic Jacobi method.
+    fn jacobi_eigenvalues(&self) -> Vec<f64> {
+        self.jacobi_eigen().0
+    }
+
+    /// Cyclic Jacobi rotations on a symmetric matrix. Returns the unsorted
+    /// eigenvalues and the matrix whose columns are the eigenvectors.
+    fn jacobi_eigen(&self) -> (Vec<f64>, Vec<Vec<f64>>) {
+        let n = self.rows;
+        let mut a = self.mat.clone();
+        let mut v = Matrix::identity(n, n).unwrap().mat;
+        for _ in 0..MAX_ITERATIONS {
+            let off: f64 = (0..n)
+                .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+                .map(|(i, j)| a[i][j] * a[i][j])
+                .sum();
+            if off <= f64::EPSILON * f64::EPSILON * self.norm_frobenius().powi(2) {
+                break;
+            }
+            for p in 0..n {
+                for q in p + 1..n {
+                    if a[p][q] == 0. {
+                        continue;
+                    }
+                    let theta = (a[q][q] - a[p][p]) / (2. * a[p][q]);
+                    let t = theta.signum() / (theta.abs() + (theta * theta + 1.).sqrt());
+                    let c = 1. / (t * t + 1.).sqrt();
+                    let s = t * c;
+                    for row in a.iter_mut().chain(v.iter_mut()) {
+                        let (akp, akq) = (row[p], row[q]);
+                        row[p] = c * akp - s * akq;
+                        row[q] = s * akp + c * akq;
+                    }
+                    let (row_p, row_q) = (a[p].clone(), a[q].clone());
+                    for k in 0..n {
+                        a[p][k] = c * row_p[k] - s * row_q[k];
+                        a[q][k] = s * row_p[k] + c * row_q[k];
+                    }
+                }
+            }
+        }
+        ((0..n).map(|i| a[i][i]).collect(), v)
+    }
+
+    /// Orthonormalizes the columns of the matrix.
+    /// Fails if the columns are linearly dependent.
+    ///
+    /// # Arguments
+    /// `method` - The Gram-Schmidt variant to use
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::{GramSchmidt, Matrix};
+    /// let m = Matrix::new_mat(vec![vec![1., 1.], vec![1., 0.], vec![0., 1.]]);
+    /// let q = m.gram_schmidt(GramSchmidt::Modified).unwrap();
+    /// assert!(Matrix::dot_product(&q.get_col(0), &q.get_col(1)).abs() < 1e-15);
+    /// assert!(Matrix::new_mat(vec![vec![1., 2.], vec![2., 4.]])
+    ///     .gram_schmidt(GramSchmidt::Classical)
+    ///     .is_err());
+    /// ```
+    pub fn gram_schmidt(&self, method: GramSchmidt) -> Result<Matrix, MatrixError> {
+        let cols: Vec<Vec<f64>> = (0..self.cols).map(|c| self.get_col(c)).collect();
+        match orthonormalize(cols, method, false) {
+            Some(q) if q.len() == self.cols => Matrix::from_cols(q),
+            _ => Err(MatrixError::new(
+                "The columns are linearly dependent".to_string(),
+            )),
+        }
+    }
+
+    /// Returns an orthonormal basis for the column space of the matrix.
+    /// Dependent columns are skipped, so the basis has rank columns.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1., 2., 0.], vec![2., 4., 0.], vec![0., 0., 1.]]);
+    /// assert_eq!(m.column_space_basis().cols, 2);
+    /// ```
+    pub fn column_space_basis(&self) -> Matrix {
+        let cols: Vec<Vec<f64>> = (0..self.cols).map(|c| self.get_col(c)).collect();
+        match orthonormalize(cols, GramSchmidt::Reorthogonalized, true) {
+            Some(q) if !q.is_empty() => Matrix::from_cols(q).unwrap(),
+            _ => Matrix::new(self.rows, 0),
+        }
+    }
+
+    /// Returns the rows x rows orthogonal projection matrix onto
+    /// the column space of the matrix. The columns need not be independent.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::approx::Tolerance;
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1.], vec![1.]]);
+    /// let p = m.projection_matrix();
+    /// assert!((p[(0, 1)] - 0.5).abs() < 1e-15);
+    /// assert!(p.is_idempotent(Tolerance::default()));
+    /// ```
+    pub fn projection_matrix(&self) -> Matrix {
+        let q = self.column_space_basis();
+        Matrix::from_fn(self.rows, self.rows, |r, c| {
+            (0..q.cols).map(|k| q[(r, k)] * q[(c, k)]).sum()
+        })
+    }
+
+    /// Projects a vector onto the column space of the matrix
+    ///
+    /// # Arguments
+    /// `v` - A vector of length rows
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1.], vec![0.], vec![0.]]);
+    /// assert_eq!(m.project(&[3., 4., 5.]).unwrap(), vec![3., 0., 0.]);
+    /// ```
+    pub fn project(&self, v: &[f64]) -> Result<Vec<f64>, MatrixError> {
+        self.decompose(v).map(|(p, _)| p)
+    }
+
+    /// Splits a vector into its projection onto the column space of
+    /// the matrix and the component orthogonal to it. The two parts
+    /// add up to the vector.
+    ///
+    /// # Arguments
+    /// `v` - A vector of length rows
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![1.], vec![1.]]);
+    /// let (p, o) = m.decompose(&[2., 0.]).unwrap();
+    /// assert!((p[0] - 1.).abs() < 1e-15 && (p[1] - 1.).abs() < 1e-15);
+    /// assert!((o[0] - 1.).abs() < 1e-15 && (o[1] + 1.).abs() < 1e-15);
+    /// ```
+    pub fn decompose(&self, v: &[f64]) -> Result<(Vec<f64>, Vec<f64>), MatrixError> {
+        if v.len() != self.rows {
+            return Err(MatrixError::new(format!(
+                "The vector should have length {} but has {}",
+                self.rows,
+                v.len()
+            )));
+        }
+        let q = self.column_space_basis();
+        let mut orthogonal = v.to_vec();
+        // Subtract one basis direction at a time, as in modified Gram-Schmidt
+        for k in 0..q.cols {
+            let d: f64 = (0..q.rows).map(|r| q[(r, k)] * orthogonal[r]).sum();
+            for (r, x) in orthogonal.iter_mut().enumerate() {
+                *x -= d * q[(r, k)];
+            }
+        }
+        let projection = v
+            .iter()
+            .zip(orthogonal.iter())
+            .map(|(a, b)| a - b)
+            .collect();
+        Ok((projection, orthogonal))
+    }
+}
+
+impl<T: Scalar + ApproxEq> ApproxEq for Matrix<T> {
+    /// Compares the dimensions and then every element
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::assert_approx_eq;
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![4., 1.], vec![2., 3.]]);
+    /// let id = Matrix::multiply(&m, &m.inverse().unwrap()).unwrap();
+    /// assert_approx_eq!(id, Matrix::identity(2, 2).unwrap());
+    /// ```
+    fn approx_eq(&self, other: &Matrix<T>, tolerance: Tolerance) -> bool {
+        self.rows == other.rows
+            && self.cols == other.cols
+            && self
+                .mat
+                .iter()
+                .zip(&other.mat)
+                .all(|(a, b)| a.approx_eq(b, tolerance))
+    }
+
+    fn ulps_eq(&self, other: &Matrix<T>, ulps: u64) -> bool {
+        self.rows == other.rows
+            && self.cols == other.cols
+            && self
+                .mat
+                .iter()
+                .zip(&other.mat)
+                .all(|(a, b)| a.ulps_eq(b, ulps))
+    }
+}
+
+impl Matrix<Complex> {
+    /// Returns a complex matrix with the given real parts
+    /// and zero imaginary parts.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::complex::Complex;
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::<Complex>::from_real(&Matrix::identity(2, 2).unwrap());
+    /// assert_eq!(m[(1, 1)], Complex::new(1., 0.));
+    /// ```
+    pub fn from_real(m: &Matrix) -> Matrix<Complex> {
+        Matrix::from_fn(m.rows, m.cols, |r, c| Complex::from(m.mat[r][c]))
+    }
+
+    /// Returns the real parts of the elements
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::complex::Complex;
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![Complex::new(1., 2.)]]);
+    /// assert_eq!(m.real().get_row(0).unwrap(), vec![1.]);
+    /// ```
+    pub fn real(&self) -> Matrix {
+        Matrix::from_fn(self.rows, self.cols, |r, c| self.mat[r][c].re)
+    }
+
+    /// Returns the imaginary parts of the elements
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::complex::Complex;
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![Complex::new(1., 2.)]]);
+    /// assert_eq!(m.imag().get_row(0).unwrap(), vec![2.]);
+    /// ```
+    pub fn imag(&self) -> Matrix {
+        Matrix::from_fn(self.rows, self.cols, |r, c| self.mat[r][c].im)
+    }
+
+    /// Returns the matrix with every element conjugated
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::complex::Complex;
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![Complex::new(1., 2.)]]);
+    /// assert_eq!(m.conjugate()[(0, 0)], Complex::new(1., -2.));
+    /// ```
+    pub fn conjugate(&self) -> Matrix<Complex> {
+        self.map(|z| z.conj())
+    }
+
+    /// Returns the conjugate (Hermitian) transpose Aᴴ
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::complex::Complex;
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![vec![Complex::new(1., 2.), Complex::new(0., 1.)]]);
+    /// let h = m.conjugate_transpose();
+    /// assert_eq!(h.rows, 2);
+    /// assert_eq!(h[(1, 0)], Complex::new(0., -1.));
+    /// ```
+    pub fn conjugate_transpose(&self) -> Matrix<Complex> {
+        Matrix::from_fn(self.cols, self.rows, |r, c| self.mat[c][r].conj())
+    }
+
+    /// Returns whether the matrix is square and equal to its conjugate transpose
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::approx::Tolerance;
+    /// use ralgeb::complex::Complex;
+    /// use ralgeb::matrix::Matrix;
+    /// let m = Matrix::new_mat(vec![
+    ///     vec![Complex::new(2., 0.), Complex::new(0., -1.)],
+    ///     vec![Complex::new(0., 1.), Complex::new(3., 0.)],
+    /// ]);
+    /// assert!(m.is_hermitian(Tolerance::default()));
+    /// ```
+    pub fn is_hermitian(&self, tolerance: Tolerance) -> bool {
+        self.is_square()
+            && self
+                .positions()
+                .all(|(r, c)| tolerance.close(self.mat[r][c], self.mat[c][r].conj()))
+    }
+
+    /// Returns whether the matrix is square and AᴴA is the identity matrix
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::approx::Tolerance;
+    /// use ralgeb::complex::Complex;
+    /// use ralgeb::matrix::Matrix;
+    /// let s = 1. / 2_f64.sqrt();
+    /// let m = Matrix::new_mat(vec![
+    ///     vec![Complex::new(s, 0.), Complex::new(s, 0.)],
+    ///     vec![Complex::new(0., s), Complex::new(0., -s)],
+    /// ]);
+    /// assert!(m.is_unitary(Tolerance::default()));
+    /// ```
+    pub fn is_unitary(&self, tolerance: Tolerance) -> bool {
+        if !self.is_square() {
+            return false;
+        }
+        let product = Matrix::multiply(&self.conjugate_transpose(), self).unwrap();
+        product.approx_eq(&Matrix::eye(self.rows, self.cols), tolerance)
+    }
+}
+
+/// Orthonormalizes the vectors in order. A vector whose remaining norm
+/// is negligible relative to its original norm is dependent: it is
+/// dropped when `skip_dependent` is set, otherwise None is returned.
+fn orthonormalize(
+    vectors: Vec<Vec<f64>>,
+    method: GramSchmidt,
+    skip_dependent: bool,
+) -> Option<Vec<Vec<f64>>> {
+    let norm = |v: &[f64]| v.iter().map(|x| x * x).sum::<f64>().sqrt();
+    let mut basis: Vec<Vec<f64>> = Vec::with_capacity(vectors.len());
+    for mut v in vectors {
+        let original = norm(&v);
+        let passes = if method == GramSchmidt::Reorthogonalized {
+            2
+        } else {
+            1
+        };
+        for _ in 0..passes {
+            if method == GramSchmidt::Modified {
+                for q in basis.iter() {
+                    let d: f64 = q.iter().zip(v.iter()).map(|(a, b)| a * b).sum();
+                    for (x, y) in v.iter_mut().zip(q) {
+                        *x -= d * y;
+                    }
+                }
+            } else {
+                let coeffs: Vec<f64> = basis
+                    .iter()
+                    .map(|q| q.iter().zip(v.iter()).map(|(a, b)| a * b).sum())
+                    .collect();
+                for (q, d) in basis.iter().zip(coeffs) {
+                    for (x, y) in v.iter_mut().zip(q) {
+                        *x -= d * y;
+                    }
+                }
+            }
+        }
+        let n = norm(&v);
+        if original == 0. || n <= 1e-10 * original {
+            if skip_dependent {
+                continue;
+            }
+            return None;
+        }
+        for x in v.iter_mut() {
+            *x /= n;
+        }
+        basis.push(v);
+    }
+    Some(basis)
+}
+
+/// Returns the Householder vector v and beta = 2 / vᵀv such that
+/// (I - beta v vᵀ) x is a multiple of the first unit vector.
+/// Returns None when x is already such a multiple.
+fn householder_vector(x: &[f64]) -> Option<(Vec<f64>, f64)> {
+    let tail: f64 = x[1..].iter().map(|v| v * v).sum();
+    if tail == 0. {
+        return None;
+    }
+    let norm = (x[0] * x[0] + tail).sqrt();
+    let mut v = x.to_vec();
+    v[0] += if x[0] < 0. { -norm } else { norm };
+    let beta = 2. / (v[0] * v[0] + tail);
+    Some((v, beta))
+}
+
+/// Applies a Householder reflection from the left to rows
+/// start..start + v.len(), touching only the given columns
+fn reflect_rows(
+    m: &mut [Vec<f64>],
+    v: &[f64],
+    beta: f64,
+    start: usize,
+    cols: std::ops::Range<usize>,
+) {
+    for c in cols {
+        let s: f64 = v
+            .iter()
+            .enumerate()
+            .map(|(i, vi)| vi * m[start + i][c])
+            .sum();
+        for (i, vi) in v.iter().enumerate() {
+            m[start + i][c] -= beta * s * vi;
+        }
+    }
+}
+
+/// Applies a Householder reflection from the right to columns
+/// start..start + v.len(), touching only the given rows
+fn reflect_cols(
+    m: &mut [Vec<f64>],
+    v: &[f64],
+    beta: f64,
+    start: usize,
+    rows: std::ops::Range<usize>,
+) {
+    for row in m[rows].iter_mut() {
+        let s: f64 = v.iter().zip(&row[start..]).map(|(vi, x)| vi * x).sum();
+        for (x, vi) in row[start..].iter_mut().zip(v) {
+            *x -= beta * s * vi;
+        }
+    }
+}
+
+/// Performs one Francis double shift QR step on the active block
+/// lo..hi of the Hessenberg matrix h, accumulating the transformations
+/// into q. The whole of h is updated so that it converges to the full
+/// Schur form rather than just its diagonal blocks.
+fn francis_step(h: &mut [Vec<f64>], q: &mut [Vec<f64>], lo: usize, hi: usize, iteration: usize) {
+    let n = h.len();
+    let (p, l) = (hi - 2, hi - 1);
+    // Shifts are the eigenvalues of the trailing 2x2 block, given by
+    // their sum and product. Use exceptional shifts if progress stalls.
+    let (s, t) = if iteration % 10 == 0 {
+        let w = h[l][p].abs() + h[p][p - 1].abs();
+        (1.5 * w, w * w)
+    } else {
+        (h[p][p] + h[l][l], h[p][p] * h[l][l] - h[p][l] * h[l][p])
+    };
+    // First column of (H - s1 I)(H - s2 I)
+    let mut x = h[lo][lo] * h[lo][lo] + h[lo][lo + 1] * h[lo + 1][lo] - s * h[lo][lo] + t;
+    let mut y = h[lo + 1][lo] * (h[lo][lo] + h[lo + 1][lo + 1] - s);
+    let mut z = h[lo + 1][lo] * h[lo + 2][lo + 1];
+    for k in lo..hi - 2 {
+        if let Some((v, beta)) = householder_vector(&[x, y, z]) {
+            let first = if k > lo { k - 1 } else { lo };
+            reflect_rows(h, &v, beta, k, first..n);
+            reflect_cols(h, &v, beta, k, 0..hi.min(k + 4));
+            reflect_cols(q, &v, beta, k, 0..n);
+        }
+        x = h[k + 1][k];
+        y = h[k + 2][k];
+        if k + 3 < hi {
+            z = h[k + 3][k];
+        }
+    }
+    if let Some((v, beta)) = householder_vector(&[x, y]) {
+        reflect_rows(h, &v, beta, hi - 2, hi - 3..n);
+        reflect_cols(h, &v, beta, hi - 2, 0..hi);
+        reflect_cols(q, &v, beta, hi - 2, 0..n);
+    }
+}
+
+/// Triangularizes the 2x2 diagonal block at (k, k) of the quasi
+/// triangular matrix h with a rotation if its eigenvalues are real,
+/// accumulating the rotation into q
+fn split_real_block(h: &mut [Vec<f64>], q: &mut [Vec<f64>], k: usize) {
+    let (a, b, c, d) = (h[k][k], h[k][k + 1], h[k + 1][k], h[k + 1][k + 1]);
+    let half = (a - d) / 2.;
+    let disc = half * half + b * c;
+    if c == 0. || disc < 0. {
+        return;
+    }
+    let lambda = (a + d) / 2. + if half < 0. { -disc.sqrt() } else { disc.sqrt() };
+    // An eigenvector of the block for lambda, taken from whichever
+    // row of (block - lambda I) gives the larger vector
+    let (e1, e2) = ((b, lambda - a), (lambda - d, c));
+    let (x, y) = if e1.0.hypot(e1.1) >= e2.0.hypot(e2.1) {
+        e1
+    } else {
+        e2
+    };
+    let r = x.hypot(y);
+    let (cs, sn) = (x / r, y / r);
+    let (top, bottom) = h.split_at_mut(k + 1);
+    for (u, w) in top[k][k..].iter_mut().zip(bottom[0][k..].iter_mut()) {
+        let (x, y) = (*u, *w);
+        *u = cs * x + sn * y;
+        *w = cs * y - sn * x;
+    }
+    for row in h.iter_mut().take(k + 2).chain(q.iter_mut()) {
+        let (u, w) = (row[k], row[k + 1]);
+        row[k] = cs * u + sn * w;
+        row[k + 1] = cs * w - sn * u;
+    }
+    h[k + 1][k] = 0.;
+}
+
+/// Returns the (start, size) of the 1x1 and 2x2 diagonal blocks of
+/// a quasi upper triangular matrix
+fn quasi_triangular_blocks(t: &Matrix) -> Vec<(usize, usize)> {
+    let mut blocks = Vec::new();
+    let mut i = 0;
+    while i < t.rows {
+        let size = if i + 1 < t.rows && t[(i + 1, i)] != 0. {
+            2
+        } else {
+            1
+        };
+        blocks.push((i, size));
+        i += size;
+    }
+    blocks
+}
+
+/// Fails unless Q is square and symmetric up to rounding
+/// relative to its largest element
+fn symmetric_check(q: &Matrix) -> Result<(), MatrixError> {
+    let scale = q.max_abs();
+    let tolerance = Tolerance::default();
+    if q.is_square()
+        && q.positions()
+            .all(|(r, c)| tolerance.negligible(q[(r, c)] - q[(c, r)], scale))
+    {
+        Ok(())
+    } else {
+        Err(MatrixError::new(
+            "Q should be a symmetric matrix".to_string(),
+        ))
+    }
+}
+
+/// Returns (M + Mᵀ) / 2
+fn symmetric_part(m: &Matrix) -> Matrix {
+    Matrix::from_fn(m.rows, m.cols, |r, c| (m[(r, c)] + m[(c, r)]) / 2.)
+}
+
+/// Solves with the packed LU factors, or with their transpose.
+fn lu_solve(lu: &[Vec<f64>], perm: &[usize], b: &[f64], transpose: bool) -> Vec<f64> {
+    let n = lu.len();
+    if !transpose {
+        // L y = P b, then U x = y
+        let mut x: Vec<f64> = perm.iter().map(|&p| b[p]).collect();
+        for i in 0..n {
+            let s: f64 = (0..i).map(|j| lu[i][j] * x[j]).sum();
+            x[i] -= s;
+        }
+        for i in (0..n).rev() {
+            let s: f64 = (i + 1..n).map(|j| lu[i][j] * x[j]).sum();
+            x[i] = (x[i] - s) / lu[i][i];
+        }
+        x
+    } else {
+        // Uᵀ y = b, then Lᵀ z = y and x = Pᵀ z
+        let mut y = b.to_vec();
+        for i in 0..n {
+            let s: f64 = (0..i).map(|j| lu[j][i] * y[j]).sum();
+            y[i] = (y[i] - s) / lu[i][i];
+        }
+        for i in (0..n).rev() {
+            let s: f64 = (i + 1..n).map(|j| lu[j][i] * y[j]).sum();
+            y[i] -= s;
+        }
+        let mut x = vec![0.; n];
+        for (i, &p) in perm.iter().enumerate() {
+            x[p] = y[i];
+        }
+        x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix;
+    #[test]
+    fn new_zero_matrix() {
+        let m = matrix::Matrix::new(2, 3);
+        let v: Vec<f64> = vec![0.0; 3];
+        assert_eq!(m.mat[0], v);
+        assert_eq!(m.rows, 2);
+        assert_eq!(m.cols, 3);
+    }
+
+    #[test]
+    fn identity() {
+        // Provide rectangular row and col
+        let m = matrix::Matrix::identity(4, 3).unwrap();
+        assert_eq!(m.rows, 4);
+        assert_eq!(m.cols, 3);
+        assert_eq!(m.mat[2], vec![0., 0., 1.]);
+        assert_eq!(m.mat[3], vec![0., 0., 0.]);
+        let m = matrix::Matrix::identity(2, 3).unwrap();
+        assert_eq!(m.mat, vec![vec![1., 0., 0.], vec![0., 1., 0.]]);
+
+        // Provide square row and col
         let m = matrix::Matrix::identity(3, 3);
         assert_eq!(m.is_some(), true);
         match m {
@@ -684,4 +3641,939 @@ mod tests {
         assert_eq!(m.rows, 4);
         assert_eq!(m.cols, 3);
     }
+
+    fn assert_close(a: &matrix::Matrix, b: &matrix::Matrix, tol: f64) {
+        assert_eq!(a.rows, b.rows);
+        assert_eq!(a.cols, b.cols);
+        for i in 0..a.rows {
+            for j in 0..a.cols {
+                assert!(
+                    (a.mat[i][j] - b.mat[i][j]).abs() <= tol,
+                    "{:?} != {:?}",
+                    a.mat,
+                    b.mat
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn inverse() {
+        let m = matrix::Matrix::new_mat(vec![vec![4., 7.], vec![2., 6.]]);
+        let inv = m.inverse().unwrap();
+        let expected = matrix::Matrix::new_mat(vec![vec![0.6, -0.7], vec![-0.2, 0.4]]);
+        assert_close(&inv, &expected, 1e-12);
+
+        let singular = matrix::Matrix::new_mat(vec![vec![1., 2.], vec![2., 4.]]);
+        assert!(singular.inverse().is_err());
+        assert!(matrix::Matrix::new(2, 3).inverse().is_err());
+    }
+
+    #[test]
+    fn solve() {
+        // Needs a row swap to find a pivot
+        let a = matrix::Matrix::new_mat(vec![vec![0., 2., 1.], vec![1., 1., 1.], vec![2., 1., 0.]]);
+        let b = matrix::Matrix::new_mat(vec![vec![7.], vec![6.], vec![4.]]);
+        let x = a.solve(&b).unwrap();
+        let expected = matrix::Matrix::new_mat(vec![vec![1.], vec![2.], vec![3.]]);
+        assert_close(&x, &expected, 1e-12);
+
+        assert!(a.solve(&matrix::Matrix::new(2, 1)).is_err());
+    }
+
+    #[test]
+    fn pow() {
+        let p = matrix::Matrix::new_mat(vec![vec![0.9, 0.1], vec![0.5, 0.5]]);
+        assert_close(
+            &p.pow(0).unwrap(),
+            &matrix::Matrix::identity(2, 2).unwrap(),
+            0.,
+        );
+        assert_close(&p.pow(1).unwrap(), &p, 0.);
+        let p3 = matrix::Matrix::multiply(&matrix::Matrix::multiply(&p, &p).unwrap(), &p).unwrap();
+        assert_close(&p.pow(3).unwrap(), &p3, 1e-15);
+
+        let m = matrix::Matrix::new_mat(vec![vec![2., 0.], vec![0., 4.]]);
+        let expected = matrix::Matrix::new_mat(vec![vec![0.125, 0.], vec![0., 0.015625]]);
+        assert_close(&m.pow(-3).unwrap(), &expected, 1e-15);
+
+        assert!(matrix::Matrix::new(2, 3).pow(2).is_err());
+        assert!(matrix::Matrix::new(2, 2).pow(-1).is_err());
+    }
+
+    #[test]
+    fn expm() {
+        let d = matrix::Matrix::new_mat(vec![vec![1., 0.], vec![0., -2.]]);
+        let expected = matrix::Matrix::new_mat(vec![
+            vec![std::f64::consts::E, 0.],
+            vec![0., (-2_f64).exp()],
+        ]);
+        assert_close(&d.expm().unwrap(), &expected, 1e-13);
+
+        // exp of a rotation generator is a rotation
+        let t = 2.5;
+        let r = matrix::Matrix::new_mat(vec![vec![0., -t], vec![t, 0.]]);
+        let expected =
+            matrix::Matrix::new_mat(vec![vec![t.cos(), -t.sin()], vec![t.sin(), t.cos()]]);
+        assert_close(&r.expm().unwrap(), &expected, 1e-13);
+
+        let z = matrix::Matrix::new(3, 3);
+        assert_close(
+            &z.expm().unwrap(),
+            &matrix::Matrix::identity(3, 3).unwrap(),
+            0.,
+        );
+        assert!(matrix::Matrix::new(2, 3).expm().is_err());
+        assert!(matrix::Matrix::new_mat(vec![vec![f64::INFINITY]])
+            .expm()
+            .is_err());
+        assert!(
+            matrix::Matrix::new_mat(vec![vec![0., f64::NAN], vec![0., 0.]])
+                .expm()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn sqrtm() {
+        let a = matrix::Matrix::new_mat(vec![vec![5., 2.], vec![2., 5.]]);
+        let s = a.sqrtm().unwrap();
+        assert_close(&matrix::Matrix::multiply(&s, &s).unwrap(), &a, 1e-12);
+
+        assert!(matrix::Matrix::new(2, 2).sqrtm().is_err());
+    }
+
+    #[test]
+    fn logm() {
+        let a = matrix::Matrix::new_mat(vec![vec![0.5, 1.], vec![-0.3, 0.2]]);
+        let l = a.expm().unwrap().logm().unwrap();
+        assert_close(&l, &a, 1e-10);
+
+        let id = matrix::Matrix::identity(3, 3).unwrap();
+        assert_close(&id.logm().unwrap(), &matrix::Matrix::new(3, 3), 0.);
+        assert!(matrix::Matrix::new(3, 2).logm().is_err());
+    }
+
+    #[test]
+    fn trace() {
+        let m = matrix::Matrix::new_mat(vec![vec![1., 2., 3.], vec![4., 5., 6.], vec![7., 8., 9.]]);
+        assert_eq!(m.trace().unwrap(), 15.);
+        assert!(matrix::Matrix::new(2, 3).trace().is_err());
+    }
+
+    #[test]
+    fn norms() {
+        let m = matrix::Matrix::new_mat(vec![vec![1., -2., 3.], vec![-4., 5., -6.]]);
+        assert_eq!(m.norm_one(), 9.);
+        assert_eq!(m.norm_inf(), 15.);
+        assert_eq!(m.max_abs(), 6.);
+        assert!((m.norm_frobenius() - 91_f64.sqrt()).abs() < 1e-12);
+
+        // Singular values of [[3, 0], [4, 5]] are 3√5 and √5
+        let m = matrix::Matrix::new_mat(vec![vec![3., 0.], vec![4., 5.]]);
+        assert!((m.norm_spectral() - 45_f64.sqrt()).abs() < 1e-12);
+        // Rectangular input
+        let m = matrix::Matrix::new_mat(vec![vec![1., 1.], vec![1., 1.], vec![1., 1.]]);
+        assert!((m.norm_spectral() - 6_f64.sqrt()).abs() < 1e-12);
+
+        assert_eq!(matrix::Matrix::new(2, 2).norm_spectral(), 0.);
+    }
+
+    #[test]
+    fn condition_estimate() {
+        let m = matrix::Matrix::new_mat(vec![vec![1., 0.], vec![0., 1e-8]]);
+        assert!((m.condition_estimate().unwrap() - 1e8).abs() < 1.);
+
+        let m =
+            matrix::Matrix::new_mat(vec![vec![4., -2., 1.], vec![3., 6., -4.], vec![2., 1., 8.]]);
+        let exact = m.norm_one() * m.inverse().unwrap().norm_one();
+        let estimate = m.condition_estimate().unwrap();
+        assert!(estimate <= exact * (1. + 1e-12));
+        assert!(estimate >= exact / 3.);
+
+        let singular = matrix::Matrix::new_mat(vec![vec![1., 2.], vec![2., 4.]]);
+        assert_eq!(singular.condition_estimate().unwrap(), f64::INFINITY);
+        assert!(matrix::Matrix::new(2, 3).condition_estimate().is_err());
+    }
+
+    #[test]
+    fn index() {
+        let mut m = matrix::Matrix::new(2, 3);
+        m[(1, 2)] = 5.;
+        assert_eq!(m[(1, 2)], 5.);
+        assert_eq!(m.mat[1], vec![0., 0., 5.]);
+    }
+
+    #[test]
+    fn hadamard() {
+        let m1 = matrix::Matrix::new_mat(vec![vec![1., 2.], vec![3., 4.]]);
+        let m2 = matrix::Matrix::new_mat(vec![vec![5., 6.], vec![7., 8.]]);
+        let m = matrix::Matrix::hadamard(&m1, &m2).unwrap();
+        assert_eq!(m.mat, vec![vec![5., 12.], vec![21., 32.]]);
+        let m = matrix::Matrix::hadamard_div(&m, &m2).unwrap();
+        assert_eq!(m.mat, m1.mat);
+        assert!(matrix::Matrix::hadamard(&m1, &matrix::Matrix::new(2, 3)).is_err());
+        assert!(matrix::Matrix::hadamard_div(&m1, &matrix::Matrix::new(3, 2)).is_err());
+    }
+
+    #[test]
+    fn map() {
+        let mut m = matrix::Matrix::new_mat(vec![vec![1., -2.], vec![3., -4.]]);
+        assert_eq!(m.map(f64::abs).mat, vec![vec![1., 2.], vec![3., 4.]]);
+        let mut count = 0;
+        m.map_inplace(|x| {
+            count += 1;
+            x * 2.
+        });
+        assert_eq!(count, 4);
+        assert_eq!(m.mat, vec![vec![2., -4.], vec![6., -8.]]);
+
+        let z = matrix::Matrix::zip_map(&m, &m, |a, b| a - b).unwrap();
+        assert_eq!(z.mat, vec![vec![0.; 2]; 2]);
+    }
+
+    #[test]
+    fn reductions() {
+        let m = matrix::Matrix::new_mat(vec![vec![2., -1., 3.], vec![4., 5., -6.]]);
+        assert_eq!(m.sum(), 7.);
+        assert_eq!(m.product(), 720.);
+        assert_eq!(m.mean(), Some(7. / 6.));
+        assert_eq!(m.min(), Some(-6.));
+        assert_eq!(m.max(), Some(5.));
+        assert_eq!(m.argmin(), Some((1, 2)));
+        assert_eq!(m.argmax(), Some((1, 1)));
+
+        let empty = matrix::Matrix::new(0, 0);
+        assert_eq!(empty.mean(), None);
+        assert_eq!(empty.max(), None);
+        assert_eq!(empty.argmin(), None);
+    }
+
+    #[test]
+    fn axis_reductions() {
+        use matrix::Axis;
+        let m = matrix::Matrix::new_mat(vec![vec![2., -1., 3.], vec![4., 5., -6.]]);
+        assert_eq!(m.sum_axis(Axis::Row), vec![4., 3.]);
+        assert_eq!(m.sum_axis(Axis::Col), vec![6., 4., -3.]);
+        assert_eq!(m.product_axis(Axis::Row), vec![-6., -120.]);
+        assert_eq!(m.mean_axis(Axis::Col), vec![3., 2., -1.5]);
+        assert_eq!(m.min_axis(Axis::Row), vec![-1., -6.]);
+        assert_eq!(m.max_axis(Axis::Col), vec![4., 5., 3.]);
+        assert_eq!(m.argmax_axis(Axis::Row), vec![2, 1]);
+        assert_eq!(m.argmax_axis(Axis::Col), vec![1, 1, 0]);
+    }
+
+    #[test]
+    fn constructors() {
+        let m = matrix::Matrix::from_fn(2, 2, |r, c| (r + 10 * c) as f64);
+        assert_eq!(m.mat, vec![vec![0., 10.], vec![1., 11.]]);
+        let m = matrix::Matrix::from_diagonal(&[2., 3.]);
+        assert_eq!(m.mat, vec![vec![2., 0.], vec![0., 3.]]);
+        assert_eq!(matrix::Matrix::ones(1, 3).mat, vec![vec![1.; 3]]);
+        let m = matrix::Matrix::filled(3, 2, -1.);
+        assert_eq!((m.rows, m.cols), (3, 2));
+        assert_eq!(m.sum(), -6.);
+    }
+
+    #[test]
+    fn from_rows_and_cols() {
+        let m = matrix::Matrix::from_rows(vec![vec![1., 2., 3.], vec![4., 5., 6.]]).unwrap();
+        assert_eq!((m.rows, m.cols), (2, 3));
+        let m = matrix::Matrix::from_cols(vec![vec![1., 2., 3.], vec![4., 5., 6.]]).unwrap();
+        assert_eq!(m.mat, vec![vec![1., 4.], vec![2., 5.], vec![3., 6.]]);
+        let m = matrix::Matrix::<f64>::from_rows(vec![]).unwrap();
+        assert_eq!((m.rows, m.cols), (0, 0));
+        assert!(matrix::Matrix::from_cols(vec![vec![1.], vec![]]).is_err());
+    }
+
+    #[test]
+    fn stacking() {
+        let a = matrix::Matrix::new_mat(vec![vec![1., 2.], vec![3., 4.]]);
+        let b = matrix::Matrix::new_mat(vec![vec![5.], vec![6.]]);
+        let h = matrix::Matrix::hstack(&[&a, &b]).unwrap();
+        assert_eq!(h.mat, vec![vec![1., 2., 5.], vec![3., 4., 6.]]);
+        assert!(matrix::Matrix::vstack(&[&a, &b]).is_err());
+
+        let c = matrix::Matrix::new_mat(vec![vec![7., 8., 9.]]);
+        let v = matrix::Matrix::vstack(&[&h, &c]).unwrap();
+        assert_eq!((v.rows, v.cols), (3, 3));
+        assert_eq!(v.mat[2], vec![7., 8., 9.]);
+        assert!(matrix::Matrix::hstack(&[&h, &c]).is_err());
+
+        let blk = matrix::Matrix::block(&[vec![&a, &b], vec![&c]]).unwrap();
+        assert_eq!(blk.mat, v.mat);
+        assert!(matrix::Matrix::block(&[vec![&a], vec![&c]]).is_err());
+    }
+
+    #[test]
+    fn reshape_and_resize() {
+        let m = matrix::Matrix::from_fn(2, 3, |r, c| (r * 3 + c) as f64);
+        let r = m.reshape(6, 1).unwrap();
+        assert_eq!(r.get_col(0), vec![0., 1., 2., 3., 4., 5.]);
+        assert!(m.reshape(4, 2).is_err());
+
+        let r = m.resize(3, 2);
+        assert_eq!(r.mat, vec![vec![0., 1.], vec![3., 4.], vec![0., 0.]]);
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let m = matrix::Matrix::new_mat(vec![vec![1., 2.], vec![3., 4.]]);
+        let m = m.insert_row(0, vec![9., 9.]).unwrap();
+        assert_eq!(m.mat, vec![vec![9., 9.], vec![1., 2.], vec![3., 4.]]);
+        let m = m.insert_col(1, vec![7., 7., 7.]).unwrap();
+        assert_eq!((m.rows, m.cols), (3, 3));
+        assert_eq!(m.mat[2], vec![3., 7., 4.]);
+        let m = m.remove_col(1).unwrap().remove_row(0).unwrap();
+        assert_eq!(m.mat, vec![vec![1., 2.], vec![3., 4.]]);
+
+        assert!(m.clone().insert_row(3, vec![0., 0.]).is_err());
+        assert!(m.clone().insert_row(0, vec![0.]).is_err());
+        assert!(m.clone().insert_col(0, vec![0.]).is_err());
+        assert!(m.clone().remove_row(2).is_err());
+        assert!(m.remove_col(2).is_err());
+    }
+
+    #[test]
+    fn flip_and_rotate() {
+        let m = matrix::Matrix::new_mat(vec![vec![1., 2., 3.], vec![4., 5., 6.]]);
+        assert_eq!(m.flip_rows().mat, vec![vec![4., 5., 6.], vec![1., 2., 3.]]);
+        assert_eq!(m.flip_cols().mat, vec![vec![3., 2., 1.], vec![6., 5., 4.]]);
+
+        let r = m.rotate90(1);
+        assert_eq!(r.mat, vec![vec![3., 6.], vec![2., 5.], vec![1., 4.]]);
+        let r = m.rotate90(-1);
+        assert_eq!(r.mat, vec![vec![4., 1.], vec![5., 2.], vec![6., 3.]]);
+        assert_eq!(m.rotate90(2).mat, vec![vec![6., 5., 4.], vec![3., 2., 1.]]);
+        assert_eq!(m.rotate90(3).mat, m.rotate90(-1).mat);
+        assert_eq!(m.rotate90(4).mat, m.mat);
+    }
+
+    #[test]
+    fn kronecker() {
+        let a = matrix::Matrix::new_mat(vec![vec![1., 2.], vec![3., 4.]]);
+        let b = matrix::Matrix::new_mat(vec![vec![0., 5.], vec![6., 7.]]);
+        let k = matrix::Matrix::kronecker(&a, &b);
+        assert_eq!(
+            k.mat,
+            vec![
+                vec![0., 5., 0., 10.],
+                vec![6., 7., 12., 14.],
+                vec![0., 15., 0., 20.],
+                vec![18., 21., 24., 28.],
+            ]
+        );
+        let v = matrix::Matrix::new_mat(vec![vec![1.], vec![2.]]);
+        let k = matrix::Matrix::kronecker(&v, &a);
+        assert_eq!((k.rows, k.cols), (4, 2));
+        assert_eq!(k.mat[3], vec![6., 8.]);
+
+        // Mixed product property (A ⊗ A)(B ⊗ B) = AB ⊗ AB
+        let ab = matrix::Matrix::multiply(&a, &b).unwrap();
+        let lhs = matrix::Matrix::multiply(
+            &matrix::Matrix::kronecker(&a, &a),
+            &matrix::Matrix::kronecker(&b, &b),
+        )
+        .unwrap();
+        assert_eq!(lhs.mat, matrix::Matrix::kronecker(&ab, &ab).mat);
+    }
+
+    #[test]
+    fn outer() {
+        let m = matrix::Matrix::outer(&[1., -1.], &[2., 3.]);
+        assert_eq!(m.mat, vec![vec![2., 3.], vec![-2., -3.]]);
+        let m = matrix::Matrix::outer(&[], &[2., 3.]);
+        assert_eq!((m.rows, m.cols), (0, 2));
+    }
+
+    #[test]
+    fn direct_sum() {
+        let a = matrix::Matrix::new_mat(vec![vec![1., 2.]]);
+        let b = matrix::Matrix::new_mat(vec![vec![3.], vec![4.]]);
+        let m = matrix::Matrix::direct_sum(&a, &b);
+        assert_eq!(
+            m.mat,
+            vec![vec![1., 2., 0.], vec![0., 0., 3.], vec![0., 0., 4.]]
+        );
+    }
+
+    #[test]
+    fn commutator() {
+        let a = matrix::Matrix::new_mat(vec![vec![1., 2.], vec![3., 4.]]);
+        let c = matrix::Matrix::commutator(&a, &a).unwrap();
+        assert_eq!(c.mat, vec![vec![0.; 2]; 2]);
+        let b = matrix::Matrix::new_mat(vec![vec![0., 1.], vec![0., 0.]]);
+        let c = matrix::Matrix::commutator(&a, &b).unwrap();
+        assert_eq!(c.mat, vec![vec![-3., -3.], vec![0., 3.]]);
+        assert!(matrix::Matrix::commutator(&a, &matrix::Matrix::new(2, 3)).is_err());
+    }
+
+    #[test]
+    fn complex_matrix() {
+        use crate::approx::Tolerance;
+        use crate::complex::Complex;
+        let i = Complex::i();
+        let one = Complex::new(1., 0.);
+        let zero = Complex::new(0., 0.);
+        // Pauli matrices: XY = iZ
+        let x = matrix::Matrix::new_mat(vec![vec![zero, one], vec![one, zero]]);
+        let y = matrix::Matrix::new_mat(vec![vec![zero, -i], vec![i, zero]]);
+        let z = matrix::Matrix::new_mat(vec![vec![one, zero], vec![zero, -one]]);
+        let xy = matrix::Matrix::multiply(&x, &y).unwrap();
+        assert_eq!(xy.mat, z.map(|v| v * i).mat);
+        for p in [&x, &y, &z].iter() {
+            assert!(p.is_hermitian(Tolerance::exact()));
+            assert!(p.is_unitary(Tolerance::default()));
+        }
+        let c = matrix::Matrix::commutator(&x, &y).unwrap();
+        assert_eq!(c.mat, z.map(|v| v * i * 2.).mat);
+
+        let k = matrix::Matrix::kronecker(&x, &z);
+        assert_eq!((k.rows, k.cols), (4, 4));
+        assert_eq!(k[(0, 2)], one);
+        assert_eq!(k[(1, 3)], -one);
+    }
+
+    #[test]
+    fn complex_conjugate_transpose() {
+        use crate::approx::Tolerance;
+        use crate::complex::Complex;
+        let m = matrix::Matrix::new_mat(vec![
+            vec![Complex::new(1., 1.), Complex::new(2., -3.)],
+            vec![Complex::new(0., 4.), Complex::new(5., 0.)],
+            vec![Complex::new(6., 0.), Complex::new(0., 0.)],
+        ]);
+        let h = m.conjugate_transpose();
+        assert_eq!((h.rows, h.cols), (2, 3));
+        assert_eq!(h[(0, 1)], Complex::new(0., -4.));
+        assert_eq!(h[(1, 0)], Complex::new(2., 3.));
+        assert_eq!(m.conjugate()[(0, 0)], Complex::new(1., -1.));
+        assert_eq!(m.real().mat, vec![vec![1., 2.], vec![0., 5.], vec![6., 0.]]);
+        assert_eq!(
+            m.imag().mat,
+            vec![vec![1., -3.], vec![4., 0.], vec![0., 0.]]
+        );
+        assert!(!m.is_hermitian(Tolerance::default()));
+        assert!(!m.is_unitary(Tolerance::default()));
+
+        let r = matrix::Matrix::<Complex>::from_real(&matrix::Matrix::ones(2, 2));
+        assert_eq!(r[(1, 0)], Complex::new(1., 0.));
+        assert!(r.is_hermitian(Tolerance::exact()));
+        assert!(!r.is_unitary(Tolerance::default()));
+    }
+
+    #[test]
+    fn complex_solve() {
+        use crate::complex::Complex;
+        let a = matrix::Matrix::new_mat(vec![
+            vec![Complex::new(1., 1.), Complex::new(2., 0.)],
+            vec![Complex::new(0., -1.), Complex::new(3., 1.)],
+        ]);
+        let inv = a.inverse().unwrap();
+        let id = matrix::Matrix::multiply(&a, &inv).unwrap();
+        let eye = matrix::Matrix::<Complex>::eye(2, 2);
+        for r in 0..2 {
+            for c in 0..2 {
+                assert!((id[(r, c)] - eye[(r, c)]).modulus() < 1e-12);
+            }
+        }
+        let sq = a.pow(2).unwrap();
+        assert_eq!(sq.mat, matrix::Matrix::multiply(&a, &a).unwrap().mat);
+        assert_eq!(a.trace().unwrap(), Complex::new(4., 2.));
+    }
+
+    #[test]
+    fn determinant() {
+        let m = matrix::Matrix::new_mat(vec![vec![0., 2., 1.], vec![1., 1., 1.], vec![2., 1., 0.]]);
+        assert!((m.determinant().unwrap() - 3.).abs() < 1e-12);
+        let singular = matrix::Matrix::new_mat(vec![vec![1., 2.], vec![2., 4.]]);
+        assert_eq!(singular.determinant().unwrap(), 0.);
+        assert_eq!(matrix::Matrix::new(0, 0).determinant().unwrap(), 1.);
+        assert!(matrix::Matrix::new(2, 3).determinant().is_err());
+    }
+
+    #[test]
+    fn badly_scaled_determinant() {
+        // Bareiss forms 1e160 · 1e160 on the way
+        let m = matrix::Matrix::new_mat(vec![
+            vec![1e160, 0., 0.],
+            vec![0., 1e160, 0.],
+            vec![0., 0., 1e-300],
+        ]);
+        let det = m.determinant().unwrap();
+        assert!((det - 1e20).abs() < 1e5);
+        // A row swap flips the sign
+        let swapped = matrix::Matrix::new_mat(vec![
+            vec![0., 1e160, 0.],
+            vec![1e160, 0., 0.],
+            vec![0., 0., 1e-300],
+        ]);
+        assert!((swapped.determinant().unwrap() + 1e20).abs() < 1e5);
+    }
+
+    #[test]
+    fn rref_and_rank() {
+        let m = matrix::Matrix::new_mat(vec![vec![1., 2., 3.], vec![2., 4., 6.], vec![1., 0., 1.]]);
+        let r = m.rref();
+        assert_close(
+            &r,
+            &matrix::Matrix::new_mat(vec![vec![1., 0., 1.], vec![0., 1., 1.], vec![0., 0., 0.]]),
+            1e-12,
+        );
+        assert_eq!(m.rank(), 2);
+        assert_eq!(matrix::Matrix::new(3, 2).rank(), 0);
+        assert_eq!(matrix::Matrix::identity(3, 4).unwrap().rank(), 3);
+    }
+
+    #[test]
+    fn large_and_badly_scaled_reductions() {
+        // Bareiss minors of these overflow or underflow
+        for &d in &[1e6, 1e-6] {
+            let m = matrix::Matrix::from_fn(60, 60, |r, c| if r == c { d } else { 0. });
+            let expected = matrix::Matrix::from_fn(60, 60, |r, c| if r == c { 1. / d } else { 0. });
+            assert_close(&m.inverse().unwrap(), &expected, 1e-15 / d);
+            assert_eq!(m.rank(), 60);
+            let cube = matrix::Matrix::from_fn(60, 60, |r, c| if r == c { d.powi(-3) } else { 0. });
+            assert_close(&m.pow(-3).unwrap(), &cube, 1e-15 / d.powi(3));
+        }
+        // Full rank, with entries of very different magnitude
+        let mut rng = crate::random::Rng::new(7);
+        let m = matrix::Matrix::from_fn(60, 60, |r, c| {
+            rng.uniform(-1., 1.) * 10_f64.powi((r % 7) as i32 - 3) + if r == c { 4. } else { 0. }
+        });
+        assert_eq!(m.rank(), 60);
+        let product = matrix::Matrix::multiply(&m, &m.inverse().unwrap()).unwrap();
+        assert_close(&product, &matrix::Matrix::identity(60, 60).unwrap(), 1e-9);
+        let r = m.rref();
+        assert_close(&r, &matrix::Matrix::identity(60, 60).unwrap(), 1e-9);
+        // Rank deficient, one row is a combination of two others
+        let mut rows: Vec<Vec<f64>> = (0..60).map(|r| m.get_row(r).unwrap()).collect();
+        rows[59] = rows[0]
+            .iter()
+            .zip(&rows[1])
+            .map(|(a, b)| 2. * a - b)
+            .collect();
+        assert_eq!(matrix::Matrix::new_mat(rows).rank(), 59);
+    }
+
+    #[test]
+    fn rational_matrix() {
+        use crate::rational::Rational;
+        // 4x4 Hilbert matrix, whose inverse has integer entries
+        let h = matrix::Matrix::from_fn(4, 4, |r, c| Rational::new(1, (r + c + 1) as i64));
+        assert_eq!(h.determinant().unwrap(), Rational::new(1, 6_048_000));
+        let inv = h.inverse().unwrap();
+        assert_eq!(inv[(0, 0)], Rational::from_integer(16));
+        assert_eq!(inv[(1, 2)], Rational::from_integer(-2700));
+        assert_eq!(inv[(3, 3)], Rational::from_integer(2800));
+        assert_eq!(
+            matrix::Matrix::multiply(&h, &inv).unwrap().mat,
+            matrix::Matrix::<Rational>::eye(4, 4).mat
+        );
+
+        let q = |n: i64| Rational::from_integer(n);
+        let m = matrix::Matrix::new_mat(vec![vec![q(1), q(2)], vec![q(3), q(-1)]]);
+        let b = matrix::Matrix::new_mat(vec![vec![q(1)], vec![q(2)]]);
+        let x = m.solve(&b).unwrap();
+        assert_eq!(x.get_col(0), vec![Rational::new(5, 7), Rational::new(1, 7)]);
+        assert_eq!(x[(0, 0)].to_string(), "5/7");
+
+        let singular = matrix::Matrix::new_mat(vec![vec![q(1), q(2)], vec![q(2), q(4)]]);
+        assert_eq!(singular.determinant().unwrap(), q(0));
+        assert!(singular.inverse().is_err());
+        assert!(singular.solve(&b).is_err());
+        assert_eq!(singular.rank(), 1);
+        assert_eq!(
+            singular.rref().mat,
+            vec![vec![q(1), q(2)], vec![q(0), q(0)]]
+        );
+    }
+
+    #[test]
+    fn characteristic_and_minimal_polynomial() {
+        use crate::approx::Tolerance;
+        use crate::rational::Rational;
+        let q = |n: i64| Rational::from_integer(n);
+        // A Jordan block of size 2 plus a 1x1 block, both for eigenvalue 2
+        let j = matrix::Matrix::new_mat(vec![
+            vec![q(2), q(1), q(0)],
+            vec![q(0), q(2), q(0)],
+            vec![q(0), q(0), q(2)],
+        ]);
+        let c = j.characteristic_polynomial().unwrap();
+        assert_eq!(c.coefficients(), &[q(-8), q(12), q(-6), q(1)]);
+        let m = j.minimal_polynomial().unwrap();
+        assert_eq!(m.coefficients(), &[q(4), q(-4), q(1)]);
+        let (_, rem) = c.div_rem(&m).unwrap();
+        assert_eq!(rem.degree(), None);
+        assert!(j.satisfies_cayley_hamilton(Tolerance::exact()).unwrap());
+
+        let a = matrix::Matrix::new_mat(vec![
+            vec![4., -2., 1., 0.5],
+            vec![3., 6., -4., 2.],
+            vec![2., 1., 8., -1.],
+            vec![0., 3., 1., 5.],
+        ]);
+        let c = a.characteristic_polynomial().unwrap();
+        assert!((c.coeff(3) + a.trace().unwrap()).abs() < 1e-12);
+        assert!((c.coeff(0) - a.determinant().unwrap()).abs() < 1e-9);
+        assert!(a.satisfies_cayley_hamilton(Tolerance::default()).unwrap());
+        // Distinct eigenvalues, so both polynomials agree
+        let m = a.minimal_polynomial().unwrap();
+        assert_eq!(m.degree(), Some(4));
+        for (x, y) in m.coefficients().iter().zip(c.coefficients()) {
+            assert!((x - y).abs() < 1e-8);
+        }
+
+        let scalar = matrix::Matrix::identity(3, 3)
+            .unwrap()
+            .scalar_mat_mul(5.)
+            .unwrap();
+        assert_eq!(
+            scalar.minimal_polynomial().unwrap().coefficients(),
+            &[-5., 1.]
+        );
+        assert!(matrix::Matrix::new(2, 3)
+            .characteristic_polynomial()
+            .is_err());
+        assert!(matrix::Matrix::new(2, 3).minimal_polynomial().is_err());
+    }
+
+    #[test]
+    fn predicates() {
+        use crate::approx::{ApproxEq, Tolerance};
+        let tol = Tolerance::default();
+        let m = matrix::Matrix::new_mat(vec![vec![4., 1., 2.], vec![1., 5., 3.], vec![2., 3., 6.]]);
+        // Rounding from a computation must not break the predicates
+        let noisy = m.map(|x| (x / 3.) * 3.);
+        assert!(noisy.approx_eq(&m, tol));
+        assert!(noisy.is_symmetric(tol));
+        assert!(noisy.is_positive_definite(tol));
+        assert!(noisy.is_invertible(tol));
+        assert!(!noisy.is_skew_symmetric(tol));
+        assert!(!noisy.is_diagonal(tol));
+        assert!(!m.approx_eq(&matrix::Matrix::new(3, 2), tol));
+
+        let tiny = Tolerance::absolute(1e-20);
+        let skewed = m.map(|x| x * (1. + 1e-12));
+        assert!(!skewed.approx_eq(&m, tiny));
+        assert!(skewed.approx_eq(&m, Tolerance::relative(1e-11)));
+
+        let q = matrix::Matrix::new_mat(vec![
+            vec![2. / 3., -2. / 3., 1. / 3.],
+            vec![1. / 3., 2. / 3., 2. / 3.],
+            vec![2. / 3., 1. / 3., -2. / 3.],
+        ]);
+        assert!(q.is_orthogonal(tol));
+        assert!(!m.is_orthogonal(tol));
+        assert!(!matrix::Matrix::new(2, 3).is_orthogonal(tol));
+
+        let p = matrix::Matrix::outer(&q.get_col(0), &q.get_col(0));
+        assert!(p.is_idempotent(tol));
+        assert!(!p.is_invertible(tol));
+        assert!(!m.is_idempotent(tol));
+
+        let u = matrix::Matrix::new_mat(vec![vec![1., 2.], vec![1e-15, 3.]]);
+        assert!(u.is_upper_triangular(tol));
+        assert!(!u.is_upper_triangular(Tolerance::exact()));
+        assert!(!u.is_lower_triangular(tol));
+        let s = matrix::Matrix::new_mat(vec![vec![0., -1.], vec![1., 0.]]);
+        assert!(s.is_skew_symmetric(tol));
+        assert!(!s.is_positive_definite(tol));
+        let semi = matrix::Matrix::new_mat(vec![vec![1., 1.], vec![1., 1.]]);
+        assert!(!semi.is_positive_definite(tol));
+        assert!(matrix::Matrix::identity(3, 3).unwrap().is_diagonal(tol));
+        assert!(!matrix::Matrix::new(2, 3).is_invertible(tol));
+    }
+
+    #[test]
+    fn approx_eq_trait() {
+        use crate::approx::{ApproxEq, Tolerance};
+        let m = matrix::Matrix::new_mat(vec![vec![2., 1.], vec![1., 3.]]);
+        let round_trip = m.inverse().unwrap().inverse().unwrap();
+        assert!(ApproxEq::approx_eq(&round_trip, &m, Tolerance::default()));
+        assert!(round_trip.ulps_eq(&m, 16));
+        assert!(!m.ulps_eq(&matrix::Matrix::new(2, 3), u64::MAX));
+        crate::assert_approx_eq!(round_trip, m);
+        crate::assert_abs_diff_eq!(round_trip, m, 1e-12);
+    }
+
+    #[test]
+    fn symmetric_eigen() {
+        use crate::approx::Tolerance;
+        let m = matrix::Matrix::new_mat(vec![
+            vec![4., 1., -2., 2.],
+            vec![1., 2., 0., 1.],
+            vec![-2., 0., 3., -2.],
+            vec![2., 1., -2., -1.],
+        ]);
+        let (values, vectors) = m.symmetric_eigen().unwrap();
+        assert!(values.windows(2).all(|w| w[0] >= w[1]));
+        assert!((values.iter().sum::<f64>() - m.trace().unwrap()).abs() < 1e-12);
+        assert!(vectors.is_orthogonal(Tolerance::default()));
+        let av = matrix::Matrix::multiply(&m, &vectors).unwrap();
+        let vl =
+            matrix::Matrix::multiply(&vectors, &matrix::Matrix::from_diagonal(&values)).unwrap();
+        assert_close(&av, &vl, 1e-12);
+        assert!(matrix::Matrix::new_mat(vec![vec![1., 2.], vec![0., 1.]])
+            .symmetric_eigen()
+            .is_err());
+    }
+
+    #[test]
+    fn into_and_inplace_variants() {
+        let a = matrix::Matrix::from_fn(3, 3, |r, c| (r * 3 + c) as f64 - 4.);
+        let b = matrix::Matrix::from_fn(3, 3, |r, c| (r + 2 * c) as f64 * 0.5);
+        let mut out = matrix::Matrix::new(3, 3);
+
+        matrix::Matrix::add_into(&a, &b, &mut out).unwrap();
+        assert_close(&out, &matrix::Matrix::add(&a, &b).unwrap(), 0.);
+        matrix::Matrix::subtract_into(&a, &b, &mut out).unwrap();
+        assert_close(&out, &matrix::Matrix::subtract(&a, &b).unwrap(), 0.);
+        matrix::Matrix::multiply_into(&a, &b, &mut out).unwrap();
+        assert_close(&out, &matrix::Matrix::multiply(&a, &b).unwrap(), 0.);
+        matrix::Matrix::transpose_into(&a, &mut out).unwrap();
+        assert_close(&out, &matrix::Matrix::transpose(a.clone()), 0.);
+
+        let mut m = a.clone();
+        m.add_inplace(&b).unwrap();
+        m.subtract_inplace(&b).unwrap();
+        assert_close(&m, &a, 0.);
+        m.transpose_inplace().unwrap();
+        assert_close(&m, &matrix::Matrix::transpose(a.clone()), 0.);
+        m.scalar_mat_mul_inplace(2.).unwrap();
+        assert_close(
+            &m,
+            &matrix::Matrix::transpose(a.clone())
+                .scalar_mat_mul(2.)
+                .unwrap(),
+            0.,
+        );
+        let mut m = a.clone();
+        m.scalar_row_mul_inplace(2, -3.).unwrap();
+        assert_close(&m, &a.clone().scalar_row_mul(2, -3.).unwrap(), 0.);
+
+        let wide = matrix::Matrix::new(3, 4);
+        assert!(matrix::Matrix::add_into(&a, &wide, &mut out).is_err());
+        assert!(matrix::Matrix::multiply_into(&a, &wide, &mut out).is_err());
+        assert!(matrix::Matrix::transpose_into(&wide, &mut out).is_err());
+        assert!(m.add_inplace(&wide).is_err());
+        assert!(m.scalar_mat_mul_inplace(0.).is_err());
+        assert!(m.scalar_row_mul_inplace(3, 2.).is_err());
+        assert!(a.clone().scalar_row_mul(3, 2.).is_err());
+        assert!(matrix::Matrix::new(3, 4).transpose_inplace().is_err());
+    }
+
+    #[test]
+    fn gram_schmidt_variants() {
+        use crate::matrix::GramSchmidt;
+        // Columns of a Hilbert matrix are nearly dependent
+        let h = matrix::Matrix::from_fn(8, 8, |r, c| 1. / (r + c + 1) as f64);
+        let loss = |q: &matrix::Matrix| {
+            let qtq = matrix::Matrix::multiply(&matrix::Matrix::transpose(q.clone()), q).unwrap();
+            matrix::Matrix::subtract(&qtq, &matrix::Matrix::eye(q.cols, q.cols))
+                .unwrap()
+                .max_abs()
+        };
+        let classical = loss(&h.gram_schmidt(GramSchmidt::Classical).unwrap());
+        let modified = loss(&h.gram_schmidt(GramSchmidt::Modified).unwrap());
+        let twice = loss(&h.gram_schmidt(GramSchmidt::Reorthogonalized).unwrap());
+        assert!(classical > 1e-3);
+        assert!(modified < classical);
+        assert!(twice < 1e-14);
+
+        // The span is preserved: Q Qᵀ A = A
+        let a = matrix::Matrix::new_mat(vec![vec![2., 1.], vec![0., 3.], vec![1., -1.]]);
+        for method in [
+            GramSchmidt::Classical,
+            GramSchmidt::Modified,
+            GramSchmidt::Reorthogonalized,
+        ] {
+            let q = a.gram_schmidt(method).unwrap();
+            assert!(q.cols == 2 && loss(&q) < 1e-15);
+            let p = matrix::Matrix::multiply(&q, &matrix::Matrix::transpose(q.clone())).unwrap();
+            assert_close(&matrix::Matrix::multiply(&p, &a).unwrap(), &a, 1e-14);
+        }
+        assert!(matrix::Matrix::new(3, 2)
+            .gram_schmidt(GramSchmidt::Modified)
+            .is_err());
+    }
+
+    #[test]
+    fn projections() {
+        use crate::approx::Tolerance;
+        // Rank 2 with a dependent middle column
+        let a = matrix::Matrix::new_mat(vec![
+            vec![1., 2., 0.],
+            vec![0., 0., 1.],
+            vec![1., 2., 1.],
+            vec![0., 0., 0.],
+        ]);
+        assert_eq!(a.column_space_basis().cols, 2);
+        let p = a.projection_matrix();
+        assert!(p.is_symmetric(Tolerance::default()));
+        assert!(p.is_idempotent(Tolerance::default()));
+        assert!((p.trace().unwrap() - 2.).abs() < 1e-14);
+        assert_close(&matrix::Matrix::multiply(&p, &a).unwrap(), &a, 1e-14);
+
+        let v = [1., 2., 3., 4.];
+        let (proj, orth) = a.decompose(&v).unwrap();
+        let pv =
+            matrix::Matrix::multiply(&p, &matrix::Matrix::from_cols(vec![v.to_vec()]).unwrap())
+                .unwrap()
+                .get_col(0);
+        for i in 0..4 {
+            assert!((proj[i] - pv[i]).abs() < 1e-14);
+            assert!((proj[i] + orth[i] - v[i]).abs() < 1e-15);
+        }
+        for c in 0..a.cols {
+            assert!(matrix::Matrix::dot_product(&orth, &a.get_col(c)).abs() < 1e-14);
+        }
+        assert_eq!(a.project(&v).unwrap(), proj);
+        assert!(a.decompose(&[1., 2.]).is_err());
+        assert_eq!(matrix::Matrix::new(3, 2).projection_matrix().max_abs(), 0.);
+    }
+
+    #[test]
+    fn hessenberg_schur_tridiagonal() {
+        use crate::approx::Tolerance;
+        use crate::random::{self, Rng};
+        let mut rng = Rng::new(45);
+        let reconstruct = |q: &matrix::Matrix, t: &matrix::Matrix| {
+            let qt = matrix::Matrix::multiply(q, t).unwrap();
+            matrix::Matrix::multiply(&qt, &matrix::Matrix::transpose(q.clone())).unwrap()
+        };
+        for n in 1..9 {
+            let a = random::normal(n, n, 0., 1., &mut rng).unwrap();
+            let (q, h) = a.hessenberg().unwrap();
+            assert!(q.is_orthogonal(Tolerance::default()));
+            assert_close(&reconstruct(&q, &h), &a, 1e-12);
+            for r in 0..n {
+                for c in 0..r.saturating_sub(1) {
+                    assert_eq!(h[(r, c)], 0.);
+                }
+            }
+
+            let (q, t) = a.schur().unwrap();
+            assert!(q.is_orthogonal(Tolerance::default()));
+            assert_close(&reconstruct(&q, &t), &a, 1e-11);
+            for r in 0..n {
+                for c in 0..r.saturating_sub(1) {
+                    assert_eq!(t[(r, c)], 0.);
+                }
+            }
+            // Every remaining 2x2 block holds a complex conjugate pair
+            for k in 1..n {
+                if t[(k, k - 1)] != 0. {
+                    assert!(k < 2 || t[(k - 1, k - 2)] == 0.);
+                    assert!(k + 1 >= n || t[(k + 1, k)] == 0.);
+                    let half = (t[(k - 1, k - 1)] - t[(k, k)]) / 2.;
+                    assert!(half * half + t[(k - 1, k)] * t[(k, k - 1)] < 0.);
+                }
+            }
+        }
+
+        // Known real eigenvalues 1..5 through a similarity transform
+        let s = random::normal(5, 5, 0., 1., &mut rng).unwrap();
+        let d = matrix::Matrix::from_diagonal(&[1., 2., 3., 4., 5.]);
+        let a = matrix::Matrix::multiply(
+            &matrix::Matrix::multiply(&s, &d).unwrap(),
+            &s.inverse().unwrap(),
+        )
+        .unwrap();
+        let (_, t) = a.schur().unwrap();
+        let mut eigenvalues: Vec<f64> = (0..5).map(|i| t[(i, i)]).collect();
+        eigenvalues.sort_by(f64::total_cmp);
+        for (i, e) in eigenvalues.iter().enumerate() {
+            assert!((e - (i + 1) as f64).abs() < 1e-8);
+        }
+        assert!(matrix::Matrix::new(2, 3).schur().is_err());
+        let empty = matrix::Matrix::from_fn(0, 0, |_, _| 0.);
+        for (q, t) in [empty.hessenberg().unwrap(), empty.schur().unwrap()] {
+            assert_eq!((q.rows, q.cols, t.rows, t.cols), (0, 0, 0, 0));
+        }
+
+        let spd = random::symmetric_positive_definite(6, &mut rng);
+        let (q, t) = spd.tridiagonalize().unwrap();
+        assert_close(&reconstruct(&q, &t.to_matrix()), &spd, 1e-12);
+        let (values, _) = spd.symmetric_eigen().unwrap();
+        let (_, schur) = t.to_matrix().schur().unwrap();
+        let mut diag: Vec<f64> = (0..6).map(|i| schur[(i, i)]).collect();
+        diag.sort_by(|a, b| b.total_cmp(a));
+        for (a, b) in values.iter().zip(diag.iter()) {
+            assert!((a - b).abs() < 1e-10);
+        }
+        assert!(matrix::Matrix::new_mat(vec![vec![1., 2.], vec![0., 1.]])
+            .tridiagonalize()
+            .is_err());
+    }
+
+    #[test]
+    fn sylvester_and_lyapunov() {
+        use crate::approx::Tolerance;
+        use crate::random::{self, Rng};
+        let mut rng = Rng::new(46);
+        let mul = |a: &matrix::Matrix, b: &matrix::Matrix| matrix::Matrix::multiply(a, b).unwrap();
+        let tr = |a: &matrix::Matrix| matrix::Matrix::transpose(a.clone());
+        for (n, m) in [(1, 1), (2, 3), (4, 1), (5, 5), (7, 6)] {
+            let a = random::normal(n, n, 0., 1., &mut rng).unwrap();
+            let b = random::normal(m, m, 0., 1., &mut rng).unwrap();
+            let c = random::normal(n, m, 0., 1., &mut rng).unwrap();
+            let x = a.solve_sylvester(&b, &c).unwrap();
+            let lhs = matrix::Matrix::add(&mul(&a, &x), &mul(&x, &b)).unwrap();
+            assert_close(&lhs, &c, 1e-9);
+        }
+        for (n, m) in [(0, 0), (0, 3), (2, 0)] {
+            let a = random::normal(n, n, 0., 1., &mut rng).unwrap();
+            let b = random::normal(m, m, 0., 1., &mut rng).unwrap();
+            let x = a.solve_sylvester(&b, &matrix::Matrix::new(n, m)).unwrap();
+            assert_eq!((x.rows, x.cols), (n, m));
+        }
+
+        // Controllability Gramian of a stable system: A W + W Aᵀ + B Bᵀ = 0
+        let p = random::symmetric_positive_definite(5, &mut rng);
+        let g = random::normal(5, 5, 0., 1., &mut rng).unwrap();
+        let skew = matrix::Matrix::subtract(&g, &tr(&g)).unwrap();
+        let a = matrix::Matrix::subtract(&skew, &p).unwrap();
+        let bm = random::normal(5, 2, 0., 1., &mut rng).unwrap();
+        let q = mul(&bm, &tr(&bm));
+        let w = a.solve_lyapunov(&q).unwrap();
+        assert!(w.is_symmetric(Tolerance::exact()));
+        let residual = matrix::Matrix::add(
+            &matrix::Matrix::add(&mul(&a, &w), &mul(&w, &tr(&a))).unwrap(),
+            &q,
+        )
+        .unwrap();
+        assert!(residual.max_abs() < 1e-10);
+        let (values, _) = w.symmetric_eigen().unwrap();
+        assert!(values.iter().all(|v| *v > -1e-10));
+
+        // Discrete: A X Aᵀ - X + Q = 0 for a Schur stable A
+        let g = random::normal(6, 6, 0., 1., &mut rng).unwrap();
+        let a = g.map(|v| v * 0.9 / g.norm_spectral());
+        let q = random::symmetric_positive_definite(6, &mut rng);
+        let x = a.solve_discrete_lyapunov(&q).unwrap();
+        let residual = matrix::Matrix::add(
+            &matrix::Matrix::subtract(&mul(&mul(&a, &x), &tr(&a)), &x).unwrap(),
+            &q,
+        )
+        .unwrap();
+        assert!(residual.max_abs() < 1e-9);
+        assert!(x.is_positive_definite(Tolerance::default()));
+
+        let eye = matrix::Matrix::eye(2, 2);
+        let c = matrix::Matrix::ones(2, 2);
+        assert!(eye.solve_sylvester(&eye.map(|v| -v), &c).is_err());
+        assert!(eye
+            .solve_sylvester(&eye, &matrix::Matrix::ones(2, 3))
+            .is_err());
+        assert!(eye.map(|v| -v).solve_discrete_lyapunov(&c).is_err());
+
+        // A non-symmetric Q has no symmetric solution
+        let a = matrix::Matrix::new_mat(vec![vec![-1., 0.], vec![0., -2.]]);
+        let q = matrix::Matrix::new_mat(vec![vec![0., 1.], vec![0., 0.]]);
+        assert!(a.solve_lyapunov(&q).is_err());
+        assert!(a.map(|v| v / 4.).solve_discrete_lyapunov(&q).is_err());
+        assert!(a.solve_lyapunov(&matrix::Matrix::ones(2, 3)).is_err());
+    }
 }
@@ -1,22 +1,26 @@
+use crate::approx::{ApproxEq, Tolerance};
+use crate::scalar::Real;
 use std::fmt;
 use std::fmt::Formatter;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 ///Point represents a unique position in
-/// the 2D coordinate system
+/// the 2D coordinate system.
+/// Coordinates are f64 unless another `Real` type, such as `Dual`
+/// or `Interval`, is given.
 ///
 /// # Examples
 /// (x: 1.,y: 1.)
-pub struct Point {
-    pub x: f64,
-    pub y: f64,
+pub struct Point<T = f64> {
+    pub x: T,
+    pub y: T,
 }
-impl fmt::Display for Point {
+impl<T: fmt::Display> fmt::Display for Point<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "({}, {})", self.x, self.y)
     }
 }
-impl Point {
+impl<T: Real> Point<T> {
     /// Returns a new instance of 2D Point
     ///
     /// # Arguments
@@ -26,9 +30,12 @@ impl Point {
     /// use ralgeb::point::Point;
     /// let pt = Point::new(1., 1.);
     /// ```
-    pub fn new(x: f64, y: f64) -> Point {
+    pub fn new(x: T, y: T) -> Point<T> {
         Point { x, y }
     }
+}
+
+impl Point {
     /// Returns a point at the origin
     ///
     /// ```
@@ -40,6 +47,24 @@ impl Point {
     }
 }
 
+impl<T: ApproxEq> ApproxEq for Point<T> {
+    /// Compares both coordinates
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::assert_approx_eq;
+    /// use ralgeb::point::Point;
+    /// assert_approx_eq!(Point::new(0.1 + 0.2, 1.), Point::new(0.3, 1.));
+    /// ```
+    fn approx_eq(&self, other: &Point<T>, tolerance: Tolerance) -> bool {
+        self.x.approx_eq(&other.x, tolerance) && self.y.approx_eq(&other.y, tolerance)
+    }
+
+    fn ulps_eq(&self, other: &Point<T>, ulps: u64) -> bool {
+        self.x.ulps_eq(&other.x, ulps) && self.y.ulps_eq(&other.y, ulps)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::point;
@@ -57,4 +82,12 @@ mod tests {
         assert_eq!(origin.x, pt2.x);
         assert_eq!(origin.y, pt2.y);
     }
+    #[test]
+    fn approx_eq() {
+        use crate::approx::{ApproxEq, Tolerance};
+        let pt = point::Point::new(1., 2.);
+        assert!(pt.approx_eq(&point::Point::new(1. + 1e-14, 2.), Tolerance::default()));
+        assert!(!pt.approx_eq(&point::Point::new(1., 2.1), Tolerance::default()));
+        assert!(pt.ulps_eq(&point::Point::new(1., 2.), 0));
+    }
 }
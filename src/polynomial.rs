@@ -0,0 +1,240 @@
+use crate::matrix::{Matrix, MatrixError};
+use crate::scalar::Scalar;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents a polynomial c0 + c1 x + c2 x^2 + ... with coefficients
+/// of any `Scalar` type.
+/// The coefficients are stored in increasing order of degree and
+/// trailing zeros are removed, so the zero polynomial has no coefficients.
+pub struct Polynomial<T = f64> {
+    coeffs: Vec<T>,
+}
+
+impl<T: Scalar> Polynomial<T> {
+    /// Creates a polynomial from its coefficients
+    ///
+    /// # Arguments
+    /// `coeffs` - The coefficients, starting with the constant term.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::polynomial::Polynomial;
+    /// // 1 + 2x + 3x^2
+    /// let p = Polynomial::new(vec![1., 2., 3., 0.]);
+    /// assert_eq!(p.coefficients(), &[1., 2., 3.]);
+    /// ```
+    pub fn new(mut coeffs: Vec<T>) -> Polynomial<T> {
+        while coeffs.last() == Some(&T::zero()) {
+            coeffs.pop();
+        }
+        Polynomial { coeffs }
+    }
+
+    /// Returns the coefficients, starting with the constant term
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::polynomial::Polynomial;
+    /// assert!(Polynomial::<f64>::new(vec![0.]).coefficients().is_empty());
+    /// ```
+    pub fn coefficients(&self) -> &[T] {
+        &self.coeffs
+    }
+
+    /// Returns the coefficient of x^power, 0 beyond the degree
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![1., 2.]);
+    /// assert_eq!(p.coeff(1), 2.);
+    /// assert_eq!(p.coeff(5), 0.);
+    /// ```
+    pub fn coeff(&self, power: usize) -> T {
+        self.coeffs.get(power).copied().unwrap_or_else(T::zero)
+    }
+
+    /// Returns the degree, or None for the zero polynomial
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::polynomial::Polynomial;
+    /// assert_eq!(Polynomial::new(vec![1., 0., 3.]).degree(), Some(2));
+    /// assert_eq!(Polynomial::<f64>::new(vec![]).degree(), None);
+    /// ```
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    /// Returns whether the leading coefficient is 1
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::polynomial::Polynomial;
+    /// assert!(Polynomial::new(vec![5., 1.]).is_monic());
+    /// ```
+    pub fn is_monic(&self) -> bool {
+        self.coeffs.last() == Some(&T::one())
+    }
+
+    /// Evaluates the polynomial at x using Horner's method
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![1., 2., 3.]);
+    /// assert_eq!(p.evaluate(2.), 17.);
+    /// ```
+    pub fn evaluate(&self, x: T) -> T {
+        self.coeffs
+            .iter()
+            .rev()
+            .fold(T::zero(), |acc, c| acc * x + *c)
+    }
+
+    /// Evaluates the polynomial at a square matrix,
+    /// the constant term is multiplied by the identity matrix
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// use ralgeb::polynomial::Polynomial;
+    /// let a = Matrix::new_mat(vec![vec![0., 1.], vec![0., 0.]]);
+    /// // a is nilpotent so x^2 vanishes on it
+    /// let p = Polynomial::new(vec![0., 0., 1.]);
+    /// assert_eq!(p.evaluate_matrix(&a).unwrap().max_abs(), 0.);
+    /// ```
+    pub fn evaluate_matrix(&self, a: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        if !a.is_square() {
+            return Err(MatrixError::new(
+                "The matrix is not a square matrix".to_string(),
+            ));
+        }
+        let n = a.rows;
+        let mut result = Matrix::zeros(n, n);
+        for c in self.coeffs.iter().rev() {
+            result = Matrix::multiply(&result, a)?;
+            for i in 0..n {
+                result[(i, i)] = result[(i, i)] + *c;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Adds 2 polynomials
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![1., 1.]);
+    /// let q = Polynomial::new(vec![0., -1., 2.]);
+    /// assert_eq!(Polynomial::add(&p, &q).coefficients(), &[1., 0., 2.]);
+    /// ```
+    pub fn add(p: &Polynomial<T>, q: &Polynomial<T>) -> Polynomial<T> {
+        let len = p.coeffs.len().max(q.coeffs.len());
+        Polynomial::new((0..len).map(|i| p.coeff(i) + q.coeff(i)).collect())
+    }
+
+    /// Multiplies 2 polynomials
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![-1., 1.]);
+    /// let q = Polynomial::new(vec![1., 1.]);
+    /// assert_eq!(Polynomial::multiply(&p, &q).coefficients(), &[-1., 0., 1.]);
+    /// ```
+    pub fn multiply(p: &Polynomial<T>, q: &Polynomial<T>) -> Polynomial<T> {
+        if p.coeffs.is_empty() || q.coeffs.is_empty() {
+            return Polynomial::new(vec![]);
+        }
+        let mut res = vec![T::zero(); p.coeffs.len() + q.coeffs.len() - 1];
+        for (i, a) in p.coeffs.iter().enumerate() {
+            for (j, b) in q.coeffs.iter().enumerate() {
+                res[i + j] = res[i + j] + *a * *b;
+            }
+        }
+        Polynomial::new(res)
+    }
+
+    /// Divides by another polynomial, returning the quotient and the remainder.
+    /// Returns None when dividing by the zero polynomial.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![-1., 0., 1.]);
+    /// let d = Polynomial::new(vec![1., 1.]);
+    /// let (q, r) = p.div_rem(&d).unwrap();
+    /// assert_eq!(q.coefficients(), &[-1., 1.]);
+    /// assert_eq!(r.degree(), None);
+    /// ```
+    pub fn div_rem(&self, divisor: &Polynomial<T>) -> Option<(Polynomial<T>, Polynomial<T>)> {
+        let d = divisor.degree()?;
+        let lead = divisor.coeffs[d];
+        let mut rem = self.coeffs.clone();
+        if rem.len() <= d {
+            return Some((Polynomial::new(vec![]), self.clone()));
+        }
+        let mut quot = vec![T::zero(); rem.len() - d];
+        for k in (0..quot.len()).rev() {
+            let q = rem[k + d] / lead;
+            quot[k] = q;
+            for (j, c) in divisor.coeffs.iter().enumerate() {
+                rem[k + j] = rem[k + j] - q * *c;
+            }
+            rem[k + d] = T::zero();
+        }
+        rem.truncate(d);
+        Some((Polynomial::new(quot), Polynomial::new(rem)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix;
+    use crate::polynomial::Polynomial;
+    use crate::rational::Rational;
+
+    #[test]
+    fn basics() {
+        let p = Polynomial::new(vec![2., 0., -1., 0., 0.]);
+        assert_eq!(p.degree(), Some(2));
+        assert_eq!(p.coeff(0), 2.);
+        assert!(!p.is_monic());
+        assert_eq!(p.evaluate(3.), -7.);
+        let zero = Polynomial::<f64>::new(vec![]);
+        assert_eq!(zero.evaluate(10.), 0.);
+        assert_eq!(Polynomial::multiply(&p, &zero), zero);
+        assert!(p.div_rem(&zero).is_none());
+    }
+
+    #[test]
+    fn exact_division() {
+        let q = |n| Rational::from_integer(n);
+        // (x^3 - 1) / (2x - 2) = x^2/2 + x/2 + 1/2
+        let p = Polynomial::new(vec![q(-1), q(0), q(0), q(1)]);
+        let d = Polynomial::new(vec![q(-2), q(2)]);
+        let (quot, rem) = p.div_rem(&d).unwrap();
+        let half = Rational::new(1, 2);
+        assert_eq!(quot.coefficients(), &[half, half, half]);
+        assert_eq!(rem.degree(), None);
+        let back = Polynomial::add(&Polynomial::multiply(&quot, &d), &rem);
+        assert_eq!(back, p);
+
+        let (quot, rem) = d.div_rem(&p).unwrap();
+        assert_eq!(quot.degree(), None);
+        assert_eq!(rem, d);
+    }
+
+    #[test]
+    fn matrix_evaluation() {
+        let a = matrix::Matrix::new_mat(vec![vec![1., 2.], vec![3., 4.]]);
+        // 2 + x^2
+        let p = Polynomial::new(vec![2., 0., 1.]);
+        let r = p.evaluate_matrix(&a).unwrap();
+        assert_eq!(r.get_row(0).unwrap(), vec![9., 10.]);
+        assert_eq!(r.get_row(1).unwrap(), vec![15., 24.]);
+        assert!(p.evaluate_matrix(&matrix::Matrix::new(2, 3)).is_err());
+    }
+}
@@ -0,0 +1,347 @@
+use crate::matrix::{GramSchmidt, Matrix, MatrixError};
+
+#[derive(Debug, Clone, PartialEq)]
+/// A small seedable pseudo random number generator (xoshiro256**).
+/// The same seed always produces the same sequence, which makes
+/// random matrices reproducible across runs and platforms.
+/// It is not suitable for cryptography.
+pub struct Rng {
+    state: [u64; 4],
+}
+
+impl Rng {
+    /// Creates a generator from a seed.
+    /// The seed is expanded with SplitMix64 so that nearby seeds
+    /// give unrelated sequences.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::random::Rng;
+    /// let mut a = Rng::new(42);
+    /// let mut b = Rng::new(42);
+    /// assert_eq!(a.next_u64(), b.next_u64());
+    /// ```
+    pub fn new(seed: u64) -> Rng {
+        let mut s = seed;
+        let mut split_mix = || {
+            s = s.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = s;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        };
+        Rng {
+            state: [split_mix(), split_mix(), split_mix(), split_mix()],
+        }
+    }
+
+    /// Returns the next 64 random bits
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::random::Rng;
+    /// let mut rng = Rng::new(3);
+    /// assert_ne!(rng.next_u64(), rng.next_u64());
+    /// ```
+    pub fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    /// Returns a uniformly distributed value in [0, 1)
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::random::Rng;
+    /// let mut rng = Rng::new(1);
+    /// let x = rng.next_f64();
+    /// assert!((0. ..1.).contains(&x));
+    /// ```
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns a uniformly distributed value in [low, high).
+    /// The bounds and their difference should be finite.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::random::Rng;
+    /// let mut rng = Rng::new(5);
+    /// let x = rng.uniform(2., 3.);
+    /// assert!((2. ..3.).contains(&x));
+    /// ```
+    pub fn uniform(&mut self, low: f64, high: f64) -> f64 {
+        low + (high - low) * self.next_f64()
+    }
+
+    /// Returns a normally distributed value using the Box-Muller transform
+    ///
+    /// # Arguments
+    /// `mean` - The mean of the distribution.
+    /// `std_dev` - The standard deviation of the distribution.
+    pub fn normal(&mut self, mean: f64, std_dev: f64) -> f64 {
+        // 1 - u lies in (0, 1] so the logarithm is finite
+        let u1 = 1. - self.next_f64();
+        let u2 = self.next_f64();
+        let z = (-2. * u1.ln()).sqrt() * (2. * std::f64::consts::PI * u2).cos();
+        mean + std_dev * z
+    }
+}
+
+/// Returns a matrix with elements drawn uniformly from [low, high)
+///
+/// # Examples
+/// ```
+/// use ralgeb::random::{self, Rng};
+/// let mut rng = Rng::new(7);
+/// let m = random::uniform(3, 2, -1., 1., &mut rng).unwrap();
+/// assert!(m.max_abs() < 1.);
+/// ```
+pub fn uniform(
+    rows: usize,
+    cols: usize,
+    low: f64,
+    high: f64,
+    rng: &mut Rng,
+) -> Result<Matrix, MatrixError> {
+    if low.is_nan() || high.is_nan() || low > high {
+        return Err(MatrixError::new(format!(
+            "The interval [{}, {}) is empty",
+            low, high
+        )));
+    }
+    if !(high - low).is_finite() {
+        return Err(MatrixError::new(format!(
+            "The interval [{}, {}) should have a finite width",
+            low, high
+        )));
+    }
+    Ok(Matrix::from_fn(rows, cols, |_, _| rng.uniform(low, high)))
+}
+
+/// Returns a matrix with normally distributed elements
+///
+/// # Examples
+/// ```
+/// use ralgeb::random::{self, Rng};
+/// let mut rng = Rng::new(7);
+/// let m = random::normal(100, 100, 5., 2., &mut rng).unwrap();
+/// assert!((m.mean().unwrap() - 5.).abs() < 0.1);
+/// ```
+pub fn normal(
+    rows: usize,
+    cols: usize,
+    mean: f64,
+    std_dev: f64,
+    rng: &mut Rng,
+) -> Result<Matrix, MatrixError> {
+    if std_dev.is_nan() || std_dev < 0. {
+        return Err(MatrixError::new(format!(
+            "The standard deviation {} is negative",
+            std_dev
+        )));
+    }
+    Ok(Matrix::from_fn(rows, cols, |_, _| {
+        rng.normal(mean, std_dev)
+    }))
+}
+
+/// Returns a random n x n orthogonal matrix, distributed uniformly
+/// (Haar measure) over the orthogonal group.
+/// Built by orthonormalizing the columns of a Gaussian matrix,
+/// which keeps the diagonal of R positive.
+///
+/// # Examples
+/// ```
+/// use ralgeb::approx::Tolerance;
+/// use ralgeb::random::{self, Rng};
+/// let q = random::orthogonal(4, &mut Rng::new(3));
+/// assert!(q.is_orthogonal(Tolerance::default()));
+/// ```
+pub fn orthogonal(n: usize, rng: &mut Rng) -> Matrix {
+    loop {
+        let g = Matrix::from_fn(n, n, |_, _| rng.normal(0., 1.));
+        if let Ok(q) = g.gram_schmidt(GramSchmidt::Modified) {
+            return q;
+        }
+    }
+}
+
+/// Returns a random n x n symmetric positive definite matrix
+/// BᵀB + nI, where B has standard normal elements
+///
+/// # Examples
+/// ```
+/// use ralgeb::approx::Tolerance;
+/// use ralgeb::random::{self, Rng};
+/// let m = random::symmetric_positive_definite(5, &mut Rng::new(3));
+/// assert!(m.is_positive_definite(Tolerance::default()));
+/// ```
+pub fn symmetric_positive_definite(n: usize, rng: &mut Rng) -> Matrix {
+    let b = Matrix::from_fn(n, n, |_, _| rng.normal(0., 1.));
+    let mut m = Matrix::multiply(&Matrix::transpose(b.clone()), &b).unwrap();
+    for i in 0..n {
+        m[(i, i)] += n as f64;
+    }
+    // Make the result exactly symmetric
+    Matrix::from_fn(n, n, |r, c| if r > c { m[(c, r)] } else { m[(r, c)] })
+}
+
+/// Returns a random sparse matrix where each element is non zero
+/// with probability `density`. The non zero elements are standard normal.
+///
+/// # Examples
+/// ```
+/// use ralgeb::random::{self, Rng};
+/// let m = random::sparse(50, 50, 0.1, &mut Rng::new(3)).unwrap();
+/// let nnz = m.map(|x| if x != 0. { 1. } else { 0. }).sum();
+/// assert!(nnz > 150. && nnz < 350.);
+/// ```
+pub fn sparse(
+    rows: usize,
+    cols: usize,
+    density: f64,
+    rng: &mut Rng,
+) -> Result<Matrix, MatrixError> {
+    if !(0. ..=1.).contains(&density) {
+        return Err(MatrixError::new(format!(
+            "The density {} is not in [0, 1]",
+            density
+        )));
+    }
+    Ok(Matrix::from_fn(rows, cols, |_, _| {
+        if rng.next_f64() < density {
+            rng.normal(0., 1.)
+        } else {
+            0.
+        }
+    }))
+}
+
+/// Returns a random rows x cols matrix U Σ Vᵀ with the given singular values,
+/// where U and V are random orthogonal matrices
+///
+/// # Arguments
+/// `singular_values` - min(rows, cols) non negative values.
+///
+/// # Examples
+/// ```
+/// use ralgeb::random::{self, Rng};
+/// let m = random::with_singular_values(3, 3, &[10., 1., 0.1], &mut Rng::new(3)).unwrap();
+/// assert!((m.norm_spectral() - 10.).abs() < 1e-9);
+/// ```
+pub fn with_singular_values(
+    rows: usize,
+    cols: usize,
+    singular_values: &[f64],
+    rng: &mut Rng,
+) -> Result<Matrix, MatrixError> {
+    if singular_values.len() != rows.min(cols) {
+        return Err(MatrixError::new(format!(
+            "A {}x{} matrix has {} singular values but {} were given",
+            rows,
+            cols,
+            rows.min(cols),
+            singular_values.len()
+        )));
+    }
+    if singular_values.iter().any(|s| s.is_nan() || *s < 0.) {
+        return Err(MatrixError::new(
+            "The singular values should be non negative".to_string(),
+        ));
+    }
+    let u = orthogonal(rows, rng);
+    let v = orthogonal(cols, rng);
+    let sigma = Matrix::from_fn(
+        rows,
+        cols,
+        |r, c| {
+            if r == c {
+                singular_values[r]
+            } else {
+                0.
+            }
+        },
+    );
+    Matrix::multiply(&Matrix::multiply(&u, &sigma)?, &Matrix::transpose(v))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::approx::{ApproxEq, Tolerance};
+    use crate::random::{self, Rng};
+
+    #[test]
+    fn reproducible() {
+        let a = random::normal(3, 3, 0., 1., &mut Rng::new(123)).unwrap();
+        let b = random::normal(3, 3, 0., 1., &mut Rng::new(123)).unwrap();
+        let c = random::normal(3, 3, 0., 1., &mut Rng::new(124)).unwrap();
+        assert!(a.approx_eq(&b, Tolerance::exact()));
+        assert!(!a.approx_eq(&c, Tolerance::default()));
+
+        // Reference values of xoshiro256** seeded through SplitMix64
+        let mut rng = Rng::new(0);
+        assert_eq!(rng.next_u64(), 0x99EC_5F36_CB75_F2B4);
+    }
+
+    #[test]
+    fn distributions() {
+        let mut rng = Rng::new(99);
+        let n = 20_000;
+        let u: Vec<f64> = (0..n).map(|_| rng.uniform(2., 4.)).collect();
+        assert!(u.iter().all(|x| (2. ..4.).contains(x)));
+        let mean = u.iter().sum::<f64>() / n as f64;
+        assert!((mean - 3.).abs() < 0.02);
+
+        let z: Vec<f64> = (0..n).map(|_| rng.normal(1., 3.)).collect();
+        let mean = z.iter().sum::<f64>() / n as f64;
+        let var = z.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+        assert!((mean - 1.).abs() < 0.1);
+        assert!((var.sqrt() - 3.).abs() < 0.1);
+
+        assert!(random::uniform(2, 2, 1., 0., &mut rng).is_err());
+        assert!(random::uniform(2, 2, 0., f64::INFINITY, &mut rng).is_err());
+        assert!(random::uniform(2, 2, f64::NEG_INFINITY, 0., &mut rng).is_err());
+        assert!(random::uniform(2, 2, -f64::MAX, f64::MAX, &mut rng).is_err());
+        assert!(random::normal(2, 2, 0., -1., &mut rng).is_err());
+        assert!(random::sparse(2, 2, 1.5, &mut rng).is_err());
+    }
+
+    #[test]
+    fn structured() {
+        let mut rng = Rng::new(5);
+        let tol = Tolerance::default();
+        for n in 1..6 {
+            assert!(random::orthogonal(n, &mut rng).is_orthogonal(tol));
+            let spd = random::symmetric_positive_definite(n, &mut rng);
+            assert!(spd.is_symmetric(Tolerance::exact()));
+            assert!(spd.is_positive_definite(tol));
+        }
+        assert_eq!(random::sparse(4, 4, 0., &mut rng).unwrap().max_abs(), 0.);
+        let dense = random::sparse(4, 4, 1., &mut rng).unwrap();
+        assert_eq!(dense.map(|x| if x != 0. { 1. } else { 0. }).sum(), 16.);
+    }
+
+    #[test]
+    fn singular_values() {
+        let mut rng = Rng::new(11);
+        let m = random::with_singular_values(4, 2, &[3., 0.5], &mut rng).unwrap();
+        assert_eq!((m.rows, m.cols), (4, 2));
+        assert!((m.norm_spectral() - 3.).abs() < 1e-9);
+        assert!((m.norm_frobenius() - (9.25f64).sqrt()).abs() < 1e-9);
+
+        let singular = random::with_singular_values(3, 3, &[2., 1., 0.], &mut rng).unwrap();
+        assert_eq!(singular.rank(), 2);
+        assert!(random::with_singular_values(3, 3, &[1., 1.], &mut rng).is_err());
+        assert!(random::with_singular_values(2, 2, &[1., -1.], &mut rng).is_err());
+    }
+}
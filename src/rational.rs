@@ -0,0 +1,363 @@
+use crate::scalar::Scalar;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Rational represents an exact fraction numer/denom.
+/// It is always kept normalized: the denominator is positive
+/// and shares no factor with the numerator.
+///
+/// Arithmetic panics on overflow of i64, like the integer types in debug builds.
+/// Use the `checked_*` methods to detect it instead.
+///
+/// # Examples
+/// 6/-14 is stored as -3/7
+pub struct Rational {
+    numer: i64,
+    denom: i64,
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denom == 1 {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+    a.abs()
+}
+
+impl Rational {
+    /// Returns the normalized fraction numer/denom
+    ///
+    /// # Arguments
+    /// * `numer` - The numerator
+    /// * `denom` - The denominator. It should be non-zero
+    ///
+    /// ```
+    /// use ralgeb::rational::Rational;
+    /// let q = Rational::new(6, -14);
+    /// assert_eq!(q.to_string(), "-3/7");
+    /// ```
+    pub fn new(numer: i64, denom: i64) -> Rational {
+        match Rational::checked_new(numer, denom) {
+            Some(q) => q,
+            None => panic!("Invalid rational {}/{}", numer, denom),
+        }
+    }
+
+    /// Returns the normalized fraction numer/denom or None
+    /// if the denominator is 0 or the result does not fit in i64
+    ///
+    /// ```
+    /// use ralgeb::rational::Rational;
+    /// assert!(Rational::checked_new(1, 0).is_none());
+    /// ```
+    pub fn checked_new(numer: i64, denom: i64) -> Option<Rational> {
+        Rational::from_i128(numer as i128, denom as i128)
+    }
+
+    /// Returns the integer n as the fraction n/1
+    ///
+    /// ```
+    /// use ralgeb::rational::Rational;
+    /// assert_eq!(Rational::from_integer(4).to_string(), "4");
+    /// ```
+    pub fn from_integer(n: i64) -> Rational {
+        Rational { numer: n, denom: 1 }
+    }
+
+    /// Returns the numerator of the normalized fraction
+    ///
+    /// ```
+    /// use ralgeb::rational::Rational;
+    /// assert_eq!(Rational::new(4, -6).numer(), -2);
+    /// ```
+    pub fn numer(&self) -> i64 {
+        self.numer
+    }
+
+    /// Returns the (positive) denominator of the normalized fraction
+    ///
+    /// ```
+    /// use ralgeb::rational::Rational;
+    /// assert_eq!(Rational::new(4, -6).denom(), 3);
+    /// ```
+    pub fn denom(&self) -> i64 {
+        self.denom
+    }
+
+    /// Returns whether the fraction is a whole number
+    ///
+    /// ```
+    /// use ralgeb::rational::Rational;
+    /// assert!(Rational::new(4, 2).is_integer());
+    /// ```
+    pub fn is_integer(&self) -> bool {
+        self.denom == 1
+    }
+
+    /// Returns the nearest f64 value
+    ///
+    /// ```
+    /// use ralgeb::rational::Rational;
+    /// assert_eq!(Rational::new(-3, 4).to_f64(), -0.75);
+    /// ```
+    pub fn to_f64(&self) -> f64 {
+        self.numer as f64 / self.denom as f64
+    }
+
+    /// Returns the absolute value
+    ///
+    /// ```
+    /// use ralgeb::rational::Rational;
+    /// assert_eq!(Rational::new(-3, 4).abs(), Rational::new(3, 4));
+    /// ```
+    pub fn abs(&self) -> Rational {
+        Rational {
+            numer: self.numer.abs(),
+            denom: self.denom,
+        }
+    }
+
+    /// Returns the reciprocal denom/numer.
+    /// Panics if the fraction is 0.
+    ///
+    /// ```
+    /// use ralgeb::rational::Rational;
+    /// assert_eq!(Rational::new(-3, 4).recip(), Rational::new(-4, 3));
+    /// ```
+    pub fn recip(&self) -> Rational {
+        Rational::new(self.denom, self.numer)
+    }
+
+    /// Adds 2 fractions, returning None on overflow
+    ///
+    /// ```
+    /// use ralgeb::rational::Rational;
+    /// let q = Rational::new(1, 2).checked_add(Rational::new(1, 3));
+    /// assert_eq!(q, Some(Rational::new(5, 6)));
+    /// assert!(Rational::from_integer(i64::MAX).checked_add(Rational::from_integer(1)).is_none());
+    /// ```
+    pub fn checked_add(self, rhs: Rational) -> Option<Rational> {
+        let (a, b) = (self.numer as i128, self.denom as i128);
+        let (c, d) = (rhs.numer as i128, rhs.denom as i128);
+        Rational::from_i128(a * d + c * b, b * d)
+    }
+
+    /// Subtracts 2 fractions, returning None on overflow
+    ///
+    /// ```
+    /// use ralgeb::rational::Rational;
+    /// let q = Rational::new(1, 2).checked_sub(Rational::new(1, 3));
+    /// assert_eq!(q, Some(Rational::new(1, 6)));
+    /// ```
+    pub fn checked_sub(self, rhs: Rational) -> Option<Rational> {
+        let (a, b) = (self.numer as i128, self.denom as i128);
+        let (c, d) = (rhs.numer as i128, rhs.denom as i128);
+        Rational::from_i128(a * d - c * b, b * d)
+    }
+
+    /// Multiplies 2 fractions, returning None on overflow
+    ///
+    /// ```
+    /// use ralgeb::rational::Rational;
+    /// let q = Rational::new(2, 3).checked_mul(Rational::new(9, 4));
+    /// assert_eq!(q, Some(Rational::new(3, 2)));
+    /// ```
+    pub fn checked_mul(self, rhs: Rational) -> Option<Rational> {
+        Rational::from_i128(
+            self.numer as i128 * rhs.numer as i128,
+            self.denom as i128 * rhs.denom as i128,
+        )
+    }
+
+    /// Divides 2 fractions, returning None on overflow or division by 0
+    ///
+    /// ```
+    /// use ralgeb::rational::Rational;
+    /// let q = Rational::new(2, 3).checked_div(Rational::new(4, 9));
+    /// assert_eq!(q, Some(Rational::new(3, 2)));
+    /// assert!(Rational::new(2, 3).checked_div(Rational::from_integer(0)).is_none());
+    /// ```
+    pub fn checked_div(self, rhs: Rational) -> Option<Rational> {
+        Rational::from_i128(
+            self.numer as i128 * rhs.denom as i128,
+            self.denom as i128 * rhs.numer as i128,
+        )
+    }
+
+    fn from_i128(numer: i128, denom: i128) -> Option<Rational> {
+        if denom == 0 {
+            return None;
+        }
+        let g = gcd(numer, denom);
+        let sign = if denom < 0 { -1 } else { 1 };
+        let numer = i64::try_from(sign * numer / g).ok()?;
+        let denom = i64::try_from(sign * denom / g).ok()?;
+        Some(Rational { numer, denom })
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(n: i64) -> Rational {
+        Rational::from_integer(n)
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Rational) -> Ordering {
+        (self.numer as i128 * other.denom as i128).cmp(&(other.numer as i128 * self.denom as i128))
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+    fn add(self, rhs: Rational) -> Rational {
+        self.checked_add(rhs)
+            .expect("Rational overflow in addition")
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+    fn sub(self, rhs: Rational) -> Rational {
+        self.checked_sub(rhs)
+            .expect("Rational overflow in subtraction")
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+    fn mul(self, rhs: Rational) -> Rational {
+        self.checked_mul(rhs)
+            .expect("Rational overflow in multiplication")
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+    fn div(self, rhs: Rational) -> Rational {
+        if rhs.numer == 0 {
+            panic!("Rational division by zero");
+        }
+        self.checked_div(rhs)
+            .expect("Rational overflow in division")
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+    fn neg(self) -> Rational {
+        Rational::new(-self.numer, self.denom)
+    }
+}
+
+impl Scalar for Rational {
+    fn zero() -> Rational {
+        Rational::from_integer(0)
+    }
+    fn one() -> Rational {
+        Rational::from_integer(1)
+    }
+    fn magnitude(&self) -> f64 {
+        self.to_f64().abs()
+    }
+    fn epsilon() -> f64 {
+        0.
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rational::Rational;
+
+    #[test]
+    fn new_rational() {
+        let q = Rational::new(10, -4);
+        assert_eq!(q.numer(), -5);
+        assert_eq!(q.denom(), 2);
+        assert_eq!(Rational::new(0, -7), Rational::from_integer(0));
+        assert_eq!(Rational::new(0, -7).denom(), 1);
+        assert!(Rational::checked_new(3, 0).is_none());
+        assert!(Rational::checked_new(i64::MIN, -1).is_none());
+        assert_eq!(Rational::from(3), Rational::new(6, 2));
+        assert!(Rational::new(6, 2).is_integer());
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_denominator() {
+        Rational::new(1, 0);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Rational::new(-3, 7).to_string(), "-3/7");
+        assert_eq!(Rational::new(14, 7).to_string(), "2");
+        assert_eq!(Rational::new(0, 3).to_string(), "0");
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = Rational::new(1, 2);
+        let b = Rational::new(-2, 3);
+        assert_eq!(a + b, Rational::new(-1, 6));
+        assert_eq!(a - b, Rational::new(7, 6));
+        assert_eq!(a * b, Rational::new(-1, 3));
+        assert_eq!(a / b, Rational::new(-3, 4));
+        assert_eq!(-b, Rational::new(2, 3));
+        assert_eq!(b.abs(), Rational::new(2, 3));
+        assert_eq!(b.recip(), Rational::new(-3, 2));
+        assert_eq!(b.to_f64(), -2. / 3.);
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        let big = Rational::from_integer(i64::MAX);
+        assert!(big.checked_add(big).is_none());
+        assert!(big.checked_mul(big).is_none());
+        assert!(Rational::from_integer(i64::MIN).checked_sub(big).is_none());
+        assert!(big.checked_div(Rational::from_integer(0)).is_none());
+        // Intermediate values overflow i64 but the result fits
+        let q = Rational::new(i64::MAX, 3).checked_mul(Rational::new(3, i64::MAX));
+        assert_eq!(q, Some(Rational::from_integer(1)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn division_by_zero() {
+        let _ = Rational::new(1, 2) / Rational::from_integer(0);
+    }
+
+    #[test]
+    fn ordering() {
+        assert!(Rational::new(1, 3) < Rational::new(1, 2));
+        assert!(Rational::new(-1, 2) < Rational::new(-1, 3));
+        let mut v = [
+            Rational::new(3, 4),
+            Rational::new(-1, 5),
+            Rational::new(2, 3),
+        ];
+        v.sort();
+        assert_eq!(v[0], Rational::new(-1, 5));
+        assert_eq!(v[2], Rational::new(3, 4));
+    }
+}
@@ -0,0 +1,104 @@
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// A number type that can be stored in a `Matrix`.
+///
+/// Implemented for f64, `Complex`, `Rational`, `Dual` and `Interval`.
+///
+/// # Examples
+/// ```
+/// use ralgeb::scalar::Scalar;
+/// assert_eq!(f64::zero() + f64::one(), 1.);
+/// assert_eq!((-2.5_f64).magnitude(), 2.5);
+/// ```
+pub trait Scalar:
+    Copy
+    + PartialEq
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    /// Returns the additive identity
+    fn zero() -> Self;
+    /// Returns the multiplicative identity
+    fn one() -> Self;
+    /// Returns the size of the value as a non-negative f64.
+    /// Used to pick pivots and to compare against tolerances.
+    fn magnitude(&self) -> f64;
+    /// Returns the relative rounding error of the type,
+    /// 0.0 for exact types such as `Rational`
+    fn epsilon() -> f64 {
+        f64::EPSILON
+    }
+}
+
+/// A real number type that geometry can be computed with.
+///
+/// Implemented for f64, `Dual` and `Interval`.
+///
+/// # Examples
+/// ```
+/// use ralgeb::scalar::Real;
+/// fn hypot<T: Real>(a: T, b: T) -> T {
+///     (a.powi(2) + b.powi(2)).sqrt()
+/// }
+/// assert_eq!(hypot(3., 4.), 5.);
+/// ```
+pub trait Real: Scalar + PartialOrd {
+    /// Converts an f64 constant into the type
+    fn from_f64(x: f64) -> Self;
+    /// Returns the square root
+    fn sqrt(self) -> Self;
+    /// Returns the value raised to an integer power
+    fn powi(self, n: i32) -> Self;
+    /// Returns the four quadrant arctangent of self (y) and x in radians
+    fn atan2(self, x: Self) -> Self;
+}
+
+impl Scalar for f64 {
+    fn zero() -> f64 {
+        0.
+    }
+    fn one() -> f64 {
+        1.
+    }
+    fn magnitude(&self) -> f64 {
+        self.abs()
+    }
+}
+
+impl Real for f64 {
+    fn from_f64(x: f64) -> f64 {
+        x
+    }
+    fn sqrt(self) -> f64 {
+        f64::sqrt(self)
+    }
+    fn powi(self, n: i32) -> f64 {
+        f64::powi(self, n)
+    }
+    fn atan2(self, x: f64) -> f64 {
+        f64::atan2(self, x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scalar::{Real, Scalar};
+    #[test]
+    fn f64_scalar() {
+        assert_eq!(f64::zero(), 0.);
+        assert_eq!(f64::one(), 1.);
+        assert_eq!((-3_f64).magnitude(), 3.);
+    }
+    #[test]
+    fn f64_real() {
+        assert_eq!(<f64 as Real>::from_f64(2.5), 2.5);
+        assert_eq!(Real::sqrt(9_f64), 3.);
+        assert_eq!(Real::powi(2_f64, -2), 0.25);
+        assert_eq!(Real::atan2(1_f64, 1.), std::f64::consts::FRAC_PI_4);
+    }
+}
@@ -0,0 +1,338 @@
+use crate::matrix::{Axis, Matrix, MatrixError};
+
+#[derive(Debug, Clone)]
+/// The result of a principal component analysis
+pub struct Pca {
+    /// The column means that were subtracted from the data
+    pub mean: Vec<f64>,
+    /// The principal axes, one per row, in decreasing order of variance
+    pub components: Matrix,
+    /// The variance of the data along each component
+    pub explained_variance: Vec<f64>,
+    /// The fraction of the total variance explained by each component
+    pub explained_variance_ratio: Vec<f64>,
+    /// The centered data expressed in the components, one row per observation
+    pub projected: Matrix,
+}
+
+/// Returns the mean of every column, i.e. of every variable.
+/// The offsets from the first observation are averaged, so the mean
+/// of a constant column is exact and it centers to exactly zero.
+///
+/// # Arguments
+/// `data` - One observation per row and one variable per column.
+///
+/// # Examples
+/// ```
+/// use ralgeb::matrix::Matrix;
+/// use ralgeb::stats;
+/// let data = Matrix::new_mat(vec![vec![1., 10.], vec![3., 20.]]);
+/// assert_eq!(stats::column_means(&data), vec![2., 15.]);
+/// ```
+pub fn column_means(data: &Matrix) -> Vec<f64> {
+    if data.rows == 0 {
+        return data.mean_axis(Axis::Col);
+    }
+    (0..data.cols)
+        .map(|c| {
+            let first = data[(0, c)];
+            let offsets: f64 = (0..data.rows).map(|r| data[(r, c)] - first).sum();
+            first + offsets / data.rows as f64
+        })
+        .collect()
+}
+
+/// Returns the data with the mean of every column subtracted
+///
+/// # Examples
+/// ```
+/// use ralgeb::matrix::Matrix;
+/// use ralgeb::stats;
+/// let data = Matrix::new_mat(vec![vec![1., 10.], vec![3., 20.]]);
+/// let c = stats::center(&data);
+/// assert_eq!(c.get_row(0).unwrap(), vec![-1., -5.]);
+/// ```
+pub fn center(data: &Matrix) -> Matrix {
+    let means = column_means(data);
+    Matrix::from_fn(data.rows, data.cols, |r, c| data[(r, c)] - means[c])
+}
+
+/// Returns the sample standard deviation of every column
+///
+/// # Examples
+/// ```
+/// use ralgeb::matrix::Matrix;
+/// use ralgeb::stats;
+/// let data = Matrix::new_mat(vec![vec![1.], vec![3.]]);
+/// assert_eq!(stats::column_std_devs(&data).unwrap(), vec![2f64.sqrt()]);
+/// ```
+pub fn column_std_devs(data: &Matrix) -> Result<Vec<f64>, MatrixError> {
+    let cov = covariance(data)?;
+    Ok((0..data.cols).map(|i| cov[(i, i)].sqrt()).collect())
+}
+
+/// Returns the data with every column centered and scaled
+/// to unit sample standard deviation
+///
+/// # Examples
+/// ```
+/// use ralgeb::matrix::Matrix;
+/// use ralgeb::stats;
+/// let data = Matrix::new_mat(vec![vec![1., 10.], vec![3., 30.]]);
+/// let z = stats::standardize(&data).unwrap();
+/// assert_eq!(z.get_col(1), z.get_col(0));
+/// ```
+pub fn standardize(data: &Matrix) -> Result<Matrix, MatrixError> {
+    let std_devs = column_std_devs(data)?;
+    constant_check(&std_devs)?;
+    let centered = center(data);
+    Ok(Matrix::from_fn(data.rows, data.cols, |r, c| {
+        centered[(r, c)] / std_devs[c]
+    }))
+}
+
+/// Returns the sample covariance matrix of the columns,
+/// normalized by the number of observations minus one
+///
+/// # Examples
+/// ```
+/// use ralgeb::matrix::Matrix;
+/// use ralgeb::stats;
+/// let data = Matrix::new_mat(vec![vec![1., 2.], vec![2., 4.], vec![3., 6.]]);
+/// let cov = stats::covariance(&data).unwrap();
+/// assert_eq!(cov.get_row(0).unwrap(), vec![1., 2.]);
+/// assert_eq!(cov.get_row(1).unwrap(), vec![2., 4.]);
+/// ```
+pub fn covariance(data: &Matrix) -> Result<Matrix, MatrixError> {
+    if data.rows < 2 {
+        return Err(MatrixError::new(
+            "At least 2 observations are needed".to_string(),
+        ));
+    }
+    let centered = center(data);
+    let n = data.cols;
+    let mut cov = Matrix::new(n, n);
+    for i in 0..n {
+        for j in i..n {
+            let s: f64 = (0..data.rows)
+                .map(|r| centered[(r, i)] * centered[(r, j)])
+                .sum();
+            cov[(i, j)] = s / (data.rows - 1) as f64;
+            cov[(j, i)] = cov[(i, j)];
+        }
+    }
+    Ok(cov)
+}
+
+/// Returns the Pearson correlation matrix of the columns
+///
+/// # Examples
+/// ```
+/// use ralgeb::matrix::Matrix;
+/// use ralgeb::stats;
+/// let data = Matrix::new_mat(vec![vec![1., 3.], vec![2., 2.], vec![3., 1.]]);
+/// let corr = stats::correlation(&data).unwrap();
+/// assert_eq!(corr.get_row(0).unwrap(), vec![1., -1.]);
+/// ```
+pub fn correlation(data: &Matrix) -> Result<Matrix, MatrixError> {
+    let cov = covariance(data)?;
+    let std_devs: Vec<f64> = (0..data.cols).map(|i| cov[(i, i)].sqrt()).collect();
+    constant_check(&std_devs)?;
+    Ok(Matrix::from_fn(data.cols, data.cols, |i, j| {
+        if i == j {
+            1.
+        } else {
+            (cov[(i, j)] / (std_devs[i] * std_devs[j])).clamp(-1., 1.)
+        }
+    }))
+}
+
+/// Runs a principal component analysis on the covariance matrix
+///
+/// # Arguments
+/// `data` - One observation per row and one variable per column.
+/// `n_components` - The number of components to keep, at most the number of columns.
+///
+/// # Examples
+/// ```
+/// use ralgeb::matrix::Matrix;
+/// use ralgeb::stats;
+/// // Points along the line y = x
+/// let data = Matrix::new_mat(vec![vec![1., 1.], vec![2., 2.], vec![3., 3.]]);
+/// let pca = stats::pca(&data, 1).unwrap();
+/// assert!((pca.explained_variance_ratio[0] - 1.).abs() < 1e-12);
+/// let axis = pca.components.get_row(0).unwrap();
+/// assert!((axis[0] - 0.5f64.sqrt()).abs() < 1e-12);
+/// ```
+pub fn pca(data: &Matrix, n_components: usize) -> Result<Pca, MatrixError> {
+    if n_components > data.cols {
+        return Err(MatrixError::new(format!(
+            "Cannot keep {} components of {} variables",
+            n_components, data.cols
+        )));
+    }
+    let cov = covariance(data)?;
+    let (values, vectors) = cov.symmetric_eigen()?;
+    let total: f64 = values.iter().map(|v| v.max(0.)).sum();
+    let mut components = Matrix::new(n_components, data.cols);
+    for k in 0..n_components {
+        let mut axis = vectors.get_col(k);
+        // Fix the sign so that the largest entry is positive
+        let largest = axis
+            .iter()
+            .fold(0., |m: f64, x| if x.abs() > m.abs() { *x } else { m });
+        if largest < 0. {
+            axis.iter_mut().for_each(|x| *x = -*x);
+        }
+        for (c, x) in axis.into_iter().enumerate() {
+            components[(k, c)] = x;
+        }
+    }
+    let explained_variance: Vec<f64> = values[..n_components].iter().map(|v| v.max(0.)).collect();
+    let explained_variance_ratio = explained_variance
+        .iter()
+        .map(|v| if total > 0. { v / total } else { 0. })
+        .collect();
+    let mean = column_means(data);
+    let projected = project(&center(data), &components)?;
+    Ok(Pca {
+        mean,
+        components,
+        explained_variance,
+        explained_variance_ratio,
+        projected,
+    })
+}
+
+impl Pca {
+    /// Projects new observations onto the components
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// use ralgeb::stats;
+    /// let data = Matrix::new_mat(vec![vec![1., 1.], vec![2., 2.], vec![3., 3.]]);
+    /// let pca = stats::pca(&data, 1).unwrap();
+    /// let p = pca.transform(&Matrix::new_mat(vec![vec![2., 2.]])).unwrap();
+    /// assert!(p[(0, 0)].abs() < 1e-12);
+    /// ```
+    pub fn transform(&self, data: &Matrix) -> Result<Matrix, MatrixError> {
+        if data.cols != self.mean.len() {
+            return Err(MatrixError::new(format!(
+                "Expected {} variables but got {}",
+                self.mean.len(),
+                data.cols
+            )));
+        }
+        let centered = Matrix::from_fn(data.rows, data.cols, |r, c| data[(r, c)] - self.mean[c]);
+        project(&centered, &self.components)
+    }
+}
+
+fn project(centered: &Matrix, components: &Matrix) -> Result<Matrix, MatrixError> {
+    Matrix::multiply(centered, &Matrix::transpose(components.clone()))
+}
+
+fn constant_check(std_devs: &[f64]) -> Result<(), MatrixError> {
+    match std_devs.iter().position(|s| *s == 0.) {
+        Some(c) => Err(MatrixError::new(format!("Column {} is constant", c))),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::approx::{ApproxEq, Tolerance};
+    use crate::matrix;
+    use crate::random::{self, Rng};
+    use crate::stats;
+
+    fn sample() -> matrix::Matrix {
+        matrix::Matrix::new_mat(vec![
+            vec![2.5, 2.4, 1.],
+            vec![0.5, 0.7, 3.],
+            vec![2.2, 2.9, 2.],
+            vec![1.9, 2.2, 5.],
+            vec![3.1, 3.0, 1.],
+            vec![2.3, 2.7, 4.],
+        ])
+    }
+
+    #[test]
+    fn covariance_and_correlation() {
+        let data = sample();
+        let cov = stats::covariance(&data).unwrap();
+        assert!(cov.is_symmetric(Tolerance::exact()));
+        // Variance of the first column computed by hand
+        let x = data.get_col(0);
+        let m = x.iter().sum::<f64>() / 6.;
+        let var = x.iter().map(|v| (v - m).powi(2)).sum::<f64>() / 5.;
+        assert!((cov[(0, 0)] - var).abs() < 1e-12);
+
+        let corr = stats::correlation(&data).unwrap();
+        assert!(corr.get_principal().unwrap().iter().all(|d| *d == 1.));
+        assert!(corr[(0, 1)] > 0.9);
+        let z = stats::standardize(&data).unwrap();
+        let from_z = stats::covariance(&z).unwrap();
+        assert!(from_z.approx_eq(&corr, Tolerance::default()));
+        assert!(stats::column_means(&z).iter().all(|m| m.abs() < 1e-12));
+
+        let one = matrix::Matrix::new_mat(vec![vec![1., 2.]]);
+        assert!(stats::covariance(&one).is_err());
+        let constant = matrix::Matrix::new_mat(vec![vec![1., 2.], vec![1., 3.]]);
+        assert!(stats::correlation(&constant).is_err());
+        assert!(stats::standardize(&constant).is_err());
+
+        // 0.1 is not representable, so a naive mean of a column of
+        // 0.1s differs from 0.1 and leaves a tiny standard deviation
+        let tenths = matrix::Matrix::from_fn(3, 2, |r, c| if c == 0 { 0.1 } else { r as f64 });
+        assert_eq!(stats::column_std_devs(&tenths).unwrap()[0], 0.);
+        assert!(stats::correlation(&tenths).is_err());
+        assert!(stats::standardize(&tenths).is_err());
+        assert!(stats::pca(&tenths, 2).is_ok());
+    }
+
+    #[test]
+    fn pca() {
+        let data = sample();
+        let full = stats::pca(&data, 3).unwrap();
+        let cov = stats::covariance(&data).unwrap();
+        let total: f64 = full.explained_variance.iter().sum();
+        assert!((total - cov.trace().unwrap()).abs() < 1e-12);
+        assert!((full.explained_variance_ratio.iter().sum::<f64>() - 1.).abs() < 1e-12);
+        let comps = matrix::Matrix::transpose(full.components.clone());
+        assert!(comps.is_orthogonal(Tolerance::default()));
+
+        // The projections are uncorrelated with the explained variances
+        let proj_cov = stats::covariance(&full.projected).unwrap();
+        let expected = matrix::Matrix::from_diagonal(&full.explained_variance);
+        assert!(proj_cov.approx_eq(&expected, Tolerance::new(1e-12, 1e-9)));
+
+        let two = stats::pca(&data, 2).unwrap();
+        assert_eq!((two.projected.rows, two.projected.cols), (6, 2));
+        let again = two.transform(&data).unwrap();
+        assert!(again.approx_eq(&two.projected, Tolerance::default()));
+        assert!(two.transform(&matrix::Matrix::new(1, 2)).is_err());
+        assert!(stats::pca(&data, 4).is_err());
+    }
+
+    #[test]
+    fn pca_recovers_structure() {
+        // Noise stretched along a known direction
+        let mut rng = Rng::new(17);
+        let z = random::normal(500, 2, 0., 1., &mut rng).unwrap();
+        let (c, s) = (0.6, 0.8);
+        let data = matrix::Matrix::from_fn(500, 2, |r, k| {
+            let (a, b) = (10. * z[(r, 0)], z[(r, 1)]);
+            if k == 0 {
+                c * a - s * b
+            } else {
+                s * a + c * b
+            }
+        });
+        let pca = stats::pca(&data, 1).unwrap();
+        let axis = pca.components.get_row(0).unwrap();
+        assert!((axis[0] - c).abs() < 0.01 && (axis[1] - s).abs() < 0.01);
+        assert!(pca.explained_variance_ratio[0] > 0.98);
+    }
+}
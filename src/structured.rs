@@ -0,0 +1,1126 @@
+use crate::matrix::{Matrix, MatrixError};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Selects the upper or lower triangle of a matrix
+pub enum Triangle {
+    Upper,
+    Lower,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents an n x n diagonal matrix by its diagonal only
+pub struct Diagonal {
+    pub n: usize,
+    diag: Vec<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents an n x n upper or lower triangular matrix.
+/// Only the elements inside the triangle are stored.
+pub struct Triangular {
+    pub n: usize,
+    triangle: Triangle,
+    rows: Vec<Vec<f64>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents an n x n banded matrix with `lower` sub-diagonals and
+/// `upper` super-diagonals. Each row stores lower + upper + 1 elements.
+pub struct Banded {
+    pub n: usize,
+    pub lower: usize,
+    pub upper: usize,
+    rows: Vec<Vec<f64>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents an n x n tridiagonal matrix by its three diagonals
+pub struct Tridiagonal {
+    pub n: usize,
+    lower: Vec<f64>,
+    diag: Vec<f64>,
+    upper: Vec<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents an n x n symmetric matrix.
+/// Only the lower triangle is stored.
+pub struct Symmetric {
+    pub n: usize,
+    rows: Vec<Vec<f64>>,
+}
+
+fn length_check(expected: usize, got: usize) -> Result<(), MatrixError> {
+    if expected == got {
+        Ok(())
+    } else {
+        Err(MatrixError::new(format!(
+            "Expected a vector of length {} but got {}",
+            expected, got
+        )))
+    }
+}
+
+fn row_length_check(row: usize, expected: usize, got: usize) -> Result<(), MatrixError> {
+    if expected == got {
+        Ok(())
+    } else {
+        Err(MatrixError::new(format!(
+            "Row {} should have {} elements but has {}",
+            row, expected, got
+        )))
+    }
+}
+
+fn square_check(m: &Matrix) -> Result<(), MatrixError> {
+    if m.is_square() {
+        Ok(())
+    } else {
+        Err(MatrixError::new(
+            "The matrix is not a square matrix".to_string(),
+        ))
+    }
+}
+
+fn zero_pivot(row: usize) -> MatrixError {
+    MatrixError::new(format!("Zero pivot in row {}", row))
+}
+
+impl Diagonal {
+    /// Creates a diagonal matrix from its diagonal
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::Diagonal;
+    /// let d = Diagonal::new(vec![1., 2., 3.]);
+    /// assert_eq!(d.get(1, 1), 2.);
+    /// assert_eq!(d.get(0, 1), 0.);
+    /// ```
+    pub fn new(diag: Vec<f64>) -> Diagonal {
+        Diagonal {
+            n: diag.len(),
+            diag,
+        }
+    }
+
+    /// Returns the element at (row, col)
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::Diagonal;
+    /// let d = Diagonal::new(vec![1., 2.]);
+    /// assert_eq!(d.get(1, 1), 2.);
+    /// assert_eq!(d.get(1, 0), 0.);
+    /// ```
+    pub fn get(&self, row: usize, col: usize) -> f64 {
+        if row == col {
+            self.diag[row]
+        } else {
+            0.
+        }
+    }
+
+    /// Returns the diagonal
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::Diagonal;
+    /// assert_eq!(Diagonal::new(vec![1., 2.]).diagonal(), &[1., 2.]);
+    /// ```
+    pub fn diagonal(&self) -> &[f64] {
+        &self.diag
+    }
+
+    /// Converts into a dense `Matrix`
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::Diagonal;
+    /// let m = Diagonal::new(vec![1., 2.]).to_matrix();
+    /// assert_eq!(m.get_row(1).unwrap(), vec![0., 2.]);
+    /// ```
+    pub fn to_matrix(&self) -> Matrix {
+        Matrix::from_diagonal(&self.diag)
+    }
+
+    /// Multiplies the matrix with a vector
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::Diagonal;
+    /// let d = Diagonal::new(vec![1., 2.]);
+    /// assert_eq!(d.multiply_vec(&[3., 4.]).unwrap(), vec![3., 8.]);
+    /// ```
+    pub fn multiply_vec(&self, x: &[f64]) -> Result<Vec<f64>, MatrixError> {
+        length_check(self.n, x.len())?;
+        Ok(self.diag.iter().zip(x).map(|(d, x)| d * x).collect())
+    }
+
+    /// Multiplies the diagonal matrix with a dense matrix, scaling its rows
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// use ralgeb::structured::Diagonal;
+    /// let d = Diagonal::new(vec![2., 3.]);
+    /// let m = d.multiply(&Matrix::ones(2, 2)).unwrap();
+    /// assert_eq!(m.get_col(0), vec![2., 3.]);
+    /// ```
+    pub fn multiply(&self, m: &Matrix) -> Result<Matrix, MatrixError> {
+        length_check(self.n, m.rows)?;
+        Ok(Matrix::from_fn(m.rows, m.cols, |r, c| {
+            self.diag[r] * m[(r, c)]
+        }))
+    }
+
+    /// Solves Dx = b
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::Diagonal;
+    /// let d = Diagonal::new(vec![2., 4.]);
+    /// assert_eq!(d.solve(&[1., 1.]).unwrap(), vec![0.5, 0.25]);
+    /// assert!(Diagonal::new(vec![0.]).solve(&[1.]).is_err());
+    /// ```
+    pub fn solve(&self, b: &[f64]) -> Result<Vec<f64>, MatrixError> {
+        length_check(self.n, b.len())?;
+        self.diag
+            .iter()
+            .zip(b)
+            .enumerate()
+            .map(|(i, (d, b))| {
+                if *d == 0. {
+                    Err(zero_pivot(i))
+                } else {
+                    Ok(b / d)
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the inverse, which is again diagonal
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::Diagonal;
+    /// let d = Diagonal::new(vec![2., 4.]).inverse().unwrap();
+    /// assert_eq!(d.diagonal(), &[0.5, 0.25]);
+    /// ```
+    pub fn inverse(&self) -> Result<Diagonal, MatrixError> {
+        Ok(Diagonal::new(self.solve(&vec![1.; self.n])?))
+    }
+
+    /// Returns the determinant, the product of the diagonal
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::Diagonal;
+    /// assert_eq!(Diagonal::new(vec![2., 3., 4.]).determinant(), 24.);
+    /// ```
+    pub fn determinant(&self) -> f64 {
+        self.diag.iter().product()
+    }
+}
+
+impl Triangular {
+    /// Creates a triangular matrix from the packed rows of its triangle
+    ///
+    /// # Arguments
+    /// `rows` - Row r holds the elements of the triangle in that row, so
+    /// columns r..n for `Upper` and columns 0..=r for `Lower`.
+    /// `triangle` - The triangle that is stored.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::{Triangle, Triangular};
+    /// let u = Triangular::new(vec![vec![1., 2.], vec![4.]], Triangle::Upper).unwrap();
+    /// assert_eq!(u.get(0, 1), 2.);
+    /// assert_eq!(u.get(1, 1), 4.);
+    /// assert!(Triangular::new(vec![vec![1.], vec![4.]], Triangle::Upper).is_err());
+    /// ```
+    pub fn new(rows: Vec<Vec<f64>>, triangle: Triangle) -> Result<Triangular, MatrixError> {
+        let n = rows.len();
+        for (r, row) in rows.iter().enumerate() {
+            let expected = match triangle {
+                Triangle::Upper => n - r,
+                Triangle::Lower => r + 1,
+            };
+            row_length_check(r, expected, row.len())?;
+        }
+        Ok(Triangular { n, triangle, rows })
+    }
+
+    /// Creates a triangular matrix from one triangle of a square matrix.
+    /// Elements outside the triangle are ignored.
+    ///
+    /// # Arguments
+    /// `m` - A square matrix.
+    /// `triangle` - The triangle to keep.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// use ralgeb::structured::{Triangle, Triangular};
+    /// let m = Matrix::new_mat(vec![vec![1., 2.], vec![3., 4.]]);
+    /// let u = Triangular::from_matrix(&m, Triangle::Upper).unwrap();
+    /// assert_eq!(u.get(0, 1), 2.);
+    /// assert_eq!(u.get(1, 0), 0.);
+    /// ```
+    pub fn from_matrix(m: &Matrix, triangle: Triangle) -> Result<Triangular, MatrixError> {
+        square_check(m)?;
+        let n = m.rows;
+        let rows = (0..n)
+            .map(|r| {
+                let cols = match triangle {
+                    Triangle::Upper => r..n,
+                    Triangle::Lower => 0..r + 1,
+                };
+                cols.map(|c| m[(r, c)]).collect()
+            })
+            .collect();
+        Ok(Triangular { n, triangle, rows })
+    }
+
+    /// Returns which triangle is stored
+    pub fn triangle(&self) -> Triangle {
+        self.triangle
+    }
+
+    /// Returns the element at (row, col)
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::{Triangle, Triangular};
+    /// let l = Triangular::new(vec![vec![1.], vec![2., 3.]], Triangle::Lower).unwrap();
+    /// assert_eq!(l.get(1, 0), 2.);
+    /// assert_eq!(l.get(0, 1), 0.);
+    /// ```
+    pub fn get(&self, row: usize, col: usize) -> f64 {
+        match self.triangle {
+            Triangle::Upper if col >= row => self.rows[row][col - row],
+            Triangle::Lower if col <= row => self.rows[row][col],
+            _ => 0.,
+        }
+    }
+
+    /// Converts into a dense `Matrix`
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::{Triangle, Triangular};
+    /// let u = Triangular::new(vec![vec![1., 2.], vec![3.]], Triangle::Upper).unwrap();
+    /// let m = u.to_matrix();
+    /// assert_eq!(m.get_row(0).unwrap(), vec![1., 2.]);
+    /// assert_eq!(m.get_row(1).unwrap(), vec![0., 3.]);
+    /// ```
+    pub fn to_matrix(&self) -> Matrix {
+        Matrix::from_fn(self.n, self.n, |r, c| self.get(r, c))
+    }
+
+    /// Returns the transpose, which lies in the other triangle
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// use ralgeb::structured::{Triangle, Triangular};
+    /// let m = Matrix::new_mat(vec![vec![1., 2.], vec![3., 4.]]);
+    /// let l = Triangular::from_matrix(&m, Triangle::Upper).unwrap().transpose();
+    /// assert_eq!(l.triangle(), Triangle::Lower);
+    /// assert_eq!(l.get(1, 0), 2.);
+    /// ```
+    pub fn transpose(&self) -> Triangular {
+        let triangle = match self.triangle {
+            Triangle::Upper => Triangle::Lower,
+            Triangle::Lower => Triangle::Upper,
+        };
+        let n = self.n;
+        let rows = (0..n)
+            .map(|r| {
+                let cols = match triangle {
+                    Triangle::Upper => r..n,
+                    Triangle::Lower => 0..r + 1,
+                };
+                cols.map(|c| self.get(c, r)).collect()
+            })
+            .collect();
+        Triangular { n, triangle, rows }
+    }
+
+    /// Multiplies the matrix with a vector
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::{Triangle, Triangular};
+    /// let u = Triangular::new(vec![vec![1., 2.], vec![3.]], Triangle::Upper).unwrap();
+    /// assert_eq!(u.multiply_vec(&[1., 1.]).unwrap(), vec![3., 3.]);
+    /// ```
+    pub fn multiply_vec(&self, x: &[f64]) -> Result<Vec<f64>, MatrixError> {
+        length_check(self.n, x.len())?;
+        Ok(self
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(r, row)| {
+                let offset = match self.triangle {
+                    Triangle::Upper => r,
+                    Triangle::Lower => 0,
+                };
+                row.iter().zip(&x[offset..]).map(|(a, x)| a * x).sum()
+            })
+            .collect())
+    }
+
+    /// Solves Tx = b by back substitution for upper triangular
+    /// and forward substitution for lower triangular matrices
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// use ralgeb::structured::{Triangle, Triangular};
+    /// let m = Matrix::new_mat(vec![vec![2., 1.], vec![0., 4.]]);
+    /// let u = Triangular::from_matrix(&m, Triangle::Upper).unwrap();
+    /// assert_eq!(u.solve(&[4., 8.]).unwrap(), vec![1., 2.]);
+    /// ```
+    pub fn solve(&self, b: &[f64]) -> Result<Vec<f64>, MatrixError> {
+        length_check(self.n, b.len())?;
+        let n = self.n;
+        let mut x = b.to_vec();
+        match self.triangle {
+            Triangle::Upper => {
+                for r in (0..n).rev() {
+                    let row = &self.rows[r];
+                    let s: f64 = row[1..].iter().zip(&x[r + 1..]).map(|(a, x)| a * x).sum();
+                    if row[0] == 0. {
+                        return Err(zero_pivot(r));
+                    }
+                    x[r] = (x[r] - s) / row[0];
+                }
+            }
+            Triangle::Lower => {
+                for r in 0..n {
+                    let row = &self.rows[r];
+                    let s: f64 = row[..r].iter().zip(&x[..r]).map(|(a, x)| a * x).sum();
+                    if row[r] == 0. {
+                        return Err(zero_pivot(r));
+                    }
+                    x[r] = (x[r] - s) / row[r];
+                }
+            }
+        }
+        Ok(x)
+    }
+
+    /// Returns the determinant, the product of the diagonal
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::{Triangle, Triangular};
+    /// let u = Triangular::new(vec![vec![2., 9.], vec![3.]], Triangle::Upper).unwrap();
+    /// assert_eq!(u.determinant(), 6.);
+    /// ```
+    pub fn determinant(&self) -> f64 {
+        (0..self.n).map(|i| self.get(i, i)).product()
+    }
+}
+
+impl Banded {
+    /// Creates a banded matrix from the packed rows of its band
+    ///
+    /// # Arguments
+    /// `rows` - Row r holds the lower + upper + 1 elements of columns
+    /// r - lower to r + upper. Those outside the matrix are ignored.
+    /// `lower` - The number of sub-diagonals.
+    /// `upper` - The number of super-diagonals.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::Banded;
+    /// let b = Banded::new(vec![vec![0., 2., 3.], vec![1., 2., 0.]], 1, 1).unwrap();
+    /// assert_eq!(b.get(0, 1), 3.);
+    /// assert_eq!(b.get(1, 0), 1.);
+    /// assert!(Banded::new(vec![vec![2.]], 1, 1).is_err());
+    /// ```
+    pub fn new(rows: Vec<Vec<f64>>, lower: usize, upper: usize) -> Result<Banded, MatrixError> {
+        for (r, row) in rows.iter().enumerate() {
+            row_length_check(r, lower + upper + 1, row.len())?;
+        }
+        Ok(Banded {
+            n: rows.len(),
+            lower,
+            upper,
+            rows,
+        })
+    }
+
+    /// Creates a banded matrix from the band of a square matrix.
+    /// Elements outside the band are ignored.
+    ///
+    /// # Arguments
+    /// `m` - A square matrix.
+    /// `lower` - The number of sub-diagonals.
+    /// `upper` - The number of super-diagonals.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// use ralgeb::structured::Banded;
+    /// let m = Matrix::new_mat(vec![vec![1., 2., 3.], vec![4., 5., 6.], vec![7., 8., 9.]]);
+    /// let b = Banded::from_matrix(&m, 1, 0).unwrap();
+    /// assert_eq!(b.get(1, 0), 4.);
+    /// assert_eq!(b.get(0, 1), 0.);
+    /// ```
+    pub fn from_matrix(m: &Matrix, lower: usize, upper: usize) -> Result<Banded, MatrixError> {
+        square_check(m)?;
+        let n = m.rows;
+        let rows = (0..n)
+            .map(|r| {
+                (0..lower + upper + 1)
+                    .map(|k| match (r + k).checked_sub(lower) {
+                        Some(c) if c < n => m[(r, c)],
+                        _ => 0.,
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(Banded {
+            n,
+            lower,
+            upper,
+            rows,
+        })
+    }
+
+    /// Returns the element at (row, col)
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::Banded;
+    /// let b = Banded::new(vec![vec![1., 2.], vec![3., 4.], vec![5., 6.]], 0, 1).unwrap();
+    /// assert_eq!(b.get(1, 2), 4.);
+    /// assert_eq!(b.get(2, 1), 0.);
+    /// ```
+    pub fn get(&self, row: usize, col: usize) -> f64 {
+        if col + self.lower < row || col > row + self.upper {
+            0.
+        } else {
+            self.rows[row][col + self.lower - row]
+        }
+    }
+
+    /// Converts into a dense `Matrix`
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::Banded;
+    /// let b = Banded::new(vec![vec![1., 2.], vec![3., 4.]], 0, 1).unwrap();
+    /// let m = b.to_matrix();
+    /// assert_eq!(m.get_row(0).unwrap(), vec![1., 2.]);
+    /// assert_eq!(m.get_row(1).unwrap(), vec![0., 3.]);
+    /// ```
+    pub fn to_matrix(&self) -> Matrix {
+        Matrix::from_fn(self.n, self.n, |r, c| self.get(r, c))
+    }
+
+    /// Multiplies the matrix with a vector
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::Banded;
+    /// let b = Banded::new(vec![vec![0., 2., 1.], vec![1., 2., 0.]], 1, 1).unwrap();
+    /// assert_eq!(b.multiply_vec(&[1., 2.]).unwrap(), vec![4., 5.]);
+    /// ```
+    pub fn multiply_vec(&self, x: &[f64]) -> Result<Vec<f64>, MatrixError> {
+        length_check(self.n, x.len())?;
+        Ok((0..self.n)
+            .map(|r| self.band(r).map(|c| self.get(r, c) * x[c]).sum())
+            .collect())
+    }
+
+    /// Solves Ax = b by Gaussian elimination restricted to the band.
+    /// No pivoting is done, so this suits diagonally dominant or
+    /// positive definite matrices.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// use ralgeb::structured::Banded;
+    /// let m = Matrix::new_mat(vec![vec![4., 1., 0.], vec![1., 4., 1.], vec![0., 1., 4.]]);
+    /// let b = Banded::from_matrix(&m, 1, 1).unwrap();
+    /// let x = b.solve(&[5., 6., 5.]).unwrap();
+    /// assert!(x.iter().all(|v| (v - 1.).abs() < 1e-12));
+    /// ```
+    pub fn solve(&self, b: &[f64]) -> Result<Vec<f64>, MatrixError> {
+        length_check(self.n, b.len())?;
+        let n = self.n;
+        let lower = self.lower;
+        let mut a = self.rows.clone();
+        let mut x = b.to_vec();
+        for k in 0..n {
+            let pivot = a[k][lower];
+            if pivot == 0. {
+                return Err(zero_pivot(k));
+            }
+            for i in k + 1..n.min(k + lower + 1) {
+                let f = a[i][k + lower - i] / pivot;
+                for j in k..n.min(k + self.upper + 1) {
+                    a[i][j + lower - i] -= f * a[k][j + lower - k];
+                }
+                x[i] -= f * x[k];
+            }
+        }
+        for i in (0..n).rev() {
+            let s: f64 = (i + 1..n.min(i + self.upper + 1))
+                .map(|j| a[i][j + lower - i] * x[j])
+                .sum();
+            x[i] = (x[i] - s) / a[i][lower];
+        }
+        Ok(x)
+    }
+
+    fn band(&self, row: usize) -> std::ops::Range<usize> {
+        row.saturating_sub(self.lower)..self.n.min(row + self.upper + 1)
+    }
+}
+
+impl Tridiagonal {
+    /// Creates a tridiagonal matrix from its three diagonals
+    ///
+    /// # Arguments
+    /// `lower` - The sub-diagonal, of length n - 1.
+    /// `diag` - The diagonal, of length n.
+    /// `upper` - The super-diagonal, of length n - 1.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::Tridiagonal;
+    /// let t = Tridiagonal::new(vec![1.], vec![2., 3.], vec![4.]).unwrap();
+    /// assert_eq!(t.get(1, 0), 1.);
+    /// assert_eq!(t.get(0, 1), 4.);
+    /// assert!(Tridiagonal::new(vec![1., 1.], vec![2., 3.], vec![4.]).is_err());
+    /// ```
+    pub fn new(
+        lower: Vec<f64>,
+        diag: Vec<f64>,
+        upper: Vec<f64>,
+    ) -> Result<Tridiagonal, MatrixError> {
+        let n = diag.len();
+        let off = n.saturating_sub(1);
+        if lower.len() != off || upper.len() != off {
+            return Err(MatrixError::new(format!(
+                "The off diagonals of a {}x{} tridiagonal matrix should have length {}",
+                n, n, off
+            )));
+        }
+        Ok(Tridiagonal {
+            n,
+            lower,
+            diag,
+            upper,
+        })
+    }
+
+    /// Creates a tridiagonal matrix from the three central diagonals
+    /// of a square matrix
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// use ralgeb::structured::Tridiagonal;
+    /// let m = Matrix::new_mat(vec![vec![1., 2., 3.], vec![4., 5., 6.], vec![7., 8., 9.]]);
+    /// let t = Tridiagonal::from_matrix(&m).unwrap();
+    /// assert_eq!(t.get(1, 2), 6.);
+    /// assert_eq!(t.get(2, 0), 0.);
+    /// ```
+    pub fn from_matrix(m: &Matrix) -> Result<Tridiagonal, MatrixError> {
+        square_check(m)?;
+        let n = m.rows;
+        let off = n.saturating_sub(1);
+        Tridiagonal::new(
+            (0..off).map(|i| m[(i + 1, i)]).collect(),
+            (0..n).map(|i| m[(i, i)]).collect(),
+            (0..off).map(|i| m[(i, i + 1)]).collect(),
+        )
+    }
+
+    /// Returns the element at (row, col)
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::Tridiagonal;
+    /// let t = Tridiagonal::new(vec![1.], vec![2., 3.], vec![4.]).unwrap();
+    /// assert_eq!(t.get(1, 1), 3.);
+    /// assert_eq!(t.get(0, 1), 4.);
+    /// ```
+    pub fn get(&self, row: usize, col: usize) -> f64 {
+        if row == col {
+            self.diag[row]
+        } else if row == col + 1 {
+            self.lower[col]
+        } else if col == row + 1 {
+            self.upper[row]
+        } else {
+            0.
+        }
+    }
+
+    /// Converts into a dense `Matrix`
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::Tridiagonal;
+    /// let m = Tridiagonal::new(vec![1.], vec![2., 3.], vec![4.]).unwrap().to_matrix();
+    /// assert_eq!(m.get_row(0).unwrap(), vec![2., 4.]);
+    /// assert_eq!(m.get_row(1).unwrap(), vec![1., 3.]);
+    /// ```
+    pub fn to_matrix(&self) -> Matrix {
+        Matrix::from_fn(self.n, self.n, |r, c| self.get(r, c))
+    }
+
+    /// Multiplies the matrix with a vector
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::Tridiagonal;
+    /// let t = Tridiagonal::new(vec![1.], vec![2., 3.], vec![4.]).unwrap();
+    /// assert_eq!(t.multiply_vec(&[1., 1.]).unwrap(), vec![6., 4.]);
+    /// ```
+    pub fn multiply_vec(&self, x: &[f64]) -> Result<Vec<f64>, MatrixError> {
+        length_check(self.n, x.len())?;
+        Ok((0..self.n)
+            .map(|i| {
+                let mut s = self.diag[i] * x[i];
+                if i > 0 {
+                    s += self.lower[i - 1] * x[i - 1];
+                }
+                if i + 1 < self.n {
+                    s += self.upper[i] * x[i + 1];
+                }
+                s
+            })
+            .collect())
+    }
+
+    /// Solves Tx = b with the Thomas algorithm
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::Tridiagonal;
+    /// // Discrete 1D Laplacian
+    /// let t = Tridiagonal::new(vec![-1.; 2], vec![2.; 3], vec![-1.; 2]).unwrap();
+    /// let x = t.solve(&[1., 0., 1.]).unwrap();
+    /// assert!(x.iter().all(|v| (v - 1.).abs() < 1e-12));
+    /// ```
+    pub fn solve(&self, b: &[f64]) -> Result<Vec<f64>, MatrixError> {
+        let mut x = b.to_vec();
+        let mut scratch = vec![0.; self.n];
+        self.solve_in_place(&mut x, &mut scratch)?;
+        Ok(x)
+    }
+
+    /// Solves Tx = b with the Thomas algorithm without allocating.
+    /// The right hand side is overwritten with the solution.
+    ///
+    /// # Arguments
+    /// `rhs` - The right hand side b, replaced by x.
+    /// `scratch` - Working storage of length at least n.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::Tridiagonal;
+    /// let t = Tridiagonal::new(vec![1.], vec![2., 2.], vec![1.]).unwrap();
+    /// let mut scratch = vec![0.; 2];
+    /// let mut x = vec![3., 3.];
+    /// t.solve_in_place(&mut x, &mut scratch).unwrap();
+    /// assert_eq!(x, vec![1., 1.]);
+    /// ```
+    pub fn solve_in_place(&self, rhs: &mut [f64], scratch: &mut [f64]) -> Result<(), MatrixError> {
+        let n = self.n;
+        length_check(n, rhs.len())?;
+        if scratch.len() < n {
+            return Err(MatrixError::new(format!(
+                "The scratch space should have length at least {}",
+                n
+            )));
+        }
+        if n == 0 {
+            return Ok(());
+        }
+        let mut denom = self.diag[0];
+        for i in 0..n {
+            if i > 0 {
+                denom = self.diag[i] - self.lower[i - 1] * scratch[i - 1];
+                rhs[i] -= self.lower[i - 1] * rhs[i - 1];
+            }
+            if denom == 0. {
+                return Err(zero_pivot(i));
+            }
+            scratch[i] = if i + 1 < n { self.upper[i] / denom } else { 0. };
+            rhs[i] /= denom;
+        }
+        for i in (0..n - 1).rev() {
+            rhs[i] -= scratch[i] * rhs[i + 1];
+        }
+        Ok(())
+    }
+
+    /// Returns the determinant using the three term recurrence
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::Tridiagonal;
+    /// let t = Tridiagonal::new(vec![-1.; 2], vec![2.; 3], vec![-1.; 2]).unwrap();
+    /// assert_eq!(t.determinant(), 4.);
+    /// ```
+    pub fn determinant(&self) -> f64 {
+        let (mut prev, mut cur) = (1., 1.);
+        for i in 0..self.n {
+            let next = if i == 0 {
+                self.diag[0]
+            } else {
+                self.diag[i] * cur - self.lower[i - 1] * self.upper[i - 1] * prev
+            };
+            prev = cur;
+            cur = next;
+        }
+        cur
+    }
+}
+
+impl Symmetric {
+    /// Creates a symmetric matrix from the packed rows of its lower triangle
+    ///
+    /// # Arguments
+    /// `rows` - Row r holds the r + 1 elements of columns 0..=r.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::Symmetric;
+    /// let s = Symmetric::new(vec![vec![1.], vec![2., 3.]]).unwrap();
+    /// assert_eq!(s.get(0, 1), 2.);
+    /// assert!(Symmetric::new(vec![vec![1., 2.], vec![3.]]).is_err());
+    /// ```
+    pub fn new(rows: Vec<Vec<f64>>) -> Result<Symmetric, MatrixError> {
+        for (r, row) in rows.iter().enumerate() {
+            row_length_check(r, r + 1, row.len())?;
+        }
+        Ok(Symmetric {
+            n: rows.len(),
+            rows,
+        })
+    }
+
+    /// Creates a symmetric matrix from the lower triangle of a square matrix.
+    /// The upper triangle is ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// use ralgeb::structured::Symmetric;
+    /// let m = Matrix::new_mat(vec![vec![1., 9.], vec![2., 3.]]);
+    /// let s = Symmetric::from_lower(&m).unwrap();
+    /// assert_eq!(s.get(0, 1), 2.);
+    /// ```
+    pub fn from_lower(m: &Matrix) -> Result<Symmetric, MatrixError> {
+        square_check(m)?;
+        let n = m.rows;
+        let rows = (0..n)
+            .map(|r| (0..=r).map(|c| m[(r, c)]).collect())
+            .collect();
+        Ok(Symmetric { n, rows })
+    }
+
+    /// Returns the element at (row, col)
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::Symmetric;
+    /// let s = Symmetric::new(vec![vec![1.], vec![2., 3.]]).unwrap();
+    /// assert_eq!(s.get(1, 0), 2.);
+    /// assert_eq!(s.get(0, 1), 2.);
+    /// ```
+    pub fn get(&self, row: usize, col: usize) -> f64 {
+        if col <= row {
+            self.rows[row][col]
+        } else {
+            self.rows[col][row]
+        }
+    }
+
+    /// Converts into a dense `Matrix`
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::Symmetric;
+    /// let m = Symmetric::new(vec![vec![1.], vec![2., 3.]]).unwrap().to_matrix();
+    /// assert_eq!(m.get_row(0).unwrap(), vec![1., 2.]);
+    /// assert_eq!(m.get_row(1).unwrap(), vec![2., 3.]);
+    /// ```
+    pub fn to_matrix(&self) -> Matrix {
+        Matrix::from_fn(self.n, self.n, |r, c| self.get(r, c))
+    }
+
+    /// Multiplies the matrix with a vector
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::structured::Symmetric;
+    /// let s = Symmetric::new(vec![vec![1.], vec![2., 3.]]).unwrap();
+    /// assert_eq!(s.multiply_vec(&[1., 1.]).unwrap(), vec![3., 5.]);
+    /// ```
+    pub fn multiply_vec(&self, x: &[f64]) -> Result<Vec<f64>, MatrixError> {
+        length_check(self.n, x.len())?;
+        Ok((0..self.n)
+            .map(|r| (0..self.n).map(|c| self.get(r, c) * x[c]).sum())
+            .collect())
+    }
+
+    /// Returns the lower triangular Cholesky factor L with A = L Lᵀ.
+    /// Fails if the matrix is not positive definite.
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// use ralgeb::structured::Symmetric;
+    /// let m = Matrix::new_mat(vec![vec![4., 2.], vec![2., 5.]]);
+    /// let l = Symmetric::from_lower(&m).unwrap().cholesky().unwrap();
+    /// assert_eq!(l.get(1, 0), 1.);
+    /// assert_eq!(l.get(1, 1), 2.);
+    /// ```
+    pub fn cholesky(&self) -> Result<Triangular, MatrixError> {
+        let n = self.n;
+        let mut l: Vec<Vec<f64>> = Vec::with_capacity(n);
+        for i in 0..n {
+            let mut row = vec![0.; i + 1];
+            for j in 0..=i {
+                let s: f64 = if j == i {
+                    row[..j].iter().map(|v| v * v).sum()
+                } else {
+                    row[..j].iter().zip(&l[j][..j]).map(|(a, b)| a * b).sum()
+                };
+                let v = self.rows[i][j] - s;
+                if i == j {
+                    if v <= 0. {
+                        return Err(MatrixError::new(
+                            "The matrix is not positive definite".to_string(),
+                        ));
+                    }
+                    row[j] = v.sqrt();
+                } else {
+                    row[j] = v / l[j][j];
+                }
+            }
+            l.push(row);
+        }
+        Ok(Triangular {
+            n,
+            triangle: Triangle::Lower,
+            rows: l,
+        })
+    }
+
+    /// Solves Ax = b through an LDLᵀ factorization without pivoting,
+    /// which also handles symmetric indefinite matrices with non zero
+    /// leading minors
+    ///
+    /// # Examples
+    /// ```
+    /// use ralgeb::matrix::Matrix;
+    /// use ralgeb::structured::Symmetric;
+    /// let m = Matrix::new_mat(vec![vec![1., 2.], vec![2., 1.]]);
+    /// let s = Symmetric::from_lower(&m).unwrap();
+    /// let x = s.solve(&[3., 3.]).unwrap();
+    /// assert!(x.iter().all(|v| (v - 1.).abs() < 1e-12));
+    /// ```
+    pub fn solve(&self, b: &[f64]) -> Result<Vec<f64>, MatrixError> {
+        length_check(self.n, b.len())?;
+        let n = self.n;
+        // Row i of l holds the i elements of the unit lower factor
+        // left of the diagonal, the same packed layout as the matrix
+        let mut l: Vec<Vec<f64>> = Vec::with_capacity(n);
+        let mut d: Vec<f64> = Vec::with_capacity(n);
+        for (i, a) in self.rows.iter().enumerate() {
+            let mut row = Vec::with_capacity(i);
+            for (j, lj) in l.iter().enumerate() {
+                let s: f64 = (0..j).map(|k| row[k] * lj[k] * d[k]).sum();
+                row.push((a[j] - s) / d[j]);
+            }
+            let s: f64 = row.iter().zip(&d).map(|(v, d)| v * v * d).sum();
+            let pivot = a[i] - s;
+            if pivot == 0. {
+                return Err(zero_pivot(i));
+            }
+            l.push(row);
+            d.push(pivot);
+        }
+        let mut x = b.to_vec();
+        for (i, row) in l.iter().enumerate() {
+            let s: f64 = row.iter().zip(&x).map(|(l, x)| l * x).sum();
+            x[i] -= s;
+        }
+        for (v, d) in x.iter_mut().zip(&d) {
+            *v /= d;
+        }
+        for i in (0..n).rev() {
+            let s: f64 = (i + 1..n).map(|k| l[k][i] * x[k]).sum();
+            x[i] -= s;
+        }
+        Ok(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix;
+    use crate::structured::{Banded, Diagonal, Symmetric, Triangle, Triangular, Tridiagonal};
+
+    fn dense_solve(m: &matrix::Matrix, b: &[f64]) -> Vec<f64> {
+        let b = matrix::Matrix::from_fn(b.len(), 1, |r, _| b[r]);
+        m.solve(&b).unwrap().get_col(0)
+    }
+
+    fn assert_close(a: &[f64], b: &[f64], tol: f64) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b) {
+            assert!((x - y).abs() <= tol, "{:?} != {:?}", a, b);
+        }
+    }
+
+    fn sample() -> matrix::Matrix {
+        matrix::Matrix::new_mat(vec![
+            vec![6., 1., 2., 0., 0.],
+            vec![1., 7., -1., 3., 0.],
+            vec![2., -1., 8., 1., 2.],
+            vec![0., 3., 1., 9., -2.],
+            vec![0., 0., 2., -2., 5.],
+        ])
+    }
+
+    #[test]
+    fn diagonal() {
+        let d = Diagonal::new(vec![2., -1., 4.]);
+        assert_eq!(d.determinant(), -8.);
+        assert_eq!(d.to_matrix().get_principal().unwrap(), vec![2., -1., 4.]);
+        assert_eq!(d.solve(&[2., 2., 2.]).unwrap(), vec![1., -2., 0.5]);
+        assert!(d.solve(&[1.]).is_err());
+        assert!(d.multiply(&matrix::Matrix::new(2, 2)).is_err());
+    }
+
+    #[test]
+    fn triangular() {
+        let m = sample();
+        let b = [1., 2., 3., 4., 5.];
+        for triangle in [Triangle::Upper, Triangle::Lower].iter() {
+            let t = Triangular::from_matrix(&m, *triangle).unwrap();
+            let dense = t.to_matrix();
+            assert_close(&t.solve(&b).unwrap(), &dense_solve(&dense, &b), 1e-12);
+            let x = t.solve(&b).unwrap();
+            assert_close(&t.multiply_vec(&x).unwrap(), &b, 1e-12);
+            assert_eq!(t.determinant(), 6. * 7. * 8. * 9. * 5.);
+            let transposed = t.transpose().to_matrix();
+            for r in 0..5 {
+                assert_eq!(transposed.get_row(r).unwrap(), dense.get_col(r));
+            }
+        }
+        let singular = matrix::Matrix::new_mat(vec![vec![1., 1.], vec![0., 0.]]);
+        let t = Triangular::from_matrix(&singular, Triangle::Upper).unwrap();
+        assert!(t.solve(&[1., 1.]).is_err());
+        assert!(Triangular::from_matrix(&matrix::Matrix::new(2, 3), Triangle::Upper).is_err());
+
+        let packed = Triangular::new(vec![vec![2.], vec![1., 3.]], Triangle::Lower).unwrap();
+        let dense = matrix::Matrix::new_mat(vec![vec![2., 0.], vec![1., 3.]]);
+        assert_eq!(
+            packed,
+            Triangular::from_matrix(&dense, Triangle::Lower).unwrap()
+        );
+        assert!(Triangular::new(vec![vec![2.], vec![1.]], Triangle::Lower).is_err());
+    }
+
+    #[test]
+    fn banded() {
+        let m = sample();
+        let b = [1., -2., 3., 0., 5.];
+        let band = Banded::from_matrix(&m, 2, 2).unwrap();
+        assert_eq!(band.to_matrix().get_row(2).unwrap(), m.get_row(2).unwrap());
+        assert_close(&band.solve(&b).unwrap(), &dense_solve(&m, &b), 1e-12);
+        assert_close(
+            &band.multiply_vec(&b).unwrap(),
+            &matrix::Matrix::multiply(&m, &matrix::Matrix::from_fn(5, 1, |r, _| b[r]))
+                .unwrap()
+                .get_col(0),
+            1e-12,
+        );
+
+        let narrow = Banded::from_matrix(&m, 1, 0).unwrap();
+        assert_eq!(narrow.get(2, 0), 0.);
+        assert_eq!(narrow.get(2, 1), -1.);
+        assert_close(
+            &narrow.solve(&b).unwrap(),
+            &dense_solve(&narrow.to_matrix(), &b),
+            1e-12,
+        );
+
+        let rows = (0..5).map(|r| narrow.to_matrix().get_row(r).unwrap());
+        let packed: Vec<Vec<f64>> = rows
+            .enumerate()
+            .map(|(r, row)| vec![if r == 0 { 0. } else { row[r - 1] }, row[r]])
+            .collect();
+        assert_eq!(Banded::new(packed, 1, 0).unwrap(), narrow);
+        assert!(Banded::new(vec![vec![1., 2.], vec![1.]], 1, 0).is_err());
+    }
+
+    #[test]
+    fn tridiagonal() {
+        let m = sample();
+        let t = Tridiagonal::from_matrix(&m).unwrap();
+        let dense = t.to_matrix();
+        assert_eq!(dense.get_row(1).unwrap(), vec![1., 7., -1., 0., 0.]);
+        let b = [3., 1., 4., 1., 5.];
+        let x = t.solve(&b).unwrap();
+        assert_close(&x, &dense_solve(&dense, &b), 1e-12);
+        assert_close(&t.multiply_vec(&x).unwrap(), &b, 1e-12);
+        assert!((t.determinant() - dense.determinant().unwrap()).abs() < 1e-9);
+
+        let mut rhs = b.to_vec();
+        let mut scratch = vec![0.; 2];
+        assert!(t.solve_in_place(&mut rhs, &mut scratch).is_err());
+
+        let singular = Tridiagonal::new(vec![1.], vec![0., 1.], vec![1.]).unwrap();
+        assert!(singular.solve(&[1., 1.]).is_err());
+        let empty = Tridiagonal::new(vec![], vec![], vec![]).unwrap();
+        assert_eq!(empty.solve(&[]).unwrap(), Vec::<f64>::new());
+    }
+
+    #[test]
+    fn symmetric() {
+        let m = sample();
+        let s = Symmetric::from_lower(&m).unwrap();
+        assert_eq!(s.to_matrix().get_row(3).unwrap(), m.get_row(3).unwrap());
+        let b = [1., 2., 3., 4., 5.];
+        assert_close(&s.solve(&b).unwrap(), &dense_solve(&m, &b), 1e-12);
+        assert_close(&s.multiply_vec(&s.solve(&b).unwrap()).unwrap(), &b, 1e-12);
+
+        let l = s.cholesky().unwrap();
+        let llt =
+            matrix::Matrix::multiply(&l.to_matrix(), &matrix::Matrix::transpose(l.to_matrix()))
+                .unwrap();
+        for (r, c) in (0..5).flat_map(|r| (0..5).map(move |c| (r, c))) {
+            assert!((llt[(r, c)] - m[(r, c)]).abs() < 1e-12);
+        }
+
+        let indefinite = matrix::Matrix::new_mat(vec![vec![1., 2.], vec![2., 1.]]);
+        let s = Symmetric::from_lower(&indefinite).unwrap();
+        assert!(s.cholesky().is_err());
+        assert_close(
+            &s.solve(&[1., 2.]).unwrap(),
+            &dense_solve(&indefinite, &[1., 2.]),
+            1e-12,
+        );
+        assert_eq!(Symmetric::new(vec![vec![1.], vec![2., 1.]]).unwrap(), s);
+        assert!(Symmetric::new(vec![vec![1.], vec![2.]]).is_err());
+
+        // A zero leading minor has no LDLᵀ factorization without pivoting
+        let s = Symmetric::new(vec![vec![0.], vec![1., 0.]]).unwrap();
+        assert!(s.solve(&[1., 1.]).is_err());
+        assert_eq!(Symmetric::new(vec![]).unwrap().solve(&[]).unwrap(), vec![]);
+    }
+}
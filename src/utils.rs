@@ -1,3 +1,5 @@
+use std::ops::Sub;
+
 /// Returns the signed difference between the x2 and x1 coordinate
 ///
 /// # Arguments
@@ -11,6 +13,6 @@
 /// delta_coord(x2, x1);
 ///```
 ///
-pub fn delta_coord(x2: f64, x1: f64) -> f64 {
+pub fn delta_coord<T: Sub<Output = T>>(x2: T, x1: T) -> T {
     x2 - x1
 }